@@ -0,0 +1,237 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! Delegated code upgrades of paras.
+//!
+//! The manager of a para, e.g. a multisig, or Root authorizes the hash of the next validation
+//! code of the para in advance with [`Pallet::authorize_code_upgrade`]. Anyone can then supply
+//! the matching validation code with [`Pallet::apply_authorized_code_upgrade`], free of charge
+//! and without the signature of the manager, until the authorization expires.
+//!
+//! A para can be locked permanently with [`Pallet::lock_permanently`], which applies the lock of
+//! `Registrar` and rejects any further authorization. Since the para itself could still remove
+//! the lock of `Registrar`, the runtime filters the `Registrar` calls of locked paras with
+//! [`PermanentLockFilter`].
+
+pub use pallet_code_upgrade_authorization::*;
+
+#[frame_support::pallet]
+pub mod pallet_code_upgrade_authorization {
+	use frame_support::{pallet_prelude::*, traits::Contains};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use primitives::{Id as ParaId, ValidationCode, ValidationCodeHash};
+	use runtime_common::{
+		paras_registrar::{self, WeightInfo as _},
+		traits::Registrar,
+	};
+	use sp_runtime::traits::Saturating;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + paras_registrar::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The number of blocks an authorization is valid for.
+		#[pallet::constant]
+		type AuthorizationPeriod: Get<BlockNumberFor<Self>>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// A code upgrade authorized for a para.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct AuthorizedCode<BlockNumber> {
+		/// The hash of the authorized validation code.
+		pub code_hash: ValidationCodeHash,
+		/// The block from which on the authorization is expired.
+		pub expires_at: BlockNumber,
+	}
+
+	/// The code upgrades authorized per para.
+	#[pallet::storage]
+	pub type Authorizations<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, AuthorizedCode<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The paras locked permanently.
+	#[pallet::storage]
+	pub type PermanentlyLocked<T: Config> = StorageMap<_, Twox64Concat, ParaId, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A code upgrade of a para was authorized.
+		CodeUpgradeAuthorized {
+			para: ParaId,
+			code_hash: ValidationCodeHash,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// An authorized code upgrade of a para was scheduled.
+		AuthorizedCodeUpgradeApplied { para: ParaId, code_hash: ValidationCodeHash },
+		/// The code upgrade authorization of a para expired and was removed.
+		AuthorizationExpired { para: ParaId, code_hash: ValidationCodeHash },
+		/// A para was locked permanently.
+		PermanentlyLocked { para: ParaId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The origin is neither Root nor the manager of an unlocked para.
+		NotManager,
+		/// The para is locked permanently.
+		ParaPermanentlyLocked,
+		/// No code upgrade is authorized for the para.
+		NotAuthorized,
+		/// The validation code does not match the authorized code hash.
+		CodeMismatch,
+		/// The authorization expired.
+		AuthorizationExpired,
+		/// The authorization has not expired yet.
+		AuthorizationNotExpired,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Authorize the validation code with `code_hash` as the next code of `para`, replacing
+		/// any previous authorization.
+		///
+		/// The origin must be Root or the manager of `para`, while the para is not locked.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 1))]
+		pub fn authorize_code_upgrade(
+			origin: OriginFor<T>,
+			para: ParaId,
+			code_hash: ValidationCodeHash,
+		) -> DispatchResult {
+			Self::ensure_root_or_manager(origin, para)?;
+
+			let expires_at = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::AuthorizationPeriod::get());
+			Authorizations::<T>::insert(para, AuthorizedCode { code_hash, expires_at });
+			Self::deposit_event(Event::<T>::CodeUpgradeAuthorized { para, code_hash, expires_at });
+			Ok(())
+		}
+
+		/// Schedule the code upgrade of `para` to `new_code`, which must match the authorized
+		/// code hash.
+		///
+		/// The call is free if it succeeds.
+		#[pallet::call_index(1)]
+		#[pallet::weight(
+			<T as paras_registrar::Config>::WeightInfo::schedule_code_upgrade(new_code.0.len() as u32)
+				.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+		)]
+		pub fn apply_authorized_code_upgrade(
+			origin: OriginFor<T>,
+			para: ParaId,
+			new_code: ValidationCode,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(!PermanentlyLocked::<T>::contains_key(para), Error::<T>::ParaPermanentlyLocked);
+			let authorized = Authorizations::<T>::get(para).ok_or(Error::<T>::NotAuthorized)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < authorized.expires_at,
+				Error::<T>::AuthorizationExpired
+			);
+			ensure!(new_code.hash() == authorized.code_hash, Error::<T>::CodeMismatch);
+
+			Authorizations::<T>::remove(para);
+			paras_registrar::Pallet::<T>::schedule_code_upgrade(
+				RawOrigin::Root.into(),
+				para,
+				new_code,
+			)?;
+			Self::deposit_event(Event::<T>::AuthorizedCodeUpgradeApplied {
+				para,
+				code_hash: authorized.code_hash,
+			});
+			Ok(Pays::No.into())
+		}
+
+		/// Remove the expired authorization of `para`.
+		///
+		/// The call is free if it succeeds.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn remove_expired_authorization(
+			origin: OriginFor<T>,
+			para: ParaId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let authorized = Authorizations::<T>::get(para).ok_or(Error::<T>::NotAuthorized)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= authorized.expires_at,
+				Error::<T>::AuthorizationNotExpired
+			);
+
+			Authorizations::<T>::remove(para);
+			Self::deposit_event(Event::<T>::AuthorizationExpired {
+				para,
+				code_hash: authorized.code_hash,
+			});
+			Ok(Pays::No.into())
+		}
+
+		/// Lock `para` permanently, removing any authorization.
+		///
+		/// The origin must be Root or the manager of `para`, while the para is not locked.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+		pub fn lock_permanently(origin: OriginFor<T>, para: ParaId) -> DispatchResult {
+			Self::ensure_root_or_manager(origin, para)?;
+
+			paras_registrar::Pallet::<T>::apply_lock(para);
+			Authorizations::<T>::remove(para);
+			PermanentlyLocked::<T>::insert(para, ());
+			Self::deposit_event(Event::<T>::PermanentlyLocked { para });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn ensure_root_or_manager(origin: OriginFor<T>, para: ParaId) -> DispatchResult {
+			ensure!(!PermanentlyLocked::<T>::contains_key(para), Error::<T>::ParaPermanentlyLocked);
+			if ensure_root(origin.clone()).is_ok() {
+				return Ok(())
+			}
+
+			let who = ensure_signed(origin)?;
+			let locked =
+				paras_registrar::Paras::<T>::get(para).map_or(true, |info| info.is_locked());
+			ensure!(
+				!locked && paras_registrar::Pallet::<T>::manager_of(para) == Some(who),
+				Error::<T>::NotManager
+			);
+			Ok(())
+		}
+	}
+
+	/// Rejects the `Registrar` calls that would get around the permanent lock of a para: removing
+	/// the lock, and scheduling a code upgrade or setting the head of the para.
+	pub struct PermanentLockFilter<T>(PhantomData<T>);
+
+	impl<T: Config> Contains<paras_registrar::Call<T>> for PermanentLockFilter<T> {
+		fn contains(call: &paras_registrar::Call<T>) -> bool {
+			match call {
+				paras_registrar::Call::remove_lock { para } |
+				paras_registrar::Call::schedule_code_upgrade { para, .. } |
+				paras_registrar::Call::set_current_head { para, .. } =>
+					!PermanentlyLocked::<T>::contains_key(para),
+				_ => true,
+			}
+		}
+	}
+}
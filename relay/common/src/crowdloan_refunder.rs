@@ -0,0 +1,267 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! Automated refunds of expired crowdloans.
+//!
+//! A crowdloan fund expires once it can no longer win an auction and its lease, if any, ended, so
+//! the raised funds are free in the fund account. Instead of relying on someone calling
+//! `Crowdloan::refund` and `Crowdloan::dissolve` for every such fund, this pallet walks the funds
+//! in `on_idle` with a cursor, refunds the contributors of expired funds in batches of
+//! `RemoveKeysLimit` contributions and dissolves the funds once they are empty, which returns the
+//! deposit to the depositor.
+//!
+//! Once a sweep completed, the funds are not swept again until a fund may have expired since: at
+//! the next lease period start, at the end of a fund which did not expire yet, once a fund was
+//! created, or once the `lease_retirement` pallet unreserved a deposit with
+//! [`Pallet::sweep_again`].
+
+pub use pallet_crowdloan_refunder::*;
+
+#[frame_support::pallet]
+pub mod pallet_crowdloan_refunder {
+	use frame_support::{pallet_prelude::*, storage::with_storage_layer, traits::Currency};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use primitives::Id as ParaId;
+	use runtime_common::{
+		crowdloan::{self, FundInfo, WeightInfo as _},
+		traits::Auctioneer,
+	};
+	use sp_runtime::traits::{CheckedSub, Saturating, Zero};
+
+	type CurrencyOf<T> =
+		<<T as crowdloan::Config>::Auctioneer as Auctioneer<BlockNumberFor<T>>>::Currency;
+	type LeasePeriodOf<T> =
+		<<T as crowdloan::Config>::Auctioneer as Auctioneer<BlockNumberFor<T>>>::LeasePeriod;
+	pub type BalanceOf<T> =
+		<CurrencyOf<T> as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type FundInfoOf<T> = FundInfo<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		BlockNumberFor<T>,
+		LeasePeriodOf<T>,
+	>;
+
+	/// The crowdloan funds, which `crowdloan` does not expose for iteration.
+	#[frame_support::storage_alias]
+	pub type Funds<T: crowdloan::Config> =
+		StorageMap<crowdloan::Pallet<T>, Twox64Concat, ParaId, FundInfoOf<T>>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + crowdloan::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The progress of the sweeps over the funds.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum Sweep<BlockNumber> {
+		/// A sweep is ongoing.
+		Ongoing {
+			/// The para of the last fund fully handled, if any.
+			last: Option<ParaId>,
+			/// The earliest end of the funds swept so far which did not expire yet.
+			wake: Option<BlockNumber>,
+			/// `crowdloan::NextFundIndex` when the sweep started.
+			next_fund_index: u32,
+		},
+		/// The last sweep completed.
+		Done {
+			/// The block from which the funds are swept again.
+			wake: BlockNumber,
+			/// `crowdloan::NextFundIndex` when the sweep started; the funds are swept again once
+			/// a fund was created.
+			next_fund_index: u32,
+		},
+	}
+
+	/// The progress of the sweeps over the funds; `None` until the first sweep started.
+	#[pallet::storage]
+	pub type SweepState<T: Config> = StorageValue<_, Sweep<BlockNumberFor<T>>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A batch of contributors to an expired fund was refunded.
+		Refunded { para: ParaId, remaining: BalanceOf<T> },
+		/// An empty expired fund was dissolved and its deposit returned to the depositor.
+		Dissolved { para: ParaId, depositor: T::AccountId, deposit: BalanceOf<T> },
+		/// Refunding or dissolving an expired fund failed, without any effect; it is retried in the
+		/// next sweep.
+		RefundFailed { para: ParaId, error: DispatchError },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+			Self::refund_expired(now, limit)
+		}
+	}
+
+	/// The outcome of handling a fund once.
+	enum Step {
+		/// The fund needs to be handled again.
+		Again,
+		/// The sweep can move on to the next fund.
+		Next,
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `fund` expired, i.e. its contributors can be refunded.
+		///
+		/// This mirrors the checks of `Crowdloan::refund`.
+		pub fn is_expired(now: BlockNumberFor<T>, fund: &FundInfoOf<T>) -> bool {
+			let Some((current_period, _)) =
+				<T as crowdloan::Config>::Auctioneer::lease_period_index(now)
+			else {
+				return false
+			};
+			let fund_account = crowdloan::Pallet::<T>::fund_account_id(fund.fund_index);
+			(now >= fund.end || current_period > fund.first_period) &&
+				CurrencyOf::<T>::free_balance(&fund_account) >= fund.raised
+		}
+
+		/// Sweep the funds again from `now` on, as a fund may have expired.
+		pub fn sweep_again(now: BlockNumberFor<T>) {
+			SweepState::<T>::mutate(|state| match state {
+				Some(Sweep::Ongoing { wake, .. }) => *wake = Some(now),
+				Some(Sweep::Done { wake, .. }) => *wake = now,
+				None => {},
+			});
+		}
+
+		/// The first block of the lease period after the one of `now`.
+		fn next_lease_period_start(now: BlockNumberFor<T>) -> BlockNumberFor<T> {
+			let (length, offset) = <T as crowdloan::Config>::Auctioneer::lease_period_length();
+			match now.checked_sub(&offset) {
+				Some(since) => now.saturating_add(length.saturating_sub(since % length)),
+				None => offset,
+			}
+		}
+
+		/// Refund and dissolve expired funds, resuming the sweep at [`SweepState`], within
+		/// `limit`.
+		pub fn refund_expired(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+			let check = T::DbWeight::get().reads(3);
+			let action = <T as crowdloan::Config>::WeightInfo::refund(
+				<T as crowdloan::Config>::RemoveKeysLimit::get(),
+			)
+			.max(<T as crowdloan::Config>::WeightInfo::dissolve());
+
+			let mut used = T::DbWeight::get().reads_writes(2, 1);
+			if used.any_gt(limit) {
+				return Weight::zero()
+			}
+
+			let fund_index = crowdloan::Pallet::<T>::next_fund_index();
+			let state = SweepState::<T>::get();
+			if let Some(Sweep::Done { wake, next_fund_index }) = state {
+				if now < wake && next_fund_index == fund_index {
+					// No fund may have expired since the last sweep.
+					return T::DbWeight::get().reads(2)
+				}
+			}
+			let (mut last, mut wake, next_fund_index) = match state {
+				Some(Sweep::Ongoing { last, wake, next_fund_index: index }) => (last, wake, index),
+				_ => (None, None, fund_index),
+			};
+			let mut funds = match last {
+				Some(para) => Funds::<T>::iter_keys_from(Funds::<T>::hashed_key_for(para)),
+				None => Funds::<T>::iter_keys(),
+			};
+			let mut current = funds.next();
+
+			while let Some(para) = current {
+				if used.saturating_add(check).any_gt(limit) {
+					break
+				}
+				used.saturating_accrue(check);
+
+				let Some(fund) = Funds::<T>::get(para) else {
+					last = Some(para);
+					current = funds.next();
+					continue
+				};
+				if !Self::is_expired(now, &fund) {
+					if fund.end > now {
+						wake = Some(wake.map_or(fund.end, |wake| wake.min(fund.end)));
+					}
+					last = Some(para);
+					current = funds.next();
+					continue
+				}
+
+				if used.saturating_add(action).any_gt(limit) {
+					break
+				}
+				used.saturating_accrue(action);
+
+				match Self::refund_or_dissolve(para, fund) {
+					Step::Again => {},
+					Step::Next => {
+						last = Some(para);
+						current = funds.next();
+					},
+				}
+			}
+
+			let sweep = match current {
+				// The sweep stopped within the funds; resume after the last fund handled.
+				Some(_) => Sweep::Ongoing { last, wake, next_fund_index },
+				// The sweep is complete; wait until a fund may have expired.
+				None => {
+					let period_start = Self::next_lease_period_start(now);
+					let wake = wake.map_or(period_start, |wake| wake.min(period_start));
+					Sweep::Done { wake, next_fund_index }
+				},
+			};
+			SweepState::<T>::put(sweep);
+			used
+		}
+
+		fn refund_or_dissolve(para: ParaId, fund: FundInfoOf<T>) -> Step {
+			let origin: OriginFor<T> = RawOrigin::Signed(fund.depositor.clone()).into();
+
+			if fund.raised.is_zero() {
+				match with_storage_layer(|| crowdloan::Pallet::<T>::dissolve(origin, para)) {
+					Ok(()) => Self::deposit_event(Event::<T>::Dissolved {
+						para,
+						depositor: fund.depositor,
+						deposit: fund.deposit,
+					}),
+					Err(error) => Self::deposit_event(Event::<T>::RefundFailed { para, error }),
+				}
+				return Step::Next
+			}
+
+			let refunded = with_storage_layer(|| crowdloan::Pallet::<T>::refund(origin, para));
+			if let Err(error) = refunded {
+				Self::deposit_event(Event::<T>::RefundFailed { para, error });
+				return Step::Next
+			}
+			let remaining = Funds::<T>::get(para).map_or_else(Zero::zero, |fund| fund.raised);
+			Self::deposit_event(Event::<T>::Refunded { para, remaining });
+			if remaining < fund.raised {
+				Step::Again
+			} else {
+				// Nothing was refunded; don't spin on this fund.
+				Step::Next
+			}
+		}
+	}
+}
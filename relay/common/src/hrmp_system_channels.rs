@@ -0,0 +1,178 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! Permissionless HRMP channels with system parachains.
+//!
+//! Opening a channel with a system parachain (ID below `LOWEST_PUBLIC_ID`) otherwise requires a
+//! `force_open_hrmp_channel` referendum, since system parachains do not act on channel requests
+//! by themselves. [`Pallet::establish_system_channel`]:
+//! - opens bidirectional channels between two system parachains, with the default capacity and
+//!   message size of the active configuration, for any signed origin;
+//! - lets a public parachain open a channel to a system parachain, requesting it if needed and
+//!   accepting it on behalf of the system parachain;
+//! - lets a public parachain open a channel from a system parachain, requesting it on behalf of
+//!   the system parachain and accepting it.
+//!
+//! Channels are only requested and accepted through the calls of the `hrmp` pallet, so both sides
+//! pay the usual deposits. A system parachain pays its deposits from its sovereign account on the
+//! Relay Chain, which governance keeps funded. Later versions of the `hrmp` pallet establish
+//! system channels and waive the deposits of system parachains themselves, with
+//! `establish_system_channel` and `poke_channel_deposits`; this pallet is to be replaced by them
+//! once the runtime is upgraded to such a version.
+
+pub use pallet_hrmp_system_channels::*;
+
+#[frame_support::pallet]
+pub mod pallet_hrmp_system_channels {
+	use frame_support::{pallet_prelude::*, storage::with_storage_layer};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use primitives::{Id as ParaId, LOWEST_PUBLIC_ID};
+	use runtime_parachains::{
+		configuration, ensure_parachain,
+		hrmp::{self, WeightInfo as _},
+		origin,
+	};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + hrmp::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The runtime origin, from which the origin of a parachain is created.
+		type RuntimeOrigin: From<origin::Origin>
+			+ Into<<Self as frame_system::Config>::RuntimeOrigin>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Bidirectional channels between two system parachains were opened.
+		SystemChannelsOpened { sender: ParaId, recipient: ParaId },
+		/// A channel from a system parachain to a public parachain was requested and accepted.
+		SystemChannelRequested { sender: ParaId, recipient: ParaId },
+		/// The channel request of a public parachain was accepted by a system parachain.
+		SystemChannelAccepted { sender: ParaId, recipient: ParaId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Neither of the parachains is a system parachain.
+		NoSystemParachain,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Establish an HRMP channel from `sender` to `recipient`, at least one of which must be a
+		/// system parachain.
+		///
+		/// If both are system parachains, the channels in both directions are opened, and any
+		/// signed origin may call this. Otherwise the origin must be the public parachain, while
+		/// the system parachain requests or accepts the channel on its side. Each side pays the
+		/// deposits of the `hrmp` pallet for its side of the channel.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			<T as hrmp::Config>::WeightInfo::force_open_hrmp_channel(1)
+				.saturating_mul(2)
+				.saturating_add(T::DbWeight::get().reads(1))
+		)]
+		pub fn establish_system_channel(
+			origin: OriginFor<T>,
+			sender: ParaId,
+			recipient: ParaId,
+		) -> DispatchResult {
+			let config = configuration::Pallet::<T>::config();
+			let (max_capacity, max_message_size) =
+				(config.hrmp_channel_max_capacity, config.hrmp_channel_max_message_size);
+
+			match (Self::is_system(sender), Self::is_system(recipient)) {
+				(true, true) => {
+					ensure_signed(origin)?;
+					for (from, to) in [(sender, recipient), (recipient, sender)] {
+						hrmp::Pallet::<T>::force_open_hrmp_channel(
+							RawOrigin::Root.into(),
+							from,
+							to,
+							max_capacity,
+							max_message_size,
+						)
+						.map_err(|e| e.error)?;
+					}
+					Self::deposit_event(Event::<T>::SystemChannelsOpened { sender, recipient });
+				},
+				(true, false) => {
+					Self::ensure_para(origin, recipient)?;
+					hrmp::Pallet::<T>::hrmp_init_open_channel(
+						Self::para_origin(sender),
+						recipient,
+						max_capacity,
+						max_message_size,
+					)?;
+					hrmp::Pallet::<T>::hrmp_accept_open_channel(
+						Self::para_origin(recipient),
+						sender,
+					)?;
+					Self::deposit_event(Event::<T>::SystemChannelRequested { sender, recipient });
+				},
+				(false, true) => {
+					Self::ensure_para(origin, sender)?;
+					let requested = with_storage_layer(|| {
+						hrmp::Pallet::<T>::hrmp_init_open_channel(
+							Self::para_origin(sender),
+							recipient,
+							max_capacity,
+							max_message_size,
+						)
+					});
+					// The public parachain may have requested the channel by itself already.
+					let already_requested: DispatchError =
+						hrmp::Error::<T>::OpenHrmpChannelAlreadyRequested.into();
+					match requested {
+						Err(e) if e != already_requested => return Err(e),
+						_ => {},
+					}
+					hrmp::Pallet::<T>::hrmp_accept_open_channel(
+						Self::para_origin(recipient),
+						sender,
+					)?;
+					Self::deposit_event(Event::<T>::SystemChannelAccepted { sender, recipient });
+				},
+				(false, false) => return Err(Error::<T>::NoSystemParachain.into()),
+			}
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn is_system(para: ParaId) -> bool {
+			para < LOWEST_PUBLIC_ID
+		}
+
+		fn para_origin(para: ParaId) -> OriginFor<T> {
+			<T as Config>::RuntimeOrigin::from(origin::Origin::Parachain(para)).into()
+		}
+
+		/// Ensure that `origin` is the parachain `para`.
+		fn ensure_para(origin: OriginFor<T>, para: ParaId) -> DispatchResult {
+			let origin_para = ensure_parachain(<T as hrmp::Config>::RuntimeOrigin::from(origin))?;
+			ensure!(origin_para == para, DispatchError::BadOrigin);
+			Ok(())
+		}
+	}
+}
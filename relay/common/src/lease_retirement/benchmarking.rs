@@ -0,0 +1,110 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks of the lease retirement.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::{
+	assert_ok,
+	traits::{Currency, EnsureOrigin, ReservableCurrency},
+};
+use frame_system::RawOrigin;
+use runtime_common::{slots, traits::Registrar};
+use runtime_parachains::paras;
+use sp_runtime::traits::Bounded;
+
+/// The deposit of every lease period of the benchmarks.
+const DEPOSIT: u32 = 1_000;
+
+/// The para of the `i`th lease of the benchmarks.
+fn para(i: u32) -> ParaId {
+	ParaId::from(2_000 + i)
+}
+
+/// An account holding enough to reserve the deposits of the benchmarks.
+fn funded_account<T: Config>(name: &'static str, i: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, i, 0);
+	<T as slots::Config>::Currency::make_free_balance_be(
+		&who,
+		BalanceOf::<T>::max_value() / 2u32.into(),
+	);
+	who
+}
+
+/// Register the para of the `i`th lease and retire its lease of two lease periods, held by
+/// different accounts, so that ending the first one unreserves a deposit and upgrades the para.
+fn retire_lease<T: Config + paras::Config>(i: u32) {
+	let para = para(i);
+	let manager = funded_account::<T>("manager", i);
+	let code = <T as slots::Config>::Registrar::worst_validation_code();
+	assert_ok!(<T as slots::Config>::Registrar::register(
+		manager,
+		para,
+		<T as slots::Config>::Registrar::worst_head_data(),
+		code.clone(),
+	));
+	assert_ok!(paras::Pallet::<T>::add_trusted_validation_code(RawOrigin::Root.into(), code));
+
+	let periods = ["ended", "current"]
+		.into_iter()
+		.map(|name| {
+			let holder = funded_account::<T>(name, i);
+			<T as slots::Config>::Currency::reserve(&holder, DEPOSIT.into())
+				.expect("the holder is funded");
+			Some((holder, DEPOSIT.into()))
+		})
+		.collect();
+	RetiredLeases::<T>::insert(para, periods);
+}
+
+#[benchmarks(where T: paras::Config)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn retire_leases(l: Linear<1, 100>) -> Result<(), BenchmarkError> {
+		let origin =
+			T::RetireOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		for i in 0..l {
+			let leaser: T::AccountId = account("leaser", i, 0);
+			slots::Leases::<T>::insert(para(i), vec![Some((leaser, DEPOSIT.into()))]);
+		}
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, l);
+
+		assert_eq!(RetiredLeases::<T>::iter().count(), l as usize);
+		assert_eq!(slots::Leases::<T>::iter().count(), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn end_lease_periods(n: Linear<1, { T::MaxLeasesPerBlock::get() }>) {
+		for i in 0..n {
+			retire_lease::<T>(i);
+		}
+		<T as slots::Config>::Registrar::execute_pending_transitions();
+		PendingPeriodEnds::<T>::put(1);
+
+		#[block]
+		{
+			Pallet::<T>::end_lease_periods(n);
+		}
+
+		assert!((0..n).all(|i| RetiredLeases::<T>::get(para(i)).len() == 1));
+	}
+}
@@ -0,0 +1,396 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! Retirement of the slot auctions.
+//!
+//! Governance moves the leases of `Slots` into this pallet with [`Pallet::retire_leases`]. The
+//! retired leases run their course as they would in `Slots`: on every lease period start, the
+//! deposits no longer needed for the remaining lease periods are unreserved and paras are
+//! upgraded or downgraded. Once the lease of a para ended, its crowdloan, if any, is refunded and
+//! dissolved by the `crowdloan_refunder` pallet.
+//!
+//! The ended lease period is ended for at most [`Config::MaxLeasesPerBlock`] retired leases per
+//! block, so ending it for every retired lease may take a few blocks after the lease period
+//! start. No leases can be retired meanwhile.
+//!
+//! [`LeaseRetirementApi`] lists the outstanding leases and crowdloan funds per para.
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use crate::crowdloan_refunder;
+use frame_support::{traits::Get, weights::Weight};
+use frame_system::pallet_prelude::BlockNumberFor;
+pub use pallet_lease_retirement::*;
+use parity_scale_codec::{Decode, Encode};
+use primitives::Id as ParaId;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Weight functions of the lease retirement.
+pub trait WeightInfo {
+	/// Retiring `l` leases of `Slots`.
+	fn retire_leases(l: u32) -> Weight;
+	/// Ending the ended lease period of `n` retired leases.
+	fn end_lease_periods(n: u32) -> Weight;
+}
+
+/// Weights of the lease retirement from its storage accesses only, until the runtime provides
+/// benchmarked weights.
+pub struct DbWeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for DbWeightInfo<T> {
+	fn retire_leases(l: u32) -> Weight {
+		T::DbWeight::get()
+			.reads_writes(2, 0)
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2).saturating_mul(l.into()))
+	}
+	fn end_lease_periods(n: u32) -> Weight {
+		T::DbWeight::get()
+			.reads_writes(3, 2)
+			.saturating_add(T::DbWeight::get().reads_writes(4, 5).saturating_mul(n.into()))
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet_lease_retirement {
+	use super::WeightInfo;
+	use crate::crowdloan_refunder;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use primitives::Id as ParaId;
+	use runtime_common::{
+		slots,
+		traits::{Leaser, Registrar},
+	};
+	use sp_runtime::traits::{CheckedSub, Saturating, Zero};
+	use sp_std::prelude::*;
+
+	pub type BalanceOf<T> = <<T as slots::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	/// The holders and deposits of the remaining lease periods of a para, starting with the
+	/// current lease period, as in `slots::Leases`.
+	pub type LeasePeriodsOf<T> =
+		Vec<Option<(<T as frame_system::Config>::AccountId, BalanceOf<T>)>>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + slots::Config + crowdloan_refunder::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to retire the leases of `Slots`.
+		type RetireOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// The maximum number of retired leases whose ended lease period is ended per block.
+		#[pallet::constant]
+		type MaxLeasesPerBlock: Get<u32>;
+
+		/// Weight information for the calls and the hooks of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// The leases moved out of `Slots`.
+	#[pallet::storage]
+	pub type RetiredLeases<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, LeasePeriodsOf<T>, ValueQuery>;
+
+	/// The number of lease periods which ended and were not yet ended for every retired lease.
+	#[pallet::storage]
+	pub type PendingPeriodEnds<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The last para whose lease period was ended by the ongoing pass over [`RetiredLeases`].
+	#[pallet::storage]
+	pub type Cursor<T: Config> = StorageValue<_, ParaId, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The lease of a para was moved out of `Slots`.
+		LeaseRetired { para: ParaId, remaining_periods: u32 },
+		/// A deposit no longer needed for the lease of a para was unreserved.
+		DepositUnreserved { para: ParaId, who: T::AccountId, amount: BalanceOf<T> },
+		/// The lease of a para ended.
+		LeaseEnded { para: ParaId },
+		/// Upgrading or downgrading a para at the start of a lease period failed.
+		LifecycleChangeFailed { para: ParaId, error: DispatchError },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The ended lease period was not yet ended for every retired lease.
+		LeasePeriodEnding,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			if let Some((_, true)) = <slots::Pallet<T> as Leaser<_>>::lease_period_index(now) {
+				PendingPeriodEnds::<T>::mutate(|pending| pending.saturating_inc());
+			}
+			if PendingPeriodEnds::<T>::get().is_zero() {
+				return T::DbWeight::get().reads(1)
+			}
+			let ended = Self::end_lease_periods(T::MaxLeasesPerBlock::get());
+			T::WeightInfo::end_lease_periods(ended)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Move up to `limit` leases out of `Slots`.
+		///
+		/// Paras whose lease was already retired are skipped and keep their lease in `Slots`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::retire_leases(*limit))]
+		pub fn retire_leases(origin: OriginFor<T>, limit: u32) -> DispatchResult {
+			T::RetireOrigin::ensure_origin(origin)?;
+			ensure!(PendingPeriodEnds::<T>::get().is_zero(), Error::<T>::LeasePeriodEnding);
+
+			let leases: Vec<_> = slots::Leases::<T>::iter()
+				.filter(|(para, _)| !RetiredLeases::<T>::contains_key(para))
+				.take(limit as usize)
+				.collect();
+			for (para, periods) in leases {
+				slots::Leases::<T>::remove(para);
+				let remaining_periods = periods.len() as u32;
+				RetiredLeases::<T>::insert(para, periods);
+				Self::deposit_event(Event::<T>::LeaseRetired { para, remaining_periods });
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The deposit held from `who` for the remaining lease `periods` of a para.
+		pub fn deposit_held(periods: &LeasePeriodsOf<T>, who: &T::AccountId) -> BalanceOf<T> {
+			periods
+				.iter()
+				.filter_map(|period| period.as_ref())
+				.filter(|(holder, _)| holder == who)
+				.map(|(_, amount)| *amount)
+				.max()
+				.unwrap_or_else(Zero::zero)
+		}
+
+		/// Report the failure to upgrade or downgrade `para`, which `Slots` silently ignores.
+		fn note_lifecycle_change(para: ParaId, result: DispatchResult) {
+			if let Err(error) = result {
+				log::warn!(
+					target: "runtime::lease_retirement",
+					"Changing the lifecycle of para {:?} failed: {:?}",
+					para,
+					error,
+				);
+				Self::deposit_event(Event::<T>::LifecycleChangeFailed { para, error });
+			}
+		}
+
+		/// End the pending ended lease periods for up to `limit` retired leases, continuing from
+		/// [`Cursor`], and return the number of leases handled.
+		pub(crate) fn end_lease_periods(limit: u32) -> u32 {
+			let mut handled = 0u32;
+			while handled < limit && !PendingPeriodEnds::<T>::get().is_zero() {
+				let wanted = limit.saturating_sub(handled);
+				let keys = match Cursor::<T>::get().map(RetiredLeases::<T>::hashed_key_for) {
+					Some(start) => RetiredLeases::<T>::iter_keys_from(start),
+					None => RetiredLeases::<T>::iter_keys(),
+				};
+				let paras: Vec<ParaId> = keys.take(wanted as usize).collect();
+				for para in &paras {
+					Self::end_lease_period(*para);
+				}
+				handled.saturating_accrue(paras.len() as u32);
+
+				match paras.last() {
+					Some(last) if paras.len() as u32 == wanted => Cursor::<T>::put(last),
+					_ => {
+						// Every retired lease is done with the oldest pending lease period.
+						Cursor::<T>::kill();
+						PendingPeriodEnds::<T>::mutate(|pending| pending.saturating_dec());
+					},
+				}
+			}
+			handled
+		}
+
+		/// End the now-ended lease period of the retired lease of `para`, as `Slots` does.
+		fn end_lease_period(para: ParaId) {
+			let mut periods = RetiredLeases::<T>::get(para);
+			if periods.is_empty() {
+				RetiredLeases::<T>::remove(para);
+				return
+			}
+
+			let ended = periods.remove(0);
+			if let Some((who, amount)) = ended {
+				let rebate =
+					amount.checked_sub(&Self::deposit_held(&periods, &who)).unwrap_or_default();
+				if !rebate.is_zero() {
+					<T as slots::Config>::Currency::unreserve(&who, rebate);
+					// The deposit may be the one of a crowdloan fund, which may thus have expired.
+					crowdloan_refunder::Pallet::<T>::sweep_again(
+						frame_system::Pallet::<T>::block_number(),
+					);
+					Self::deposit_event(Event::<T>::DepositUnreserved {
+						para,
+						who,
+						amount: rebate,
+					});
+				}
+			}
+
+			match periods.first() {
+				None => {
+					RetiredLeases::<T>::remove(para);
+					Self::note_lifecycle_change(
+						para,
+						<T as slots::Config>::Registrar::make_parathread(para),
+					);
+					Self::deposit_event(Event::<T>::LeaseEnded { para });
+				},
+				Some(current) => {
+					if current.is_some() {
+						Self::note_lifecycle_change(
+							para,
+							<T as slots::Config>::Registrar::make_parachain(para),
+						);
+					}
+					RetiredLeases::<T>::insert(para, periods);
+				},
+			}
+		}
+	}
+}
+
+/// A crowdloan fund of a para.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CrowdloanFund<AccountId, Balance, BlockNumber> {
+	/// The account which created the fund and holds its deposit.
+	pub depositor: AccountId,
+	/// The amount raised from contributors and not yet refunded.
+	pub raised: Balance,
+	/// The block at which the crowdloan ends.
+	pub end: BlockNumber,
+	/// Whether the fund expired and is being refunded and dissolved.
+	pub expired: bool,
+}
+
+/// The outstanding lease and crowdloan fund of a para.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct OutstandingLease<AccountId, Balance, BlockNumber> {
+	/// The para.
+	pub para: ParaId,
+	/// Whether the lease was moved out of `Slots`.
+	pub retired: bool,
+	/// The number of lease periods left, including the current one.
+	pub remaining_periods: u32,
+	/// The block at which the lease ends and the last deposits are unreserved.
+	pub ends_at: BlockNumber,
+	/// The deposits currently held for the lease, per account.
+	pub deposits: Vec<(AccountId, Balance)>,
+	/// The crowdloan fund of the para, if any.
+	pub crowdloan: Option<CrowdloanFund<AccountId, Balance, BlockNumber>>,
+}
+
+/// The [`OutstandingLease`] of a para of the runtime `T`.
+pub type OutstandingLeaseOf<T> =
+	OutstandingLease<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+/// The outstanding leases of `Slots` and of this pallet, and the crowdloan funds, per para.
+pub fn outstanding_leases<T>() -> Vec<OutstandingLeaseOf<T>>
+where
+	T: Config + crowdloan_refunder::Config,
+	crowdloan_refunder::BalanceOf<T>: Into<BalanceOf<T>>,
+{
+	use runtime_common::{slots, traits::Leaser};
+	use sp_runtime::traits::{Saturating, Zero};
+
+	let now = frame_system::Pallet::<T>::block_number();
+	let current_period = <slots::Pallet<T> as Leaser<_>>::lease_period_index(now)
+		.map_or_else(Zero::zero, |(period, _)| period);
+	let period_start = |period: BlockNumberFor<T>| {
+		period
+			.saturating_mul(<T as slots::Config>::LeasePeriod::get())
+			.saturating_add(<T as slots::Config>::LeaseOffset::get())
+	};
+
+	let mut leases: Vec<_> = slots::Leases::<T>::iter()
+		.map(|(para, periods)| (para, false, periods))
+		.chain(RetiredLeases::<T>::iter().map(|(para, periods)| (para, true, periods)))
+		.map(|(para, retired, periods)| {
+			let mut deposits: Vec<(T::AccountId, BalanceOf<T>)> = Vec::new();
+			for (who, _) in periods.iter().flatten() {
+				if !deposits.iter().any(|(holder, _)| holder == who) {
+					deposits.push((who.clone(), Pallet::<T>::deposit_held(&periods, who)));
+				}
+			}
+			let remaining_periods = periods.len() as u32;
+			OutstandingLease {
+				para,
+				retired,
+				remaining_periods,
+				ends_at: period_start(current_period.saturating_add(remaining_periods.into())),
+				deposits,
+				crowdloan: None,
+			}
+		})
+		.collect();
+
+	for (para, fund) in crowdloan_refunder::Funds::<T>::iter() {
+		let crowdloan = CrowdloanFund {
+			expired: crowdloan_refunder::Pallet::<T>::is_expired(now, &fund),
+			depositor: fund.depositor,
+			raised: fund.raised.into(),
+			end: fund.end,
+		};
+		match leases.iter_mut().find(|lease| lease.para == para) {
+			Some(lease) => lease.crowdloan = Some(crowdloan),
+			None => leases.push(OutstandingLease {
+				para,
+				retired: false,
+				remaining_periods: 0,
+				ends_at: now,
+				deposits: Vec::new(),
+				crowdloan: Some(crowdloan),
+			}),
+		}
+	}
+
+	leases.sort_by_key(|lease| lease.para);
+	leases
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to query the outstanding leases and crowdloan funds while auctions are retired.
+	pub trait LeaseRetirementApi<AccountId, Balance, BlockNumber>
+	where
+		AccountId: parity_scale_codec::Codec,
+		Balance: parity_scale_codec::Codec,
+		BlockNumber: parity_scale_codec::Codec,
+	{
+		/// The outstanding leases and crowdloan funds, per para.
+		fn outstanding_leases() -> Vec<OutstandingLease<AccountId, Balance, BlockNumber>>;
+	}
+}
@@ -0,0 +1,27 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallets and runtime APIs shared by the Polkadot and Kusama Relay Chains.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod code_upgrade_authorization;
+pub mod crowdloan_refunder;
+pub mod hrmp_system_channels;
+pub mod lease_retirement;
+// Pausing of the UMP queues of misbehaving parachains.
+pub mod ump_queue_breaker;
+pub mod validator_disabling;
@@ -0,0 +1,266 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! Pausing of the upward message queues of misbehaving parachains.
+//!
+//! The UMP queue of a para can be paused and resumed by governance, and is paused automatically
+//! once more than `max_failures` of the para's upward messages could never be processed within
+//! `failure_window` blocks. Governance may set these [`Thresholds`]; until it does, the
+//! `MaxFailures` and `FailureWindow` of the runtime configuration apply. While paused,
+//! `pallet_message_queue` keeps the messages of the para but does not service them.
+//!
+//! Only messages the para itself got wrong count: those which cannot be decoded or are of an
+//! unsupported format, and those permanently overweight. A message whose execution merely failed
+//! does not count, since any user of a para can send such a message with `pallet_xcm::send`. The
+//! queues of system parachains, which carry the messages of governance, are never paused
+//! automatically.
+
+pub use pallet_ump_queue_breaker::*;
+use sp_std::prelude::*;
+
+#[frame_support::pallet]
+pub mod pallet_ump_queue_breaker {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{ProcessMessageError, QueuePausedQuery},
+	};
+	use frame_system::pallet_prelude::*;
+	use primitives::{Id as ParaId, LOWEST_PUBLIC_ID};
+	use runtime_parachains::inclusion::{AggregateMessageOrigin, UmpQueueId};
+	use sp_runtime::traits::Saturating;
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to pause and resume the UMP queue of a para.
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Origin allowed to set the thresholds of the circuit breaker.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The default number of blocks over which failed and overweight messages of a para are
+		/// counted, until governance sets other [`Thresholds`].
+		#[pallet::constant]
+		type FailureWindow: Get<BlockNumberFor<Self>>;
+
+		/// The default number of failed and overweight messages of a para tolerated within the
+		/// failure window, until governance sets other [`Thresholds`].
+		#[pallet::constant]
+		type MaxFailures: Get<u32>;
+
+		/// The weight above which a message is considered overweight, i.e. the weight limit of
+		/// `pallet_message_queue` for servicing a single message.
+		#[pallet::constant]
+		type OverweightLimit: Get<Weight>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The reason why the UMP queue of a para was paused.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum PauseReason {
+		/// The queue was paused by governance.
+		Governance,
+		/// The para exceeded the tolerated number of failed and overweight messages.
+		CircuitBreaker,
+	}
+
+	/// Information about a paused UMP queue.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct PauseInfo<BlockNumber> {
+		/// The reason why the queue was paused.
+		pub reason: PauseReason,
+		/// The block at which the queue was paused.
+		pub since: BlockNumber,
+	}
+
+	/// The failed and overweight messages of a para within the current window.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct FailureCount<BlockNumber> {
+		/// The block at which the window started.
+		pub window_start: BlockNumber,
+		/// The number of failed and overweight messages since `window_start`.
+		pub count: u32,
+	}
+
+	/// The thresholds at which the UMP queue of a para is paused.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Thresholds<BlockNumber> {
+		/// The number of blocks over which failed and overweight messages of a para are counted.
+		pub failure_window: BlockNumber,
+		/// The number of failed and overweight messages of a para tolerated within
+		/// `failure_window`. The UMP queue of the para is paused on the next one.
+		pub max_failures: u32,
+	}
+
+	/// The thresholds set by governance, overriding `FailureWindow` and `MaxFailures`.
+	#[pallet::storage]
+	pub type BreakerThresholds<T: Config> =
+		StorageValue<_, Thresholds<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The paras whose UMP queue is paused.
+	#[pallet::storage]
+	pub type PausedQueues<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, PauseInfo<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The failed and overweight messages per para within the current window.
+	#[pallet::storage]
+	pub type Failures<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, FailureCount<BlockNumberFor<T>>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The UMP queue of a para was paused.
+		QueuePaused { para: ParaId, reason: PauseReason },
+		/// The UMP queue of a para was resumed.
+		QueueResumed { para: ParaId },
+		/// The thresholds of the circuit breaker were changed.
+		ThresholdsSet { thresholds: Thresholds<BlockNumberFor<T>> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The UMP queue of the para is already paused.
+		AlreadyPaused,
+		/// The UMP queue of the para is not paused.
+		NotPaused,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause the UMP queue of `para`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn pause(origin: OriginFor<T>, para: ParaId) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(!PausedQueues::<T>::contains_key(para), Error::<T>::AlreadyPaused);
+			Self::do_pause(para, PauseReason::Governance);
+			Ok(())
+		}
+
+		/// Resume the UMP queue of `para`, forgetting about its past failed messages.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 2))]
+		pub fn resume(origin: OriginFor<T>, para: ParaId) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(PausedQueues::<T>::contains_key(para), Error::<T>::NotPaused);
+			PausedQueues::<T>::remove(para);
+			Failures::<T>::remove(para);
+			Self::deposit_event(Event::<T>::QueueResumed { para });
+			Ok(())
+		}
+
+		/// Set the thresholds of the circuit breaker, or restore the defaults of the runtime
+		/// configuration with `None`.
+		///
+		/// The failures already counted for the current windows are kept.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_thresholds(
+			origin: OriginFor<T>,
+			thresholds: Option<Thresholds<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			BreakerThresholds::<T>::set(thresholds);
+			Self::deposit_event(Event::<T>::ThresholdsSet { thresholds: Self::thresholds() });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The weight of [`Self::note_processed`].
+		pub fn note_processed_weight() -> Weight {
+			T::DbWeight::get().reads_writes(3, 2)
+		}
+
+		/// The thresholds in effect: those set by governance, or else the defaults of the runtime
+		/// configuration.
+		pub fn thresholds() -> Thresholds<BlockNumberFor<T>> {
+			BreakerThresholds::<T>::get().unwrap_or_else(|| Thresholds {
+				failure_window: T::FailureWindow::get(),
+				max_failures: T::MaxFailures::get(),
+			})
+		}
+
+		/// Note the outcome of processing an upward message of `para`, pausing its queue if the
+		/// para exceeded the tolerated number of messages which can never be processed.
+		pub fn note_processed(para: ParaId, result: &Result<bool, ProcessMessageError>) {
+			let failed = match result {
+				// The execution of a message can be made to fail by any user of the para.
+				Ok(_) => false,
+				// Only messages that can never be serviced count, not those exceeding the weight
+				// left in the current block.
+				Err(ProcessMessageError::Overweight(required)) =>
+					required.any_gt(T::OverweightLimit::get()),
+				Err(ProcessMessageError::Yield) => false,
+				Err(_) => true,
+			};
+			if !failed || para < LOWEST_PUBLIC_ID {
+				return
+			}
+
+			let thresholds = Self::thresholds();
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut failures = Failures::<T>::get(para)
+				.filter(|f| now < f.window_start.saturating_add(thresholds.failure_window))
+				.unwrap_or(FailureCount { window_start: now, count: 0 });
+			failures.count.saturating_inc();
+
+			if failures.count > thresholds.max_failures && !PausedQueues::<T>::contains_key(para) {
+				Failures::<T>::remove(para);
+				Self::do_pause(para, PauseReason::CircuitBreaker);
+			} else {
+				Failures::<T>::insert(para, failures);
+			}
+		}
+
+		/// All paused UMP queues.
+		pub fn paused_queues() -> Vec<(ParaId, PauseInfo<BlockNumberFor<T>>)> {
+			PausedQueues::<T>::iter().collect()
+		}
+
+		fn do_pause(para: ParaId, reason: PauseReason) {
+			let since = frame_system::Pallet::<T>::block_number();
+			PausedQueues::<T>::insert(para, PauseInfo { reason, since });
+			Self::deposit_event(Event::<T>::QueuePaused { para, reason });
+		}
+	}
+
+	impl<T: Config> QueuePausedQuery<AggregateMessageOrigin> for Pallet<T> {
+		fn is_paused(origin: &AggregateMessageOrigin) -> bool {
+			match origin {
+				AggregateMessageOrigin::Ump(UmpQueueId::Para(para)) =>
+					PausedQueues::<T>::contains_key(para),
+			}
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to query the paused UMP queues.
+	pub trait UmpQueueBreakerApi<BlockNumber>
+	where
+		BlockNumber: parity_scale_codec::Codec,
+	{
+		/// The paras whose UMP queue is paused, along with why and since when.
+		fn paused_queues() -> Vec<(primitives::Id, PauseInfo<BlockNumber>)>;
+	}
+}
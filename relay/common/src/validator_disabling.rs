@@ -0,0 +1,231 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! Disabling of validators slashed in disputes.
+//!
+//! Validators slashed for equivocation are disabled by `pallet_staking` as soon as the offence is
+//! reported. [`DisableValidatorsForDisputes`] does the same for the validators losing a dispute in
+//! the current session, instead of letting them participate until the end of the era: it reports
+//! a [`DisputeLoss`] offence, which `pallet_staking` handles like any other offence by recording
+//! the losers in its `OffendingValidators` and disabling them in `pallet_session`. The offence
+//! slashes nothing; the dispute slash itself is still applied through `Inner`.
+//!
+//! Disabling never exceeds [`disabling_limit`], the number of byzantine validators the protocol
+//! tolerates, such that disabling alone can never stall finality.
+//!
+//! Disabling only takes effect where `pallet_session` is consulted, e.g. for block authoring.
+//! The `paras_inherent` and `disputes` pallets of `polkadot-runtime-parachains` still accept
+//! backing and dispute statements of disabled validators, and `ParachainHost` is declared in
+//! `polkadot-primitives`, so neither can be changed by the runtime. Nodes query the disabled
+//! validators with the separate [`DisabledValidatorsApi`] instead.
+
+use frame_support::{dispatch::DispatchClass, traits::Get, weights::Weight};
+use pallet_session::historical::IdentificationTuple;
+use primitives::{CandidateHash, SessionIndex, ValidatorIndex};
+use runtime_parachains::{disputes::SlashingHandler, shared};
+use sp_runtime::{traits::Convert, Perbill};
+use sp_staking::offence::{DisableStrategy, Kind, Offence, ReportOffence};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The maximum number of disabled validators out of `validators`, i.e. the number of faulty
+/// validators tolerated by the byzantine threshold.
+pub fn disabling_limit(validators: usize) -> usize {
+	validators.saturating_sub(1) / 3
+}
+
+/// The loss of a dispute about a candidate by validators of the current session.
+///
+/// The offence disables the losers without slashing them.
+pub struct DisputeLoss<Offender> {
+	/// The session of the dispute.
+	pub session_index: SessionIndex,
+	/// The candidate of the dispute.
+	pub candidate_hash: CandidateHash,
+	/// The number of validators of the session.
+	pub validator_set_count: u32,
+	/// The losers of the dispute.
+	pub offenders: Vec<Offender>,
+}
+
+impl<Offender: Clone> Offence<Offender> for DisputeLoss<Offender> {
+	const ID: Kind = *b"disputes:disable";
+	type TimeSlot = (SessionIndex, CandidateHash);
+
+	fn offenders(&self) -> Vec<Offender> {
+		self.offenders.clone()
+	}
+
+	fn session_index(&self) -> SessionIndex {
+		self.session_index
+	}
+
+	fn validator_set_count(&self) -> u32 {
+		self.validator_set_count
+	}
+
+	fn time_slot(&self) -> Self::TimeSlot {
+		(self.session_index, self.candidate_hash)
+	}
+
+	fn disable_strategy(&self) -> DisableStrategy {
+		DisableStrategy::Always
+	}
+
+	fn slash_fraction(&self, _offenders_count: u32) -> Perbill {
+		Perbill::zero()
+	}
+}
+
+/// Report the not yet disabled validators at the `losers` indices of the active validators of
+/// the current session to `R` as a [`DisputeLoss`] about `candidate_hash`, up to
+/// [`disabling_limit`].
+///
+/// Returns the indices of the reported validators in the session validator set.
+pub fn disable_losers<T, R>(
+	session: SessionIndex,
+	candidate_hash: CandidateHash,
+	losers: &[ValidatorIndex],
+) -> Vec<u32>
+where
+	T: shared::Config + pallet_session::historical::Config,
+	R: ReportOffence<T::AccountId, IdentificationTuple<T>, DisputeLoss<IdentificationTuple<T>>>,
+{
+	// Validators of past sessions may not be part of the current validator set anymore.
+	if session != shared::Pallet::<T>::session_index() {
+		return Vec::new()
+	}
+
+	let active = shared::Pallet::<T>::active_validator_indices();
+	let validators = pallet_session::Pallet::<T>::validators();
+	let disabled = pallet_session::Pallet::<T>::disabled_validators();
+	let room = disabling_limit(validators.len()).saturating_sub(disabled.len());
+	let mut reported = Vec::new();
+	let mut offenders = Vec::new();
+	for loser in losers {
+		if offenders.len() >= room {
+			log::warn!(
+				target: "runtime::parachains::disputes",
+				"Disabling limit of {} validators reached, not disabling further dispute losers",
+				disabling_limit(validators.len()),
+			);
+			break
+		}
+		let Some(ValidatorIndex(index)) = active.get(loser.0 as usize) else { continue };
+		if disabled.binary_search(index).is_ok() || reported.contains(index) {
+			continue
+		}
+		let Some(validator) = validators.get(*index as usize) else { continue };
+		let Some(exposure) = T::FullIdentificationOf::convert(validator.clone()) else { continue };
+		reported.push(*index);
+		offenders.push((validator.clone(), exposure));
+	}
+	if offenders.is_empty() {
+		return reported
+	}
+
+	let count = offenders.len() as u64;
+	let offence = DisputeLoss {
+		session_index: session,
+		candidate_hash,
+		validator_set_count: validators.len() as u32,
+		offenders,
+	};
+	if let Err(error) = R::report_offence(Vec::new(), offence) {
+		log::warn!(
+			target: "runtime::parachains::disputes",
+			"Reporting the losers of the dispute about {:?} failed: {:?}",
+			candidate_hash,
+			error,
+		);
+		return Vec::new()
+	}
+
+	// Slashes are applied while the disputes of the inherent are processed, whose weight covers
+	// neither the report nor its handling by `pallet_staking`.
+	let weight = T::DbWeight::get()
+		.reads_writes(5 + losers.len() as u64, 2)
+		.saturating_add(T::DbWeight::get().reads_writes(8, 6).saturating_mul(count));
+	frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, DispatchClass::Mandatory);
+	reported
+}
+
+/// The disabled validators of the current session, as indices of the active validators.
+pub fn disabled_validators<T>() -> Vec<ValidatorIndex>
+where
+	T: shared::Config + pallet_session::Config,
+{
+	let disabled = pallet_session::Pallet::<T>::disabled_validators();
+	shared::Pallet::<T>::active_validator_indices()
+		.into_iter()
+		.enumerate()
+		.filter(|(_, ValidatorIndex(index))| disabled.binary_search(index).is_ok())
+		.map(|(position, _)| ValidatorIndex(position as u32))
+		.collect()
+}
+
+/// A [`SlashingHandler`] which reports the losers of disputes in the current session to `R`,
+/// which disables them, before passing them on to `Inner`.
+pub struct DisableValidatorsForDisputes<T, R, Inner>(PhantomData<(T, R, Inner)>);
+
+impl<T, R, Inner, BlockNumber> SlashingHandler<BlockNumber>
+	for DisableValidatorsForDisputes<T, R, Inner>
+where
+	T: shared::Config + pallet_session::historical::Config,
+	R: ReportOffence<T::AccountId, IdentificationTuple<T>, DisputeLoss<IdentificationTuple<T>>>,
+	Inner: SlashingHandler<BlockNumber>,
+{
+	fn punish_for_invalid(
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		losers: impl IntoIterator<Item = ValidatorIndex>,
+		backers: impl IntoIterator<Item = ValidatorIndex>,
+	) {
+		let losers: Vec<_> = losers.into_iter().collect();
+		disable_losers::<T, R>(session, candidate_hash, &losers);
+		Inner::punish_for_invalid(session, candidate_hash, losers, backers)
+	}
+
+	fn punish_against_valid(
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		losers: impl IntoIterator<Item = ValidatorIndex>,
+		backers: impl IntoIterator<Item = ValidatorIndex>,
+	) {
+		let losers: Vec<_> = losers.into_iter().collect();
+		disable_losers::<T, R>(session, candidate_hash, &losers);
+		Inner::punish_against_valid(session, candidate_hash, losers, backers)
+	}
+
+	fn initializer_initialize(now: BlockNumber) -> Weight {
+		Inner::initializer_initialize(now)
+	}
+
+	fn initializer_finalize() {
+		Inner::initializer_finalize()
+	}
+
+	fn initializer_on_new_session(session_index: SessionIndex) {
+		Inner::initializer_on_new_session(session_index)
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to query the disabled validators.
+	pub trait DisabledValidatorsApi {
+		/// The disabled validators of the current session, as indices of the active validators.
+		fn disabled_validators() -> Vec<ValidatorIndex>;
+	}
+}
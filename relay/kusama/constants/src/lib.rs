@@ -97,6 +97,26 @@ pub mod fee {
 	}
 }
 
+/// Parachain consensus related constants.
+pub mod async_backing {
+	use primitives::vstaging::AsyncBackingParams;
+
+	/// The asynchronous backing parameters of the Relay Chain.
+	///
+	/// Parachains may build on relay parents up to `allowed_ancestry_len` blocks old and have up to
+	/// `max_candidate_depth + 1` candidates backed on top of each other.
+	pub const ASYNC_BACKING_PARAMS: AsyncBackingParams =
+		AsyncBackingParams { max_candidate_depth: 3, allowed_ancestry_len: 2 };
+}
+
+/// System parachains.
+pub mod system_parachain {
+	/// Asset Hub parachain ID.
+	pub const ASSET_HUB_ID: u32 = 1000;
+	/// People parachain ID.
+	pub const PEOPLE_ID: u32 = 1004;
+}
+
 #[cfg(test)]
 mod tests {
 	use super::{
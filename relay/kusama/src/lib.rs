@@ -46,7 +46,9 @@ use runtime_parachains::{
 	inclusion::{AggregateMessageOrigin, UmpQueueId},
 	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, reward_points as parachains_reward_points,
-	runtime_api_impl::v5 as parachains_runtime_api_impl,
+	runtime_api_impl::{
+		v5 as parachains_runtime_api_impl, vstaging as parachains_staging_runtime_api_impl,
+	},
 	scheduler as parachains_scheduler, session_info as parachains_session_info,
 	shared as parachains_shared,
 };
@@ -109,8 +111,17 @@ mod weights;
 // Voter bag threshold definitions.
 mod bag_thresholds;
 
+mod async_backing_migration;
 mod paras_scheduler_migration;
 
+pub mod society_status;
+
+// Pallets shared with the other Relay Chain.
+pub use relay_common::{
+	code_upgrade_authorization, crowdloan_refunder, hrmp_system_channels, lease_retirement,
+	ump_queue_breaker, validator_disabling,
+};
+
 // Historical information of society finances.
 mod past_payouts;
 
@@ -159,11 +170,15 @@ pub fn native_version() -> NativeVersion {
 	NativeVersion { runtime_version: VERSION, can_author_with: Default::default() }
 }
 
-/// We currently allow all calls.
+/// We allow all calls, except those of `Registrar` that would get around a permanent lock.
 pub struct BaseFilter;
 impl Contains<RuntimeCall> for BaseFilter {
-	fn contains(_c: &RuntimeCall) -> bool {
-		true
+	fn contains(c: &RuntimeCall) -> bool {
+		match c {
+			RuntimeCall::Registrar(call) =>
+				code_upgrade_authorization::PermanentLockFilter::<Runtime>::contains(call),
+			_ => true,
+		}
 	}
 }
 
@@ -173,7 +188,7 @@ parameter_types! {
 }
 
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = BaseFilter;
 	type BlockWeights = BlockWeights;
 	type BlockLength = BlockLength;
 	type RuntimeOrigin = RuntimeOrigin;
@@ -1245,11 +1260,18 @@ impl ProcessMessage for MessageProcessor {
 		let para = match origin {
 			AggregateMessageOrigin::Ump(UmpQueueId::Para(para)) => para,
 		};
-		xcm_builder::ProcessXcmMessage::<
+		// Reserve the weight of noting the outcome with the `UmpQueueBreaker` upfront.
+		let note_weight = UmpQueueBreaker::note_processed_weight();
+		if meter.try_consume(note_weight).is_err() {
+			return Err(ProcessMessageError::Overweight(note_weight))
+		}
+		let result = xcm_builder::ProcessXcmMessage::<
 			Junction,
 			xcm_executor::XcmExecutor<xcm_config::XcmConfig>,
 			RuntimeCall,
-		>::process_message(message, Junction::Parachain(para.into()), meter, id)
+		>::process_message(message, Junction::Parachain(para.into()), meter, id);
+		UmpQueueBreaker::note_processed(para, &result);
+		result
 	}
 }
 
@@ -1265,10 +1287,29 @@ impl pallet_message_queue::Config for Runtime {
 	type MessageProcessor =
 		pallet_message_queue::mock_helpers::NoopMessageProcessor<AggregateMessageOrigin>;
 	type QueueChangeHandler = ParaInclusion;
-	type QueuePausedQuery = ();
+	type QueuePausedQuery = UmpQueueBreaker;
 	type WeightInfo = weights::pallet_message_queue::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const UmpFailureWindow: BlockNumber = 1 * HOURS;
+	pub const UmpMaxFailures: u32 = 100;
+}
+
+impl ump_queue_breaker::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	// UMP queues are paused and resumed by any of:
+	// - Root;
+	// - the GeneralAdmin origin;
+	// - the Fellows origin.
+	type PauseOrigin = EitherOf<EitherOf<EnsureRoot<Self::AccountId>, GeneralAdmin>, Fellows>;
+	// The thresholds of the circuit breaker are set by Root or the GeneralAdmin origin.
+	type AdminOrigin = EitherOf<EnsureRoot<Self::AccountId>, GeneralAdmin>;
+	type FailureWindow = UmpFailureWindow;
+	type MaxFailures = UmpMaxFailures;
+	type OverweightLimit = MessageQueueServiceWeight;
+}
+
 impl parachains_dmp::Config for Runtime {}
 
 impl parachains_hrmp::Config for Runtime {
@@ -1279,6 +1320,11 @@ impl parachains_hrmp::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_hrmp::WeightInfo<Runtime>;
 }
 
+impl hrmp_system_channels::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+}
+
 impl parachains_paras_inherent::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_paras_inherent::WeightInfo<Runtime>;
 }
@@ -1298,7 +1344,12 @@ impl parachains_initializer::Config for Runtime {
 impl parachains_disputes::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RewardValidators = parachains_reward_points::RewardValidatorsWithEraPoints<Runtime>;
-	type SlashingHandler = parachains_slashing::SlashValidatorsForDisputes<ParasSlashing>;
+	// Losers of disputes are disabled right away through an offence, besides being slashed.
+	type SlashingHandler = validator_disabling::DisableValidatorsForDisputes<
+		Runtime,
+		Offences,
+		parachains_slashing::SlashValidatorsForDisputes<ParasSlashing>,
+	>;
 	type WeightInfo = weights::runtime_parachains_disputes::WeightInfo<Runtime>;
 }
 
@@ -1333,6 +1384,15 @@ impl paras_registrar::Config for Runtime {
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const CodeUpgradeAuthorizationPeriod: BlockNumber = 7 * DAYS;
+}
+
+impl code_upgrade_authorization::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AuthorizationPeriod = CodeUpgradeAuthorizationPeriod;
+}
+
 parameter_types! {
 	// 6 weeks
 	pub LeasePeriod: BlockNumber = prod_or_fast!(6 * WEEKS, 6 * WEEKS, "KSM_LEASE_PERIOD");
@@ -1369,6 +1429,21 @@ impl crowdloan::Config for Runtime {
 	type WeightInfo = weights::runtime_common_crowdloan::WeightInfo<Runtime>;
 }
 
+impl crowdloan_refunder::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
+parameter_types! {
+	pub const MaxRetiredLeasesPerBlock: u32 = 16;
+}
+
+impl lease_retirement::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RetireOrigin = EitherOf<EnsureRoot<Self::AccountId>, LeaseAdmin>;
+	type MaxLeasesPerBlock = MaxRetiredLeasesPerBlock;
+	type WeightInfo = lease_retirement::DbWeightInfo<Self>;
+}
+
 parameter_types! {
 	// The average auction is 7 days long, so this will be 70% for ending period.
 	// 5 Days = 72000 Blocks @ 6 sec per block
@@ -1606,6 +1681,9 @@ construct_runtime! {
 		Slots: slots::{Pallet, Call, Storage, Event<T>} = 71,
 		Auctions: auctions::{Pallet, Call, Storage, Event<T>} = 72,
 		Crowdloan: crowdloan::{Pallet, Call, Storage, Event<T>} = 73,
+		LeaseRetirement: lease_retirement::{Pallet, Call, Storage, Event<T>} = 74,
+		CrowdloanRefunder: crowdloan_refunder::{Pallet, Storage, Event<T>} = 75,
+		CodeUpgradeAuthorization: code_upgrade_authorization::{Pallet, Call, Storage, Event<T>} = 76,
 
 		// State trie migration pallet, only temporary.
 		StateTrieMigration: pallet_state_trie_migration = 98,
@@ -1615,6 +1693,8 @@ construct_runtime! {
 
 		// Generalized message queue
 		MessageQueue: pallet_message_queue::{Pallet, Call, Storage, Event<T>} = 100,
+		UmpQueueBreaker: ump_queue_breaker::{Pallet, Call, Storage, Event<T>} = 101,
+		HrmpSystemChannels: hrmp_system_channels::{Pallet, Call, Storage, Event<T>} = 102,
 	}
 }
 
@@ -1738,6 +1818,7 @@ pub mod migrations {
 		parachains_configuration::migration::v7::MigrateToV7<Runtime>,
 		paras_scheduler_migration::v1::MigrateToV1<Runtime>,
 		parachains_configuration::migration::v8::MigrateToV8<Runtime>,
+		async_backing_migration::EnableAsyncBacking<Runtime>,
 
 		// Unlock/unreserve balances from Gov v1 pallets that hold them
 		// https://github.com/paritytech/polkadot/issues/6749
@@ -1796,6 +1877,7 @@ mod benches {
 		[runtime_parachains::initializer, Initializer]
 		[runtime_parachains::paras_inherent, ParaInherent]
 		[runtime_parachains::paras, Paras]
+		[lease_retirement, LeaseRetirement]
 		// Substrate
 		[pallet_balances, Balances]
 		[pallet_balances, NisCounterpartBalances]
@@ -1904,6 +1986,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(7)]
 	impl primitives::runtime_api::ParachainHost<Block, Hash, BlockNumber> for Runtime {
 		fn validators() -> Vec<ValidatorId> {
 			parachains_runtime_api_impl::validators::<Runtime>()
@@ -2034,6 +2117,18 @@ sp_api::impl_runtime_apis! {
 				key_ownership_proof,
 			)
 		}
+
+		fn minimum_backing_votes() -> u32 {
+			parachains_staging_runtime_api_impl::minimum_backing_votes::<Runtime>()
+		}
+
+		fn para_backing_state(para_id: ParaId) -> Option<primitives::vstaging::BackingState> {
+			parachains_staging_runtime_api_impl::backing_state::<Runtime>(para_id)
+		}
+
+		fn async_backing_params() -> primitives::vstaging::AsyncBackingParams {
+			parachains_staging_runtime_api_impl::async_backing_params::<Runtime>()
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block, BeefyId> for Runtime {
@@ -2238,6 +2333,30 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl ump_queue_breaker::UmpQueueBreakerApi<Block, BlockNumber> for Runtime {
+		fn paused_queues() -> Vec<(ParaId, ump_queue_breaker::PauseInfo<BlockNumber>)> {
+			UmpQueueBreaker::paused_queues()
+		}
+	}
+
+	impl lease_retirement::LeaseRetirementApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn outstanding_leases() -> Vec<lease_retirement::OutstandingLease<AccountId, Balance, BlockNumber>> {
+			lease_retirement::outstanding_leases::<Runtime>()
+		}
+	}
+
+	impl validator_disabling::DisabledValidatorsApi<Block> for Runtime {
+		fn disabled_validators() -> Vec<ValidatorIndex> {
+			validator_disabling::disabled_validators::<Runtime>()
+		}
+	}
+
+	impl society_status::SocietyStatusApi<Block, AccountId, Balance> for Runtime {
+		fn society_status(who: AccountId) -> society_status::SocietyStatus<Balance> {
+			society_status::society_status(who)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -2652,6 +2771,36 @@ mod remote_tests {
 		ext.execute_with(|| Runtime::on_runtime_upgrade(UpgradeCheckSelect::PreAndPost));
 	}
 
+	#[tokio::test]
+	async fn refund_all_expired_crowdloans() {
+		if var("RUN_CROWDLOAN_REFUND_TEST").is_err() {
+			return
+		}
+
+		sp_tracing::try_init_simple();
+		let mut ext = remote_ext_test_setup().await;
+		ext.execute_with(|| {
+			use crowdloan_refunder::Funds;
+			use frame_support::traits::Hooks;
+
+			let now = System::block_number();
+			let expired: Vec<_> = Funds::<Runtime>::iter()
+				.filter(|(_, fund)| CrowdloanRefunder::is_expired(now, fund))
+				.map(|(para, _)| para)
+				.collect();
+			log::info!("{} expired crowdloans", expired.len());
+
+			let limit = <Runtime as frame_system::Config>::BlockWeights::get().max_block;
+			let mut blocks = 0;
+			while expired.iter().any(|para| Funds::<Runtime>::contains_key(para)) {
+				CrowdloanRefunder::on_idle(now, limit);
+				blocks += 1;
+				assert!(blocks < 10_000, "expired crowdloans are not refunded");
+			}
+			log::info!("refunded all expired crowdloans in {} blocks", blocks);
+		});
+	}
+
 	#[tokio::test]
 	#[ignore = "this test is meant to be executed manually"]
 	async fn try_fast_unstake_all() {
@@ -0,0 +1,109 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! The state of an account in `Society`, for accounts acting locally, through a proxy or, from
+//! Asset Hub and the People chain, through XCM.
+
+use crate::{AccountId, Balance, Runtime};
+use pallet_society::{BidKind, Bids, Candidates, Defending, Founder, Head, Members, Skeptic};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// The votes on a candidate or the defender.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Votes {
+	pub approvals: u32,
+	pub rejections: u32,
+}
+
+/// The candidacy of an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Candidacy<Balance> {
+	/// The payout the candidate asked for.
+	pub bid: Balance,
+	/// Whether a member vouched for the candidate.
+	pub vouched: bool,
+	/// The votes on the candidate.
+	pub votes: Votes,
+	/// Whether the skeptic of the candidate was struck already.
+	pub skeptic_struck: bool,
+}
+
+/// The state of an account in `Society`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SocietyStatus<Balance> {
+	/// The rank of the account, if it is a member.
+	pub rank: Option<u32>,
+	/// The strikes of the account, if it is a member.
+	pub strikes: u32,
+	/// Whether the account is the founder.
+	pub founder: bool,
+	/// Whether the account is the head.
+	pub head: bool,
+	/// The bid of the account, if it bid for membership.
+	pub bid: Option<Balance>,
+	/// The candidacy of the account, if it is a candidate.
+	pub candidacy: Option<Candidacy<Balance>>,
+	/// Whether the account is the skeptic of the candidates or of the defender.
+	pub skeptic: bool,
+	/// The votes on the account, if it is the defender.
+	pub defending: Option<Votes>,
+}
+
+/// The state of `who` in `Society`.
+pub fn society_status(who: AccountId) -> SocietyStatus<Balance> {
+	let member = Members::<Runtime>::get(&who);
+	let defending = Defending::<Runtime>::get();
+
+	SocietyStatus {
+		rank: member.as_ref().map(|record| record.rank),
+		strikes: member.map_or(0, |record| record.strikes),
+		founder: Founder::<Runtime>::get().as_ref() == Some(&who),
+		head: Head::<Runtime>::get().as_ref() == Some(&who),
+		bid: Bids::<Runtime>::get()
+			.into_iter()
+			.find(|bid| bid.who == who)
+			.map(|bid| bid.value),
+		candidacy: Candidates::<Runtime>::get(&who).map(|candidacy| Candidacy {
+			bid: candidacy.bid,
+			vouched: matches!(candidacy.kind, BidKind::Vouch(..)),
+			votes: Votes {
+				approvals: candidacy.tally.approvals,
+				rejections: candidacy.tally.rejections,
+			},
+			skeptic_struck: candidacy.skeptic_struck,
+		}),
+		skeptic: Skeptic::<Runtime>::get().as_ref() == Some(&who) ||
+			defending.as_ref().map_or(false, |(_, skeptic, _)| *skeptic == who),
+		defending: defending.and_then(|(defender, _, tally)| {
+			(defender == who)
+				.then(|| Votes { approvals: tally.approvals, rejections: tally.rejections })
+		}),
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to query the state of an account in `Society`.
+	pub trait SocietyStatusApi<AccountId, Balance>
+	where
+		AccountId: parity_scale_codec::Codec,
+		Balance: parity_scale_codec::Codec,
+	{
+		/// The membership, bid, candidacy, skeptic and defender state of `who`.
+		fn society_status(who: AccountId) -> SocietyStatus<Balance>;
+	}
+}
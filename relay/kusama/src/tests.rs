@@ -146,6 +146,749 @@ fn nominator_limit() {
 	println!("can support {} nominators to yield a weight of {}", active, weight_with(active));
 }
 
+#[test]
+fn ump_queue_breaker_pauses_failing_para() {
+	use frame_support::{assert_ok, traits::QueuePausedQuery};
+	use ump_queue_breaker::PauseReason;
+
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		let para = ParaId::from(2000);
+		let is_paused =
+			|| UmpQueueBreaker::is_paused(&AggregateMessageOrigin::Ump(UmpQueueId::Para(para)));
+
+		for _ in 0..UmpMaxFailures::get() {
+			UmpQueueBreaker::note_processed(para, &Err(ProcessMessageError::Unsupported));
+		}
+		// Messages which were processed, even if their execution failed, and those exceeding the
+		// weight left in the block do not count.
+		UmpQueueBreaker::note_processed(para, &Ok(true));
+		UmpQueueBreaker::note_processed(para, &Ok(false));
+		UmpQueueBreaker::note_processed(
+			para,
+			&Err(ProcessMessageError::Overweight(Weight::from_parts(1, 1))),
+		);
+		assert!(!is_paused());
+
+		UmpQueueBreaker::note_processed(para, &Err(ProcessMessageError::Corrupt));
+		assert!(is_paused());
+		assert_eq!(UmpQueueBreaker::paused_queues()[0].1.reason, PauseReason::CircuitBreaker);
+
+		assert_ok!(UmpQueueBreaker::resume(RuntimeOrigin::root(), para));
+		assert!(!is_paused());
+		assert_ok!(UmpQueueBreaker::pause(RuntimeOrigin::root(), para));
+		assert_eq!(UmpQueueBreaker::paused_queues()[0].1.reason, PauseReason::Governance);
+	});
+}
+
+#[test]
+fn ump_queue_breaker_thresholds_are_set_by_governance() {
+	use frame_support::{assert_noop, assert_ok, traits::QueuePausedQuery};
+	use sp_runtime::DispatchError;
+	use ump_queue_breaker::Thresholds;
+
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		let para = ParaId::from(2000);
+		let is_paused =
+			|| UmpQueueBreaker::is_paused(&AggregateMessageOrigin::Ump(UmpQueueId::Para(para)));
+		let thresholds = Thresholds { failure_window: 10, max_failures: 1 };
+		assert_eq!(
+			UmpQueueBreaker::thresholds(),
+			Thresholds {
+				failure_window: UmpFailureWindow::get(),
+				max_failures: UmpMaxFailures::get(),
+			}
+		);
+
+		assert_noop!(
+			UmpQueueBreaker::set_thresholds(RuntimeOrigin::none(), Some(thresholds)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(UmpQueueBreaker::set_thresholds(RuntimeOrigin::root(), Some(thresholds)));
+		assert_eq!(UmpQueueBreaker::thresholds(), thresholds);
+
+		// Failures are only counted within the failure window.
+		System::set_block_number(1);
+		UmpQueueBreaker::note_processed(para, &Err(ProcessMessageError::Corrupt));
+		System::set_block_number(11);
+		UmpQueueBreaker::note_processed(para, &Err(ProcessMessageError::Corrupt));
+		assert!(!is_paused());
+		UmpQueueBreaker::note_processed(para, &Err(ProcessMessageError::Corrupt));
+		assert!(is_paused());
+
+		// The queues of system parachains are never paused automatically.
+		let system_para = ParaId::from(1000);
+		for _ in 0..=thresholds.max_failures {
+			UmpQueueBreaker::note_processed(system_para, &Err(ProcessMessageError::Corrupt));
+		}
+		assert!(!UmpQueueBreaker::is_paused(&AggregateMessageOrigin::Ump(UmpQueueId::Para(
+			system_para
+		)));
+
+		assert_ok!(UmpQueueBreaker::set_thresholds(RuntimeOrigin::root(), None));
+		assert_eq!(UmpQueueBreaker::thresholds().max_failures, UmpMaxFailures::get());
+	});
+}
+
+#[test]
+fn retired_leases_unreserve_deposits_at_lease_end() {
+	use frame_support::{assert_ok, traits::Hooks};
+
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		let para = ParaId::from(2000);
+		let leaser = Charlie.to_account_id();
+		let deposit = 100 * UNITS;
+		Balances::make_free_balance_be(&leaser, 1_000 * UNITS);
+
+		assert_ok!(Slots::force_lease(RuntimeOrigin::root(), para, leaser.clone(), deposit, 0, 2));
+		assert_eq!(Balances::reserved_balance(&leaser), deposit);
+
+		assert_ok!(LeaseRetirement::retire_leases(RuntimeOrigin::root(), 10));
+		assert!(slots::Leases::<Runtime>::get(para).is_empty());
+		let leases = lease_retirement::outstanding_leases::<Runtime>();
+		assert_eq!(leases.len(), 1);
+		assert!(leases[0].retired);
+		assert_eq!(leases[0].remaining_periods, 2);
+		assert_eq!(leases[0].ends_at, 2 * LeasePeriod::get());
+		assert_eq!(leases[0].deposits, vec![(leaser.clone(), deposit)]);
+
+		// The deposit is held until the last lease period ended.
+		LeaseRetirement::on_initialize(LeasePeriod::get());
+		assert_eq!(Balances::reserved_balance(&leaser), deposit);
+		LeaseRetirement::on_initialize(2 * LeasePeriod::get());
+		assert_eq!(Balances::reserved_balance(&leaser), 0);
+		assert!(lease_retirement::outstanding_leases::<Runtime>().is_empty());
+	});
+}
+
+#[test]
+fn retired_leases_end_over_several_blocks() {
+	use frame_support::{assert_noop, assert_ok, traits::Hooks};
+
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		let leaser = Charlie.to_account_id();
+		let deposit = UNITS;
+		let count = MaxRetiredLeasesPerBlock::get() + 1;
+		Balances::make_free_balance_be(&leaser, 1_000 * UNITS);
+		for i in 0..count {
+			let para = ParaId::from(2000 + i);
+			assert_ok!(Slots::force_lease(RuntimeOrigin::root(), para, leaser.clone(), deposit, 0, 1));
+		}
+		assert_ok!(LeaseRetirement::retire_leases(RuntimeOrigin::root(), count));
+		let reserved = Balances::reserved_balance(&leaser);
+
+		// Only `MaxRetiredLeasesPerBlock` leases end at the lease period start, and no leases can
+		// be retired until the others ended in the next block.
+		LeaseRetirement::on_initialize(LeasePeriod::get());
+		assert_eq!(lease_retirement::outstanding_leases::<Runtime>().len(), 1);
+		assert_eq!(Balances::reserved_balance(&leaser), reserved - (count - 1) as Balance * deposit);
+		assert_noop!(
+			LeaseRetirement::retire_leases(RuntimeOrigin::root(), count),
+			lease_retirement::Error::<Runtime>::LeasePeriodEnding,
+		);
+
+		LeaseRetirement::on_initialize(LeasePeriod::get() + 1);
+		assert!(lease_retirement::outstanding_leases::<Runtime>().is_empty());
+		assert_eq!(Balances::reserved_balance(&leaser), 0);
+		assert_ok!(LeaseRetirement::retire_leases(RuntimeOrigin::root(), count));
+	});
+}
+
+#[test]
+fn crowdloan_refunder_idles_after_a_complete_sweep() {
+	use crowdloan_refunder::{Sweep, SweepState};
+	use frame_support::traits::Hooks;
+
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		let now = 10;
+		let limit = <Runtime as frame_system::Config>::BlockWeights::get().max_block;
+		let idle = <Runtime as frame_system::Config>::DbWeight::get().reads(2);
+
+		// A complete sweep waits for the next lease period start.
+		assert!(CrowdloanRefunder::on_idle(now, limit).any_gt(idle));
+		assert_eq!(
+			SweepState::<Runtime>::get(),
+			Some(Sweep::Done { wake: LeasePeriod::get(), next_fund_index: 0 })
+		);
+		assert_eq!(CrowdloanRefunder::on_idle(now + 1, limit), idle);
+
+		// Unreserving a deposit of a lease sweeps the funds again.
+		CrowdloanRefunder::sweep_again(now + 1);
+		assert!(CrowdloanRefunder::on_idle(now + 1, limit).any_gt(idle));
+		assert_eq!(CrowdloanRefunder::on_idle(now + 2, limit), idle);
+		assert!(CrowdloanRefunder::on_idle(LeasePeriod::get(), limit).any_gt(idle));
+	});
+}
+
+#[test]
+fn code_upgrade_authorization_works() {
+	use code_upgrade_authorization::Error;
+	use frame_support::{assert_noop, assert_ok};
+	use primitives::ValidationCode;
+
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		System::set_block_number(1);
+		let para = ParaId::from(2000);
+		let anyone = RuntimeOrigin::signed(Charlie.to_account_id());
+		let code = ValidationCode(vec![1, 2, 3]);
+
+		assert_noop!(
+			CodeUpgradeAuthorization::authorize_code_upgrade(anyone.clone(), para, code.hash()),
+			Error::<Runtime>::NotManager,
+		);
+		assert_noop!(
+			CodeUpgradeAuthorization::apply_authorized_code_upgrade(
+				anyone.clone(),
+				para,
+				code.clone(),
+			),
+			Error::<Runtime>::NotAuthorized,
+		);
+
+		assert_ok!(CodeUpgradeAuthorization::authorize_code_upgrade(
+			RuntimeOrigin::root(),
+			para,
+			code.hash(),
+		));
+		assert_noop!(
+			CodeUpgradeAuthorization::apply_authorized_code_upgrade(
+				anyone.clone(),
+				para,
+				ValidationCode(vec![4, 5, 6]),
+			),
+			Error::<Runtime>::CodeMismatch,
+		);
+		assert_noop!(
+			CodeUpgradeAuthorization::remove_expired_authorization(anyone.clone(), para),
+			Error::<Runtime>::AuthorizationNotExpired,
+		);
+
+		System::set_block_number(1 + CodeUpgradeAuthorizationPeriod::get());
+		assert_noop!(
+			CodeUpgradeAuthorization::apply_authorized_code_upgrade(
+				anyone.clone(),
+				para,
+				code.clone(),
+			),
+			Error::<Runtime>::AuthorizationExpired,
+		);
+		assert_ok!(CodeUpgradeAuthorization::remove_expired_authorization(anyone.clone(), para));
+		System::assert_last_event(RuntimeEvent::CodeUpgradeAuthorization(
+			code_upgrade_authorization::Event::AuthorizationExpired {
+				para,
+				code_hash: code.hash(),
+			},
+		));
+
+		assert_ok!(CodeUpgradeAuthorization::lock_permanently(RuntimeOrigin::root(), para));
+		assert_noop!(
+			CodeUpgradeAuthorization::authorize_code_upgrade(
+				RuntimeOrigin::root(),
+				para,
+				code.hash(),
+			),
+			Error::<Runtime>::ParaPermanentlyLocked,
+		);
+	});
+}
+
+#[test]
+fn permanently_locked_para_cannot_be_upgraded() {
+	use code_upgrade_authorization::PermanentlyLocked;
+	use frame_support::{assert_noop, assert_ok, dispatch::Pays, storage_alias, traits::Contains};
+	use primitives::{HeadData, ValidationCode};
+	use runtime_parachains::{configuration::HostConfiguration, paras::ParaLifecycle};
+	use sp_runtime::traits::Dispatchable;
+
+	#[storage_alias]
+	type ActiveConfig = StorageValue<Configuration, HostConfiguration<BlockNumber>, ValueQuery>;
+	#[storage_alias]
+	type ParaLifecycles = StorageMap<Paras, Twox64Concat, ParaId, ParaLifecycle>;
+
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		System::set_block_number(1);
+		ActiveConfig::put(HostConfiguration {
+			max_code_size: 1024,
+			max_head_data_size: 1024,
+			..Default::default()
+		});
+		let para = ParaId::from(2000);
+		let manager = RuntimeOrigin::signed(Charlie.to_account_id());
+		let anyone = RuntimeOrigin::signed(AccountId::from([1; 32]));
+		Balances::make_free_balance_be(&Charlie.to_account_id(), 1_000 * UNITS);
+		assert_ok!(Registrar::reserve(manager.clone()));
+		assert_ok!(Registrar::register(
+			manager.clone(),
+			para,
+			HeadData(vec![1]),
+			ValidationCode(vec![1]),
+		));
+		ParaLifecycles::insert(para, ParaLifecycle::Parachain);
+
+		// The manager authorizes an upgrade, which anyone applies for free.
+		let code = ValidationCode(vec![1, 2, 3]);
+		assert_ok!(CodeUpgradeAuthorization::authorize_code_upgrade(
+			manager.clone(),
+			para,
+			code.hash(),
+		));
+		let post_info = CodeUpgradeAuthorization::apply_authorized_code_upgrade(
+			anyone.clone(),
+			para,
+			code.clone(),
+		)
+		.unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		System::assert_last_event(RuntimeEvent::CodeUpgradeAuthorization(
+			code_upgrade_authorization::Event::AuthorizedCodeUpgradeApplied {
+				para,
+				code_hash: code.hash(),
+			},
+		));
+		assert!(code_upgrade_authorization::Authorizations::<Runtime>::get(para).is_none());
+
+		assert_ok!(CodeUpgradeAuthorization::lock_permanently(manager.clone(), para));
+		assert!(PermanentlyLocked::<Runtime>::contains_key(para));
+
+		// Neither the para nor its manager can remove the lock or upgrade the para directly.
+		let para_origin: RuntimeOrigin = parachains_origin::Origin::Parachain(para).into();
+		for call in [
+			paras_registrar::Call::remove_lock { para },
+			paras_registrar::Call::schedule_code_upgrade { para, new_code: code.clone() },
+			paras_registrar::Call::set_current_head { para, new_head: HeadData(vec![2]) },
+		] {
+			let call = RuntimeCall::Registrar(call);
+			assert!(!BaseFilter::contains(&call));
+			for origin in [para_origin.clone(), manager.clone()] {
+				assert_noop!(
+					call.clone().dispatch(origin),
+					frame_system::Error::<Runtime>::CallFiltered,
+				);
+			}
+		}
+		// Other paras are not affected.
+		assert!(BaseFilter::contains(&RuntimeCall::Registrar(
+			paras_registrar::Call::remove_lock { para: 2001.into() }
+		)));
+	});
+}
+
+#[test]
+fn async_backing_migration_is_idempotent() {
+	use crate::async_backing_migration::{EnableAsyncBacking, ASYNC_BACKING_PARAMS};
+	use frame_support::{storage_alias, traits::OnRuntimeUpgrade};
+	use runtime_parachains::configuration::HostConfiguration;
+
+	#[storage_alias]
+	type PendingConfigs = StorageValue<
+		Configuration,
+		Vec<(SessionIndex, HostConfiguration<BlockNumber>)>,
+		ValueQuery,
+	>;
+
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		assert_ne!(Configuration::config().async_backing_params, ASYNC_BACKING_PARAMS);
+
+		EnableAsyncBacking::<Runtime>::on_runtime_upgrade();
+		let pending = PendingConfigs::get();
+		assert_eq!(pending.len(), 1);
+		assert_eq!(pending[0].1.async_backing_params, ASYNC_BACKING_PARAMS);
+
+		// Scheduled params are not scheduled again.
+		EnableAsyncBacking::<Runtime>::on_runtime_upgrade();
+		assert_eq!(PendingConfigs::get(), pending);
+	});
+}
+
+#[test]
+fn system_parachain_accounts_are_converted() {
+	use xcm::latest::prelude::*;
+	use xcm_config::SovereignAccountOf;
+	use xcm_executor::traits::ConvertLocation;
+
+	let account = |para: u32| MultiLocation {
+		parents: 0,
+		interior: X2(Parachain(para), AccountId32 { network: None, id: [1; 32] }),
+	};
+	let asset_hub = SovereignAccountOf::convert_location(&account(1000)).unwrap();
+	let people = SovereignAccountOf::convert_location(&account(1004)).unwrap();
+	assert_ne!(asset_hub, people);
+	assert_ne!(asset_hub, AccountId::from([1; 32]));
+	assert!(SovereignAccountOf::convert_location(&account(2000)).is_none());
+}
+
+#[test]
+fn nis_counterpart_is_teleportable_to_asset_hub() {
+	use frame_support::traits::ContainsPair;
+	use xcm::latest::prelude::*;
+	use xcm_config::{NisCounterpartLocation, TrustedTeleporters};
+
+	// Asset Hub Kusama expects KTC at this location.
+	assert_eq!(NisCounterpartLocation::get(), PalletInstance(45).into());
+
+	let ktc: MultiAsset = (NisCounterpartLocation::get(), 1).into();
+	assert!(TrustedTeleporters::contains(&ktc, &Parachain(1000).into()));
+	assert!(!TrustedTeleporters::contains(&ktc, &Parachain(1001).into()));
+}
+
+#[test]
+fn society_calls_over_xcm_need_signed_origin() {
+	use xcm_config::XcmCallDispatcher;
+	use xcm_executor::traits::CallDispatcher;
+
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		let bid = RuntimeCall::Society(pallet_society::Call::bid { value: 0 });
+		let proxied = RuntimeCall::Proxy(pallet_proxy::Call::proxy {
+			real: sp_runtime::MultiAddress::Id(AccountId::from([1; 32])),
+			force_proxy_type: None,
+			call: Box::new(bid.clone()),
+		});
+		for call in [bid, proxied] {
+			assert_eq!(
+				XcmCallDispatcher::dispatch(call, RuntimeOrigin::root()).map_err(|e| e.error),
+				Err(sp_runtime::DispatchError::BadOrigin),
+			);
+		}
+
+		// Society calls not exposed to XCM are filtered regardless of the origin.
+		let dissolve = RuntimeCall::Society(pallet_society::Call::dissolve {});
+		assert!(XcmCallDispatcher::dispatch(
+			dissolve,
+			RuntimeOrigin::signed(AccountId::from([1; 32]))
+		)
+		.is_err());
+	});
+}
+
+#[test]
+fn society_status_of_unknown_account_is_empty() {
+	use society_status::{society_status, SocietyStatus};
+
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		assert_eq!(
+			society_status(AccountId::from([1; 32])),
+			SocietyStatus {
+				rank: None,
+				strikes: 0,
+				founder: false,
+				head: false,
+				bid: None,
+				candidacy: None,
+				skeptic: false,
+				defending: None,
+			},
+		);
+	});
+}
+
+mod validator_disabling_tests {
+	use super::*;
+	use frame_support::{dispatch::DispatchClass, storage_alias, traits::Get};
+	use primitives::CandidateHash;
+	use validator_disabling::{disabled_validators, disabling_limit};
+
+	#[storage_alias]
+	type Validators = StorageValue<Session, Vec<AccountId>, ValueQuery>;
+	#[storage_alias]
+	type ActiveValidatorIndices = StorageValue<ParasShared, Vec<ValidatorIndex>, ValueQuery>;
+
+	fn with_validators(count: u32, f: impl FnOnce()) {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			Validators::put((0..count).map(|i| AccountId::from([i as u8; 32])).collect::<Vec<_>>());
+			// The active validators are the session validators in reverse order.
+			ActiveValidatorIndices::put((0..count).rev().map(ValidatorIndex).collect::<Vec<_>>());
+			pallet_staking::ActiveEra::<Runtime>::put(pallet_staking::ActiveEraInfo {
+				index: 0,
+				start: None,
+			});
+			f()
+		})
+	}
+
+	fn losers(indices: impl IntoIterator<Item = u32>) -> Vec<ValidatorIndex> {
+		indices.into_iter().map(ValidatorIndex).collect()
+	}
+
+	fn disable_losers(session: SessionIndex, dispute: u8, losers: &[ValidatorIndex]) -> Vec<u32> {
+		let candidate_hash = CandidateHash([dispute; 32].into());
+		validator_disabling::disable_losers::<Runtime, Offences>(session, candidate_hash, losers)
+	}
+
+	#[test]
+	fn disabling_limit_is_byzantine_threshold() {
+		assert_eq!(disabling_limit(0), 0);
+		assert_eq!(disabling_limit(1), 0);
+		assert_eq!(disabling_limit(3), 0);
+		assert_eq!(disabling_limit(4), 1);
+		assert_eq!(disabling_limit(6), 1);
+		assert_eq!(disabling_limit(7), 2);
+		assert_eq!(disabling_limit(10), 3);
+		assert_eq!(disabling_limit(1000), 333);
+	}
+
+	#[test]
+	fn dispute_losers_are_disabled_by_staking_up_to_limit() {
+		with_validators(10, || {
+			assert_eq!(disable_losers(0, 1, &losers([0, 1])), vec![9, 8]);
+			assert_eq!(disabled_validators::<Runtime>(), losers([0, 1]));
+
+			// Disabled validators are not reported again and do not count towards the limit.
+			assert_eq!(disable_losers(0, 2, &losers([1, 2, 3])), vec![7]);
+			assert_eq!(disabled_validators::<Runtime>(), losers([0, 1, 2]));
+			assert_eq!(Session::disabled_validators(), vec![7, 8, 9]);
+			assert_eq!(
+				pallet_staking::OffendingValidators::<Runtime>::get(),
+				vec![(7, true), (8, true), (9, true)]
+			);
+
+			// The reports are accounted for in the block weight.
+			let db = <Runtime as frame_system::Config>::DbWeight::get();
+			assert_eq!(
+				*System::block_weight().get(DispatchClass::Mandatory),
+				db.reads_writes(7, 2) +
+					db.reads_writes(8, 6) * 2 +
+					db.reads_writes(8, 2) +
+					db.reads_writes(8, 6),
+			);
+		});
+	}
+
+	#[test]
+	fn no_validator_is_disabled_below_four_validators() {
+		with_validators(3, || {
+			assert!(disable_losers(0, 1, &losers([0])).is_empty());
+			assert!(disabled_validators::<Runtime>().is_empty());
+		});
+	}
+
+	#[test]
+	fn losers_of_past_sessions_and_unknown_losers_are_not_disabled() {
+		with_validators(10, || {
+			assert!(disable_losers(1, 1, &losers([0])).is_empty());
+			assert!(disable_losers(0, 1, &losers([10])).is_empty());
+			assert!(Session::disabled_validators().is_empty());
+			assert!(pallet_staking::OffendingValidators::<Runtime>::get().is_empty());
+		});
+	}
+}
+
+mod hrmp_system_channels_tests {
+	use super::*;
+	use frame_support::{assert_noop, assert_ok, storage_alias};
+	use hrmp_system_channels::Event as SystemChannelsEvent;
+	use parachains_configuration::HostConfiguration;
+	use parachains_hrmp::{Event as HrmpEvent, HrmpOpenChannelRequest};
+	use parachains_paras::ParaLifecycle;
+	use primitives::HrmpChannelId;
+	use sp_runtime::{traits::AccountIdConversion, DispatchError};
+
+	#[storage_alias]
+	type ActiveConfig = StorageValue<Configuration, HostConfiguration<BlockNumber>, ValueQuery>;
+	#[storage_alias]
+	type ParaLifecycles = StorageMap<Paras, Twox64Concat, ParaId, ParaLifecycle>;
+	#[storage_alias]
+	type HrmpOpenChannelRequests =
+		StorageMap<Hrmp, Twox64Concat, HrmpChannelId, HrmpOpenChannelRequest>;
+
+	const ASSET_HUB: u32 = 1000;
+	const BRIDGE_HUB: u32 = 1002;
+	const PUBLIC: u32 = 2000;
+	const MAX_CAPACITY: u32 = 8;
+	const MAX_MESSAGE_SIZE: u32 = 1024;
+	const SENDER_DEPOSIT: Balance = 5 * UNITS;
+	const RECIPIENT_DEPOSIT: Balance = 7 * UNITS;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut ext = sp_io::TestExternalities::new_empty();
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			ActiveConfig::put(HostConfiguration {
+				hrmp_channel_max_capacity: MAX_CAPACITY,
+				hrmp_channel_max_total_size: MAX_CAPACITY * MAX_MESSAGE_SIZE,
+				hrmp_channel_max_message_size: MAX_MESSAGE_SIZE,
+				hrmp_max_parachain_outbound_channels: 10,
+				hrmp_max_parachain_inbound_channels: 10,
+				hrmp_sender_deposit: SENDER_DEPOSIT,
+				hrmp_recipient_deposit: RECIPIENT_DEPOSIT,
+				..Default::default()
+			});
+			for para in [ASSET_HUB, BRIDGE_HUB, PUBLIC] {
+				ParaLifecycles::insert(ParaId::from(para), ParaLifecycle::Parachain);
+				Balances::make_free_balance_be(&account(para), 1_000 * UNITS);
+			}
+		});
+		ext
+	}
+
+	fn account(para: u32) -> AccountId {
+		ParaId::from(para).into_account_truncating()
+	}
+
+	fn para_origin(para: u32) -> RuntimeOrigin {
+		parachains_origin::Origin::Parachain(para.into()).into()
+	}
+
+	fn channel_id(sender: u32, recipient: u32) -> HrmpChannelId {
+		HrmpChannelId { sender: sender.into(), recipient: recipient.into() }
+	}
+
+	#[test]
+	fn system_paras_get_bidirectional_channels() {
+		new_test_ext().execute_with(|| {
+			let issuance = Balances::total_issuance();
+			assert_ok!(HrmpSystemChannels::establish_system_channel(
+				RuntimeOrigin::signed(Charlie.to_account_id()),
+				ASSET_HUB.into(),
+				BRIDGE_HUB.into(),
+			));
+
+			for (sender, recipient) in [(ASSET_HUB, BRIDGE_HUB), (BRIDGE_HUB, ASSET_HUB)] {
+				System::assert_has_event(RuntimeEvent::Hrmp(HrmpEvent::HrmpChannelForceOpened(
+					sender.into(),
+					recipient.into(),
+					MAX_CAPACITY,
+					MAX_MESSAGE_SIZE,
+				)));
+				let request = HrmpOpenChannelRequests::get(channel_id(sender, recipient)).unwrap();
+				assert_eq!(request.sender_deposit, SENDER_DEPOSIT);
+			}
+			System::assert_last_event(RuntimeEvent::HrmpSystemChannels(
+				SystemChannelsEvent::SystemChannelsOpened {
+					sender: ASSET_HUB.into(),
+					recipient: BRIDGE_HUB.into(),
+				},
+			));
+			// Each system para pays the deposits of its sides from its sovereign account, and no
+			// funds are minted.
+			for para in [ASSET_HUB, BRIDGE_HUB] {
+				assert_eq!(
+					Balances::reserved_balance(&account(para)),
+					SENDER_DEPOSIT + RECIPIENT_DEPOSIT
+				);
+			}
+			assert_eq!(Balances::total_issuance(), issuance);
+		});
+	}
+
+	#[test]
+	fn system_paras_without_funds_get_no_channels() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&account(BRIDGE_HUB), 0);
+			assert!(HrmpSystemChannels::establish_system_channel(
+				RuntimeOrigin::signed(Charlie.to_account_id()),
+				ASSET_HUB.into(),
+				BRIDGE_HUB.into(),
+			)
+			.is_err());
+		});
+	}
+
+	#[test]
+	fn public_para_opens_channel_to_system_para() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				HrmpSystemChannels::establish_system_channel(
+					RuntimeOrigin::signed(Charlie.to_account_id()),
+					PUBLIC.into(),
+					ASSET_HUB.into(),
+				),
+				DispatchError::BadOrigin,
+			);
+			assert_noop!(
+				HrmpSystemChannels::establish_system_channel(
+					para_origin(PUBLIC + 1),
+					PUBLIC.into(),
+					ASSET_HUB.into(),
+				),
+				DispatchError::BadOrigin,
+			);
+
+			assert_ok!(HrmpSystemChannels::establish_system_channel(
+				para_origin(PUBLIC),
+				PUBLIC.into(),
+				ASSET_HUB.into(),
+			));
+			System::assert_has_event(RuntimeEvent::Hrmp(HrmpEvent::OpenChannelRequested(
+				PUBLIC.into(),
+				ASSET_HUB.into(),
+				MAX_CAPACITY,
+				MAX_MESSAGE_SIZE,
+			)));
+			System::assert_has_event(RuntimeEvent::Hrmp(HrmpEvent::OpenChannelAccepted(
+				PUBLIC.into(),
+				ASSET_HUB.into(),
+			)));
+			assert_eq!(Balances::reserved_balance(&account(PUBLIC)), SENDER_DEPOSIT);
+			assert_eq!(Balances::reserved_balance(&account(ASSET_HUB)), RECIPIENT_DEPOSIT);
+		});
+	}
+
+	#[test]
+	fn requested_channel_to_system_para_is_accepted() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Hrmp::hrmp_init_open_channel(
+				para_origin(PUBLIC),
+				ASSET_HUB.into(),
+				MAX_CAPACITY,
+				MAX_MESSAGE_SIZE,
+			));
+
+			assert_ok!(HrmpSystemChannels::establish_system_channel(
+				para_origin(PUBLIC),
+				PUBLIC.into(),
+				ASSET_HUB.into(),
+			));
+			assert!(HrmpOpenChannelRequests::get(channel_id(PUBLIC, ASSET_HUB)).unwrap().confirmed);
+			assert_eq!(Balances::reserved_balance(&account(PUBLIC)), SENDER_DEPOSIT);
+		});
+	}
+
+	#[test]
+	fn public_para_opens_channel_from_system_para() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				HrmpSystemChannels::establish_system_channel(
+					RuntimeOrigin::signed(Charlie.to_account_id()),
+					ASSET_HUB.into(),
+					PUBLIC.into(),
+				),
+				DispatchError::BadOrigin,
+			);
+
+			assert_ok!(HrmpSystemChannels::establish_system_channel(
+				para_origin(PUBLIC),
+				ASSET_HUB.into(),
+				PUBLIC.into(),
+			));
+			System::assert_has_event(RuntimeEvent::Hrmp(HrmpEvent::OpenChannelRequested(
+				ASSET_HUB.into(),
+				PUBLIC.into(),
+				MAX_CAPACITY,
+				MAX_MESSAGE_SIZE,
+			)));
+			System::assert_has_event(RuntimeEvent::Hrmp(HrmpEvent::OpenChannelAccepted(
+				ASSET_HUB.into(),
+				PUBLIC.into(),
+			)));
+			assert_eq!(Balances::reserved_balance(&account(ASSET_HUB)), SENDER_DEPOSIT);
+			assert_eq!(Balances::reserved_balance(&account(PUBLIC)), RECIPIENT_DEPOSIT);
+		});
+	}
+
+	#[test]
+	fn public_paras_are_rejected() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				HrmpSystemChannels::establish_system_channel(
+					para_origin(PUBLIC),
+					PUBLIC.into(),
+					(PUBLIC + 1).into(),
+				),
+				hrmp_system_channels::Error::<Runtime>::NoSystemParachain,
+			);
+		});
+	}
+}
+
 #[test]
 fn call_size() {
 	RuntimeCall::assert_size_under(256);
@@ -17,17 +17,21 @@
 //! XCM configurations for the Kusama runtime.
 
 use super::{
-	parachains_origin, AccountId, AllPalletsWithSystem, Balances, Dmp, Fellows, ParaId, Runtime,
-	RuntimeCall, RuntimeEvent, RuntimeOrigin, StakingAdmin, TransactionByteFee, WeightToFee,
-	XcmPallet,
+	parachains_origin, AccountId, AllPalletsWithSystem, Balances, Dmp, Fellows,
+	NisCounterpartBalances, ParaId, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin,
+	StakingAdmin, TransactionByteFee, WeightToFee, XcmPallet,
 };
 use frame_support::{
+	dispatch::{DispatchErrorWithPostInfo, PostDispatchInfo},
 	match_types, parameter_types,
-	traits::{Contains, Everything, Nothing},
+	traits::{Contains, Everything, Nothing, PalletInfoAccess},
 	weights::Weight,
 };
 use frame_system::EnsureRoot;
-use kusama_runtime_constants::currency::CENTS;
+use kusama_runtime_constants::{
+	currency::CENTS,
+	system_parachain::{ASSET_HUB_ID, PEOPLE_ID},
+};
 use runtime_common::{
 	crowdloan, paras_registrar,
 	xcm_sender::{ChildParachainRouter, ExponentialPrice},
@@ -39,12 +43,12 @@ use xcm_builder::{
 	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
 	AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom, ChildParachainAsNative,
 	ChildParachainConvertsVia, ChildSystemParachainAsSuperuser,
-	CurrencyAdapter as XcmCurrencyAdapter, IsChildSystemParachain, IsConcrete, MintLocation,
-	OriginToPluralityVoice, SignedAccountId32AsNative, SignedToAccountId32,
-	SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId, UsingComponents,
-	WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
+	CurrencyAdapter as XcmCurrencyAdapter, DescribeAllTerminal, DescribeFamily, HashedDescription,
+	IsChildSystemParachain, IsConcrete, MintLocation, OriginToPluralityVoice,
+	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
+	TrailingSetTopicAsId, UsingComponents, WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
 };
-use xcm_executor::traits::WithOriginFilter;
+use xcm_executor::traits::{CallDispatcher, ConvertLocation, WithOriginFilter};
 
 parameter_types! {
 	/// The location of the KSM token, from the context of this chain. Since this token is native to this
@@ -61,6 +65,10 @@ parameter_types! {
 	pub CheckAccount: AccountId = XcmPallet::check_account();
 	/// The check account that is allowed to mint assets locally.
 	pub LocalCheckAccount: (AccountId, MintLocation) = (CheckAccount::get(), MintLocation::Local);
+	/// The location of the NIS counterpart token (KTC), i.e. of the `NisCounterpartBalances`
+	/// pallet.
+	pub NisCounterpartLocation: MultiLocation =
+		PalletInstance(<NisCounterpartBalances as PalletInfoAccess>::index() as u8).into();
 }
 
 /// The canonical means of converting a `MultiLocation` into an `AccountId`, used when we want to
@@ -70,8 +78,27 @@ pub type SovereignAccountOf = (
 	ChildParachainConvertsVia<ParaId, AccountId>,
 	// We can directly alias an `AccountId32` into a local account.
 	AccountId32Aliases<ThisNetwork, AccountId>,
+	// Accounts of Asset Hub and the People chain get a local account derived from their location.
+	SystemParachainAccountsConvertsVia,
 );
 
+/// Converts the `AccountId32` accounts of Asset Hub and the People chain into local accounts, such
+/// that they can hold funds and dispatch calls, e.g. of `Society`, through XCM `Transact`.
+pub struct SystemParachainAccountsConvertsVia;
+impl ConvertLocation<AccountId> for SystemParachainAccountsConvertsVia {
+	fn convert_location(location: &MultiLocation) -> Option<AccountId> {
+		match location {
+			MultiLocation {
+				parents: 0,
+				interior: X2(Parachain(ASSET_HUB_ID | PEOPLE_ID), AccountId32 { .. }),
+			} => HashedDescription::<AccountId, DescribeFamily<DescribeAllTerminal>>::convert_location(
+				location,
+			),
+			_ => None,
+		}
+	}
+}
+
 /// Our asset transactor. This is what allows us to interest with the runtime facilities from the
 /// point of view of XCM-only concepts like `MultiLocation` and `MultiAsset`.
 ///
@@ -89,6 +116,26 @@ pub type LocalAssetTransactor = XcmCurrencyAdapter<
 	LocalCheckAccount,
 >;
 
+/// Means for transacting the NIS counterpart token (KTC), mapped to `NisCounterpartLocation`.
+///
+/// Teleports are tracked in the check account, such that the total issuance of KTC, which `Nis`
+/// relies on, includes the KTC teleported to Asset Hub.
+pub type NisCounterpartAssetTransactor = XcmCurrencyAdapter<
+	// Use this currency:
+	NisCounterpartBalances,
+	// Use this currency when it is a fungible asset matching the given location or name:
+	IsConcrete<NisCounterpartLocation>,
+	// We can convert the MultiLocations with our converter above:
+	SovereignAccountOf,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// We track our teleports in/out to keep total issuance correct.
+	LocalCheckAccount,
+>;
+
+/// Means for transacting assets on this chain.
+pub type AssetTransactors = (LocalAssetTransactor, NisCounterpartAssetTransactor);
+
 /// The means that we convert the XCM message origin location into a local dispatch origin.
 type LocalOriginConverter = (
 	// A `Signed` origin of the sovereign account that the original location controls.
@@ -130,10 +177,15 @@ parameter_types! {
 	pub const Encointer: MultiLocation = Parachain(1001).into_location();
 	pub const KsmForStatemine: (MultiAssetFilter, MultiLocation) = (Ksm::get(), Statemine::get());
 	pub const KsmForEncointer: (MultiAssetFilter, MultiLocation) = (Ksm::get(), Encointer::get());
+	pub Ktc: MultiAssetFilter = Wild(AllOf { fun: WildFungible, id: Concrete(NisCounterpartLocation::get()) });
+	pub KtcForStatemine: (MultiAssetFilter, MultiLocation) = (Ktc::get(), Statemine::get());
 	pub const MaxAssetsIntoHolding: u32 = 64;
 }
-pub type TrustedTeleporters =
-	(xcm_builder::Case<KsmForStatemine>, xcm_builder::Case<KsmForEncointer>);
+pub type TrustedTeleporters = (
+	xcm_builder::Case<KsmForStatemine>,
+	xcm_builder::Case<KsmForEncointer>,
+	xcm_builder::Case<KtcForStatemine>,
+);
 
 match_types! {
 	pub type OnlyParachains: impl Contains<MultiLocation> = {
@@ -265,7 +317,29 @@ impl Contains<RuntimeCall> for SafeCallFilter {
 				pallet_identity::Call::remove_sub { .. } |
 				pallet_identity::Call::quit_sub { .. },
 			) |
-			RuntimeCall::Society(..) |
+			RuntimeCall::Society(
+				pallet_society::Call::bid { .. } |
+				pallet_society::Call::unbid { .. } |
+				pallet_society::Call::vouch { .. } |
+				pallet_society::Call::unvouch { .. } |
+				pallet_society::Call::vote { .. } |
+				pallet_society::Call::defender_vote { .. } |
+				pallet_society::Call::payout { .. } |
+				pallet_society::Call::waive_repay { .. } |
+				pallet_society::Call::punish_skeptic { .. } |
+				pallet_society::Call::claim_membership { .. } |
+				pallet_society::Call::bestow_membership { .. } |
+				pallet_society::Call::kick_candidate { .. } |
+				pallet_society::Call::resign_candidacy { .. } |
+				pallet_society::Call::drop_candidate { .. } |
+				pallet_society::Call::cleanup_candidacy { .. } |
+				pallet_society::Call::cleanup_challenge { .. },
+			) |
+			RuntimeCall::Nis(
+				pallet_nis::Call::thaw_communal { .. } |
+				pallet_nis::Call::communify { .. } |
+				pallet_nis::Call::privatize { .. },
+			) |
 			RuntimeCall::Recovery(..) |
 			RuntimeCall::Vesting(..) |
 			RuntimeCall::Bounties(
@@ -313,11 +387,42 @@ impl Contains<RuntimeCall> for SafeCallFilter {
 	}
 }
 
+/// Whether `call` is a `Society` call, directly or through a proxy or derivative account.
+fn is_society_call(call: &RuntimeCall) -> bool {
+	match call {
+		RuntimeCall::Society(..) => true,
+		RuntimeCall::Proxy(
+			pallet_proxy::Call::proxy { call, .. } |
+			pallet_proxy::Call::proxy_announced { call, .. },
+		) |
+		RuntimeCall::Utility(pallet_utility::Call::as_derivative { call, .. }) => is_society_call(call),
+		_ => false,
+	}
+}
+
+/// Dispatches the calls of XCM `Transact` which pass [`SafeCallFilter`].
+///
+/// `Society` calls, including those made through a proxy, are only dispatched for `Signed` origins,
+/// i.e. the sovereign accounts of [`SovereignAccountOf`], such as the accounts of Asset Hub and the
+/// People chain. In particular, system parachains cannot act on `Society` as `Root`.
+pub struct XcmCallDispatcher;
+impl CallDispatcher<RuntimeCall> for XcmCallDispatcher {
+	fn dispatch(
+		call: RuntimeCall,
+		origin: RuntimeOrigin,
+	) -> Result<PostDispatchInfo, DispatchErrorWithPostInfo<PostDispatchInfo>> {
+		if is_society_call(&call) && frame_system::ensure_signed(origin.clone()).is_err() {
+			return Err(sp_runtime::DispatchError::BadOrigin.into())
+		}
+		WithOriginFilter::<SafeCallFilter>::dispatch(call, origin)
+	}
+}
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type RuntimeCall = RuntimeCall;
 	type XcmSender = XcmRouter;
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor = AssetTransactors;
 	type OriginConverter = LocalOriginConverter;
 	type IsReserve = ();
 	type IsTeleporter = TrustedTeleporters;
@@ -343,7 +448,7 @@ impl xcm_executor::Config for XcmConfig {
 	// No bridges yet...
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
-	type CallDispatcher = WithOriginFilter<SafeCallFilter>;
+	type CallDispatcher = XcmCallDispatcher;
 	type SafeCallFilter = SafeCallFilter;
 	type Aliasers = Nothing;
 }
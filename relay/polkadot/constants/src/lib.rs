@@ -111,12 +111,31 @@ pub mod xcm {
 	}
 }
 
+/// Parachain consensus related constants.
+pub mod async_backing {
+	use primitives::vstaging::AsyncBackingParams;
+
+	/// The asynchronous backing parameters of the Relay Chain.
+	///
+	/// Parachains may build on relay parents up to `allowed_ancestry_len` blocks old and have up to
+	/// `max_candidate_depth + 1` candidates backed on top of each other.
+	pub const ASYNC_BACKING_PARAMS: AsyncBackingParams =
+		AsyncBackingParams { max_candidate_depth: 3, allowed_ancestry_len: 2 };
+}
+
 /// System Parachains.
 pub mod system_parachain {
 	/// Statemint parachain ID.
 	pub const STATEMINT_ID: u32 = 1000;
 	/// Collectives parachain ID.
 	pub const COLLECTIVES_ID: u32 = 1001;
+
+	/// Constants of the Collectives parachain the Relay Chain needs to be aware of.
+	pub mod collectives {
+		/// The index of the Fellowship treasury pallet. The funds of the Fellowship treasury are
+		/// held on the Asset Hub, by the sovereign account of the location of this pallet.
+		pub const FELLOWSHIP_TREASURY_PALLET_INDEX: u8 = 65;
+	}
 }
 
 #[cfg(test)]
@@ -0,0 +1,87 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! Enables asynchronous backing by scheduling [`ASYNC_BACKING_PARAMS`] in the host
+//! configuration.
+//!
+//! The parameters are part of the configuration since its v5 storage version, so enabling them is
+//! a change of values, not of the storage layout. The migration is therefore not keyed on the
+//! storage version of `Configuration`, but checks the active and pending configurations, which
+//! makes it a no-op once the parameters are set.
+
+use frame_support::{storage_alias, traits::OnRuntimeUpgrade, weights::Weight};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+pub use polkadot_runtime_constants::async_backing::ASYNC_BACKING_PARAMS;
+use primitives::{vstaging::AsyncBackingParams, SessionIndex};
+use runtime_parachains::configuration::{self, HostConfiguration};
+use sp_core::Get;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+const LOG_TARGET: &str = "runtime::parachains::configuration";
+
+#[storage_alias]
+type PendingConfigs<T: configuration::Config> = StorageValue<
+	configuration::Pallet<T>,
+	Vec<(SessionIndex, HostConfiguration<BlockNumberFor<T>>)>,
+	frame_support::pallet_prelude::ValueQuery,
+>;
+
+/// The asynchronous backing parameters the configuration will end up with, taking the pending
+/// configurations into account.
+fn scheduled_params<T: configuration::Config>() -> AsyncBackingParams {
+	PendingConfigs::<T>::get()
+		.last()
+		.map(|(_, config)| config.async_backing_params)
+		.unwrap_or_else(|| configuration::Pallet::<T>::config().async_backing_params)
+}
+
+/// Schedule [`ASYNC_BACKING_PARAMS`] for the next session, unless already active or scheduled.
+pub struct EnableAsyncBacking<T>(PhantomData<T>);
+impl<T: configuration::Config> OnRuntimeUpgrade for EnableAsyncBacking<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let weight = T::DbWeight::get().reads(2);
+		if scheduled_params::<T>() == ASYNC_BACKING_PARAMS {
+			log::info!(target: LOG_TARGET, "Asynchronous backing already enabled, skipping");
+			return weight
+		}
+
+		match configuration::Pallet::<T>::set_async_backing_params(
+			RawOrigin::Root.into(),
+			ASYNC_BACKING_PARAMS,
+		) {
+			Ok(_) => log::info!(
+				target: LOG_TARGET,
+				"Scheduled asynchronous backing params {:?}",
+				ASYNC_BACKING_PARAMS,
+			),
+			Err(error) => log::error!(
+				target: LOG_TARGET,
+				"Failed to schedule asynchronous backing params: {:?}",
+				error,
+			),
+		}
+		weight.saturating_add(T::DbWeight::get().reads_writes(3, 1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+		frame_support::ensure!(
+			scheduled_params::<T>() == ASYNC_BACKING_PARAMS,
+			"Asynchronous backing params should be scheduled after the migration"
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,87 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! Auxiliary `struct`/`enum`s for the Polkadot runtime.
+
+use crate::{Balance, Runtime, RuntimeOrigin, Treasury, XcmPallet, LOG_TARGET};
+use frame_support::{traits::Get, weights::Weight};
+use pallet_treasury::PositiveImbalanceOf;
+use pallet_xcm::WeightInfo;
+use polkadot_runtime_constants::system_parachain::{
+	collectives::FELLOWSHIP_TREASURY_PALLET_INDEX, COLLECTIVES_ID, STATEMINT_ID,
+};
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::latest::prelude::*;
+
+/// Implements [`pallet_treasury::SpendFunds`] to top up the Fellowship treasury with `Amount` DOT
+/// every spend period of the Treasury.
+///
+/// The funds of the Fellowship treasury are held on the Asset Hub, by the sovereign account of the
+/// Fellowship treasury pallet of the Collectives parachain, from which its spends are paid out. The
+/// top-up is teleported there straight from the Treasury account. If the teleport fails, the funds
+/// stay in the Treasury and the top-up is attempted again in the next spend period.
+pub struct TopUpFellowshipTreasury<Amount>(PhantomData<Amount>);
+
+impl<Amount: Get<Balance>> pallet_treasury::SpendFunds<Runtime>
+	for TopUpFellowshipTreasury<Amount>
+{
+	fn spend_funds(
+		budget_remaining: &mut Balance,
+		_imbalance: &mut PositiveImbalanceOf<Runtime>,
+		total_weight: &mut Weight,
+		missed_any: &mut bool,
+	) {
+		let amount = Amount::get().min(*budget_remaining);
+		if amount.is_zero() {
+			return
+		}
+
+		let result = XcmPallet::limited_teleport_assets(
+			RuntimeOrigin::signed(Treasury::account_id()),
+			Box::new(Parachain(STATEMINT_ID).into_location().into()),
+			Box::new(fellowship_treasury_location().into()),
+			Box::new((Here, amount).into()),
+			0,
+			WeightLimit::Unlimited,
+		);
+
+		*total_weight = total_weight
+			.saturating_add(<Runtime as pallet_xcm::Config>::WeightInfo::teleport_assets());
+
+		match result {
+			Ok(_) => budget_remaining.saturating_reduce(amount),
+			Err(err) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to top up the Fellowship treasury with {:?}: {:?}",
+					amount,
+					err,
+				);
+				*missed_any = true;
+			},
+		}
+	}
+}
+
+/// The location of the Fellowship treasury pallet as seen from the Asset Hub, whose sovereign
+/// account holds the funds of the Fellowship treasury.
+pub fn fellowship_treasury_location() -> MultiLocation {
+	MultiLocation::new(
+		1,
+		X2(Parachain(COLLECTIVES_ID), PalletInstance(FELLOWSHIP_TREASURY_PALLET_INDEX)),
+	)
+}
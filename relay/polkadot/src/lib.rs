@@ -34,7 +34,9 @@ use runtime_parachains::{
 	inclusion::{AggregateMessageOrigin, UmpQueueId},
 	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, reward_points as parachains_reward_points,
-	runtime_api_impl::v5 as parachains_runtime_api_impl,
+	runtime_api_impl::{
+		v5 as parachains_runtime_api_impl, vstaging as parachains_staging_runtime_api_impl,
+	},
 	scheduler as parachains_scheduler, session_info as parachains_session_info,
 	shared as parachains_shared,
 };
@@ -58,6 +60,7 @@ use pallet_grandpa::{fg_primitives, AuthorityId as GrandpaId};
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use pallet_session::historical as session_historical;
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
+use pallet_xcm::{EnsureXcm, IsVoiceOfBody};
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::{
 	slashing, AccountId, AccountIndex, Balance, BlockNumber, CandidateEvent, CandidateHash,
@@ -106,13 +109,23 @@ mod weights;
 
 mod bag_thresholds;
 
+// Auxiliary types used within the runtime.
+pub mod impls;
+
+mod async_backing_migration;
 mod paras_scheduler_migration;
 
+// Pallets shared with the other Relay Chain.
+pub use relay_common::{
+	code_upgrade_authorization, crowdloan_refunder, hrmp_system_channels, lease_retirement,
+	ump_queue_breaker, validator_disabling,
+};
+
 // Governance configurations.
 pub mod governance;
 use governance::{
-	pallet_custom_origins, AuctionAdmin, FellowshipAdmin, GeneralAdmin, LeaseAdmin, StakingAdmin,
-	Treasurer, TreasurySpender,
+	pallet_custom_origins, AuctionAdmin, FellowsBodyId, FellowshipAdmin, GeneralAdmin, LeaseAdmin,
+	StakingAdmin, Treasurer, TreasurySpender,
 };
 
 pub mod xcm_config;
@@ -152,13 +165,25 @@ pub fn native_version() -> NativeVersion {
 	NativeVersion { runtime_version: VERSION, can_author_with: Default::default() }
 }
 
+/// We allow all calls, except those of `Registrar` that would get around a permanent lock.
+pub struct BaseFilter;
+impl Contains<RuntimeCall> for BaseFilter {
+	fn contains(c: &RuntimeCall) -> bool {
+		match c {
+			RuntimeCall::Registrar(call) =>
+				code_upgrade_authorization::PermanentLockFilter::<Runtime>::contains(call),
+			_ => true,
+		}
+	}
+}
+
 parameter_types! {
 	pub const Version: RuntimeVersion = VERSION;
 	pub const SS58Prefix: u8 = 0;
 }
 
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = BaseFilter;
 	type BlockWeights = BlockWeights;
 	type BlockLength = BlockLength;
 	type RuntimeOrigin = RuntimeOrigin;
@@ -703,6 +728,8 @@ parameter_types! {
 	pub const MaxPeerInHeartbeats: u32 = 10_000;
 	pub const RootSpendOriginMaxAmount: Balance = Balance::MAX;
 	pub const CouncilSpendOriginMaxAmount: Balance = Balance::MAX;
+	// The amount teleported to the Fellowship treasury on the Collectives every spend period.
+	pub const FellowshipTreasuryTopUp: Balance = 25_000 * DOLLARS;
 }
 
 impl pallet_treasury::Config for Runtime {
@@ -718,7 +745,7 @@ impl pallet_treasury::Config for Runtime {
 	type SpendPeriod = SpendPeriod;
 	type Burn = Burn;
 	type BurnDestination = ();
-	type SpendFunds = Bounties;
+	type SpendFunds = (Bounties, impls::TopUpFellowshipTreasury<FellowshipTreasuryTopUp>);
 	type MaxApprovals = MaxApprovals;
 	type WeightInfo = weights::pallet_treasury::WeightInfo<Runtime>;
 	type SpendOrigin = TreasurySpender;
@@ -1171,11 +1198,18 @@ impl ProcessMessage for MessageProcessor {
 		let para = match origin {
 			AggregateMessageOrigin::Ump(UmpQueueId::Para(para)) => para,
 		};
-		xcm_builder::ProcessXcmMessage::<
+		// Reserve the weight of noting the outcome with the `UmpQueueBreaker` upfront.
+		let note_weight = UmpQueueBreaker::note_processed_weight();
+		if meter.try_consume(note_weight).is_err() {
+			return Err(ProcessMessageError::Overweight(note_weight))
+		}
+		let result = xcm_builder::ProcessXcmMessage::<
 			Junction,
 			xcm_executor::XcmExecutor<xcm_config::XcmConfig>,
 			RuntimeCall,
-		>::process_message(message, Junction::Parachain(para.into()), meter, id)
+		>::process_message(message, Junction::Parachain(para.into()), meter, id);
+		UmpQueueBreaker::note_processed(para, &result);
+		result
 	}
 }
 
@@ -1191,10 +1225,32 @@ impl pallet_message_queue::Config for Runtime {
 	type MessageProcessor =
 		pallet_message_queue::mock_helpers::NoopMessageProcessor<AggregateMessageOrigin>;
 	type QueueChangeHandler = ParaInclusion;
-	type QueuePausedQuery = ();
+	type QueuePausedQuery = UmpQueueBreaker;
 	type WeightInfo = weights::pallet_message_queue::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const UmpFailureWindow: BlockNumber = 1 * HOURS;
+	pub const UmpMaxFailures: u32 = 100;
+}
+
+impl ump_queue_breaker::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	// UMP queues are paused and resumed by any of:
+	// - Root;
+	// - the GeneralAdmin origin;
+	// - the Fellows plurality on the Collectives chain.
+	type PauseOrigin = EitherOfDiverse<
+		EitherOf<EnsureRoot<Self::AccountId>, GeneralAdmin>,
+		EnsureXcm<IsVoiceOfBody<xcm_config::CollectivesLocation, FellowsBodyId>>,
+	>;
+	// The thresholds of the circuit breaker are set by Root or the GeneralAdmin origin.
+	type AdminOrigin = EitherOf<EnsureRoot<Self::AccountId>, GeneralAdmin>;
+	type FailureWindow = UmpFailureWindow;
+	type MaxFailures = UmpMaxFailures;
+	type OverweightLimit = MessageQueueServiceWeight;
+}
+
 impl parachains_dmp::Config for Runtime {}
 
 impl parachains_hrmp::Config for Runtime {
@@ -1205,6 +1261,11 @@ impl parachains_hrmp::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_hrmp::WeightInfo<Self>;
 }
 
+impl hrmp_system_channels::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+}
+
 impl parachains_paras_inherent::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_paras_inherent::WeightInfo<Runtime>;
 }
@@ -1224,7 +1285,12 @@ impl parachains_initializer::Config for Runtime {
 impl parachains_disputes::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RewardValidators = parachains_reward_points::RewardValidatorsWithEraPoints<Runtime>;
-	type SlashingHandler = parachains_slashing::SlashValidatorsForDisputes<ParasSlashing>;
+	// Losers of disputes are disabled right away through an offence, besides being slashed.
+	type SlashingHandler = validator_disabling::DisableValidatorsForDisputes<
+		Runtime,
+		Offences,
+		parachains_slashing::SlashValidatorsForDisputes<ParasSlashing>,
+	>;
 	type WeightInfo = weights::runtime_parachains_disputes::WeightInfo<Runtime>;
 }
 
@@ -1262,6 +1328,15 @@ impl paras_registrar::Config for Runtime {
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const CodeUpgradeAuthorizationPeriod: BlockNumber = 7 * DAYS;
+}
+
+impl code_upgrade_authorization::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AuthorizationPeriod = CodeUpgradeAuthorizationPeriod;
+}
+
 parameter_types! {
 	// 12 weeks = 3 months per lease period -> 8 lease periods ~ 2 years
 	pub LeasePeriod: BlockNumber = prod_or_fast!(12 * WEEKS, 12 * WEEKS, "DOT_LEASE_PERIOD");
@@ -1307,6 +1382,21 @@ impl crowdloan::Config for Runtime {
 	type WeightInfo = weights::runtime_common_crowdloan::WeightInfo<Runtime>;
 }
 
+impl crowdloan_refunder::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
+parameter_types! {
+	pub const MaxRetiredLeasesPerBlock: u32 = 16;
+}
+
+impl lease_retirement::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RetireOrigin = EitherOf<EnsureRoot<Self::AccountId>, LeaseAdmin>;
+	type MaxLeasesPerBlock = MaxRetiredLeasesPerBlock;
+	type WeightInfo = lease_retirement::DbWeightInfo<Self>;
+}
+
 parameter_types! {
 	// The average auction is 7 days long, so this will be 70% for ending period.
 	// 5 Days = 72000 Blocks @ 6 sec per block
@@ -1461,12 +1551,17 @@ construct_runtime! {
 		Slots: slots::{Pallet, Call, Storage, Event<T>} = 71,
 		Auctions: auctions::{Pallet, Call, Storage, Event<T>} = 72,
 		Crowdloan: crowdloan::{Pallet, Call, Storage, Event<T>} = 73,
+		LeaseRetirement: lease_retirement::{Pallet, Call, Storage, Event<T>} = 74,
+		CrowdloanRefunder: crowdloan_refunder::{Pallet, Storage, Event<T>} = 75,
+		CodeUpgradeAuthorization: code_upgrade_authorization::{Pallet, Call, Storage, Event<T>} = 76,
 
 		// Pallet for sending XCM.
 		XcmPallet: pallet_xcm::{Pallet, Call, Storage, Event<T>, Origin, Config<T>} = 99,
 
 		// Generalized message queue
 		MessageQueue: pallet_message_queue::{Pallet, Call, Storage, Event<T>} = 100,
+		UmpQueueBreaker: ump_queue_breaker::{Pallet, Call, Storage, Event<T>} = 101,
+		HrmpSystemChannels: hrmp_system_channels::{Pallet, Call, Storage, Event<T>} = 102,
 	}
 }
 
@@ -1575,6 +1670,7 @@ pub mod migrations {
 		parachains_configuration::migration::v7::MigrateToV7<Runtime>,
 		crate::paras_scheduler_migration::v1::MigrateToV1<Runtime>,
 		parachains_configuration::migration::v8::MigrateToV8<Runtime>,
+		crate::async_backing_migration::EnableAsyncBacking<Runtime>,
 
 		// Gov v1 storage migrations
 		// https://github.com/paritytech/polkadot/issues/6749
@@ -1631,6 +1727,7 @@ mod benches {
 		[runtime_parachains::initializer, Initializer]
 		[runtime_parachains::paras, Paras]
 		[runtime_parachains::paras_inherent, ParaInherent]
+		[lease_retirement, LeaseRetirement]
 		// Substrate
 		[pallet_bags_list, VoterList]
 		[pallet_balances, Balances]
@@ -1757,6 +1854,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(7)]
 	impl primitives::runtime_api::ParachainHost<Block, Hash, BlockNumber> for Runtime {
 		fn validators() -> Vec<ValidatorId> {
 			parachains_runtime_api_impl::validators::<Runtime>()
@@ -1887,6 +1985,18 @@ sp_api::impl_runtime_apis! {
 				key_ownership_proof,
 			)
 		}
+
+		fn minimum_backing_votes() -> u32 {
+			parachains_staging_runtime_api_impl::minimum_backing_votes::<Runtime>()
+		}
+
+		fn para_backing_state(para_id: ParaId) -> Option<primitives::vstaging::BackingState> {
+			parachains_staging_runtime_api_impl::backing_state::<Runtime>(para_id)
+		}
+
+		fn async_backing_params() -> primitives::vstaging::AsyncBackingParams {
+			parachains_staging_runtime_api_impl::async_backing_params::<Runtime>()
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block, BeefyId> for Runtime {
@@ -2059,6 +2169,24 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl ump_queue_breaker::UmpQueueBreakerApi<Block, BlockNumber> for Runtime {
+		fn paused_queues() -> Vec<(ParaId, ump_queue_breaker::PauseInfo<BlockNumber>)> {
+			UmpQueueBreaker::paused_queues()
+		}
+	}
+
+	impl lease_retirement::LeaseRetirementApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn outstanding_leases() -> Vec<lease_retirement::OutstandingLease<AccountId, Balance, BlockNumber>> {
+			lease_retirement::outstanding_leases::<Runtime>()
+		}
+	}
+
+	impl validator_disabling::DisabledValidatorsApi<Block> for Runtime {
+		fn disabled_validators() -> Vec<ValidatorIndex> {
+			validator_disabling::disabled_validators::<Runtime>()
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -2455,6 +2583,298 @@ mod test {
 	}
 }
 
+#[cfg(test)]
+mod fellowship_treasury_tests {
+	use super::*;
+	use frame_support::{assert_ok, storage_alias};
+	use pallet_treasury::SpendFunds;
+	use parachains_configuration::HostConfiguration;
+	use parachains_paras::ParaLifecycle;
+	use polkadot_runtime_constants::system_parachain::{
+		collectives::FELLOWSHIP_TREASURY_PALLET_INDEX, COLLECTIVES_ID, STATEMINT_ID,
+	};
+	use sp_runtime::BuildStorage;
+	use xcm::{latest::prelude::*, VersionedXcm};
+
+	#[storage_alias]
+	type ActiveConfig = StorageValue<Configuration, HostConfiguration<BlockNumber>, ValueQuery>;
+	#[storage_alias]
+	type ParaLifecycles = StorageMap<Paras, Twox64Concat, ParaId, ParaLifecycle>;
+
+	type TopUp = impls::TopUpFellowshipTreasury<FellowshipTreasuryTopUp>;
+
+	fn top_up(budget: Balance) -> (Balance, bool) {
+		let mut budget_remaining = budget;
+		let mut imbalance = Default::default();
+		let mut total_weight = Weight::zero();
+		let mut missed_any = false;
+		TopUp::spend_funds(
+			&mut budget_remaining,
+			&mut imbalance,
+			&mut total_weight,
+			&mut missed_any,
+		);
+		(budget_remaining, missed_any)
+	}
+
+	#[test]
+	fn top_up_teleports_to_the_fellowship_treasury_on_asset_hub() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			System::set_block_number(1);
+			ActiveConfig::put(HostConfiguration {
+				max_downward_message_size: 50 * 1024,
+				..Default::default()
+			});
+			ParaLifecycles::insert(ParaId::from(STATEMINT_ID), ParaLifecycle::Parachain);
+			assert_ok!(XcmPallet::force_default_xcm_version(
+				RuntimeOrigin::root(),
+				Some(XCM_VERSION)
+			));
+
+			let amount = FellowshipTreasuryTopUp::get();
+			let treasury = Treasury::account_id();
+			Balances::make_free_balance_be(&treasury, 3 * amount);
+
+			// Only the top-up is taken from the budget.
+			assert_eq!(top_up(2 * amount), (amount, false));
+			// The Treasury pays the top-up and the delivery fees.
+			assert!(Balances::free_balance(&treasury) <= 2 * amount);
+			assert!(Balances::free_balance(&treasury) > 2 * amount - UNITS);
+
+			let messages = Dmp::dmq_contents(STATEMINT_ID.into());
+			assert_eq!(messages.len(), 1);
+			let message: Xcm<()> = VersionedXcm::<()>::decode(&mut &messages[0].msg[..])
+				.unwrap()
+				.try_into()
+				.unwrap();
+			let teleported: MultiAssets = (Parent, amount).into();
+			let beneficiary = MultiLocation::new(
+				1,
+				X2(Parachain(COLLECTIVES_ID), PalletInstance(FELLOWSHIP_TREASURY_PALLET_INDEX)),
+			);
+			assert!(matches!(
+				message.0.first(),
+				Some(ReceiveTeleportedAsset(assets)) if *assets == teleported
+			));
+			assert!(message.0.iter().any(|instruction| matches!(
+				instruction,
+				DepositAsset { beneficiary: to, .. } if *to == beneficiary
+			)));
+		});
+	}
+
+	#[test]
+	fn failed_top_up_keeps_the_budget_and_is_retried() {
+		let t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+		sp_io::TestExternalities::new(t).execute_with(|| {
+			// Nothing is spent without a budget.
+			assert_eq!(top_up(0), (0, false));
+
+			// The Treasury account holds no funds, so the teleport fails.
+			let budget = 2 * FellowshipTreasuryTopUp::get();
+			assert_eq!(top_up(budget), (budget, true));
+		});
+	}
+}
+
+#[cfg(test)]
+mod ump_queue_breaker_tests {
+	use super::*;
+	use frame_support::{assert_noop, assert_ok, traits::QueuePausedQuery};
+	use sp_runtime::DispatchError;
+	use ump_queue_breaker::{PauseReason, Thresholds};
+
+	const PARA: u32 = 2000;
+
+	fn is_paused() -> bool {
+		UmpQueueBreaker::is_paused(&AggregateMessageOrigin::Ump(UmpQueueId::Para(PARA.into())))
+	}
+
+	#[test]
+	fn ump_queue_breaker_pauses_failing_para() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			let para = ParaId::from(PARA);
+			for _ in 0..UmpMaxFailures::get() {
+				UmpQueueBreaker::note_processed(para, &Err(ProcessMessageError::Unsupported));
+			}
+			// Messages which were processed, even if their execution failed, and those exceeding the
+			// weight left in the block do not count.
+			UmpQueueBreaker::note_processed(para, &Ok(true));
+			UmpQueueBreaker::note_processed(para, &Ok(false));
+			UmpQueueBreaker::note_processed(
+				para,
+				&Err(ProcessMessageError::Overweight(Weight::from_parts(1, 1))),
+			);
+			assert!(!is_paused());
+
+			UmpQueueBreaker::note_processed(para, &Err(ProcessMessageError::Corrupt));
+			assert!(is_paused());
+			assert_eq!(UmpQueueBreaker::paused_queues()[0].1.reason, PauseReason::CircuitBreaker);
+
+			assert_ok!(UmpQueueBreaker::resume(RuntimeOrigin::root(), para));
+			assert!(!is_paused());
+			assert_ok!(UmpQueueBreaker::pause(RuntimeOrigin::root(), para));
+			assert_eq!(UmpQueueBreaker::paused_queues()[0].1.reason, PauseReason::Governance);
+		});
+	}
+
+	#[test]
+	fn ump_queue_breaker_thresholds_are_set_by_governance() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			let para = ParaId::from(PARA);
+			let thresholds = Thresholds { failure_window: 10, max_failures: 1 };
+			assert_eq!(
+				UmpQueueBreaker::thresholds(),
+				Thresholds {
+					failure_window: UmpFailureWindow::get(),
+					max_failures: UmpMaxFailures::get(),
+				}
+			);
+
+			assert_noop!(
+				UmpQueueBreaker::set_thresholds(RuntimeOrigin::none(), Some(thresholds)),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(UmpQueueBreaker::set_thresholds(RuntimeOrigin::root(), Some(thresholds)));
+			assert_eq!(UmpQueueBreaker::thresholds(), thresholds);
+
+			// Failures are only counted within the failure window.
+			System::set_block_number(1);
+			UmpQueueBreaker::note_processed(para, &Err(ProcessMessageError::Corrupt));
+			System::set_block_number(11);
+			UmpQueueBreaker::note_processed(para, &Err(ProcessMessageError::Corrupt));
+			assert!(!is_paused());
+			UmpQueueBreaker::note_processed(para, &Err(ProcessMessageError::Corrupt));
+			assert!(is_paused());
+
+			// The queues of system parachains are never paused automatically.
+			let system_para = ParaId::from(1000);
+			for _ in 0..=thresholds.max_failures {
+				UmpQueueBreaker::note_processed(system_para, &Err(ProcessMessageError::Corrupt));
+			}
+			assert!(!UmpQueueBreaker::is_paused(&AggregateMessageOrigin::Ump(UmpQueueId::Para(
+				system_para
+			)));
+
+			assert_ok!(UmpQueueBreaker::set_thresholds(RuntimeOrigin::root(), None));
+			assert_eq!(UmpQueueBreaker::thresholds().max_failures, UmpMaxFailures::get());
+		});
+	}
+}
+
+#[cfg(test)]
+mod code_upgrade_authorization_tests {
+	use super::*;
+	use frame_support::{assert_noop, traits::Contains};
+	use primitives::{HeadData, ValidationCode};
+	use sp_runtime::traits::Dispatchable;
+
+	#[test]
+	fn registrar_calls_of_permanently_locked_paras_are_filtered() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			let (para, new_code, new_head) =
+				(ParaId::from(2000), ValidationCode(vec![1]), HeadData(vec![1]));
+			let calls = [
+				paras_registrar::Call::remove_lock { para },
+				paras_registrar::Call::schedule_code_upgrade { para, new_code },
+				paras_registrar::Call::set_current_head { para, new_head },
+			]
+			.map(RuntimeCall::Registrar);
+			assert!(calls.iter().all(BaseFilter::contains));
+
+			code_upgrade_authorization::PermanentlyLocked::<Runtime>::insert(para, ());
+			for call in calls {
+				assert!(!BaseFilter::contains(&call));
+				assert_noop!(
+					call.dispatch(parachains_origin::Origin::Parachain(para).into()),
+					frame_system::Error::<Runtime>::CallFiltered,
+				);
+			}
+		});
+	}
+}
+
+#[cfg(test)]
+mod hrmp_system_channels_tests {
+	use super::*;
+	use frame_support::{assert_noop, assert_ok, storage_alias};
+	use parachains_configuration::HostConfiguration;
+	use parachains_hrmp::HrmpOpenChannelRequest;
+	use parachains_paras::ParaLifecycle;
+	use primitives::HrmpChannelId;
+	use sp_runtime::{traits::AccountIdConversion, DispatchError};
+
+	#[storage_alias]
+	type ActiveConfig = StorageValue<Configuration, HostConfiguration<BlockNumber>, ValueQuery>;
+	#[storage_alias]
+	type ParaLifecycles = StorageMap<Paras, Twox64Concat, ParaId, ParaLifecycle>;
+	#[storage_alias]
+	type HrmpOpenChannelRequests =
+		StorageMap<Hrmp, Twox64Concat, HrmpChannelId, HrmpOpenChannelRequest>;
+
+	const ASSET_HUB: u32 = 1000;
+	const COLLECTIVES: u32 = 1001;
+	const PUBLIC: u32 = 2000;
+	const DEPOSIT: Balance = 10 * UNITS;
+
+	fn account(para: u32) -> AccountId {
+		ParaId::from(para).into_account_truncating()
+	}
+
+	#[test]
+	fn system_channels_are_paid_by_both_sides() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			System::set_block_number(1);
+			ActiveConfig::put(HostConfiguration {
+				hrmp_channel_max_capacity: 8,
+				hrmp_channel_max_total_size: 8 * 1024,
+				hrmp_channel_max_message_size: 1024,
+				hrmp_max_parachain_outbound_channels: 10,
+				hrmp_max_parachain_inbound_channels: 10,
+				hrmp_sender_deposit: DEPOSIT,
+				hrmp_recipient_deposit: DEPOSIT,
+				..Default::default()
+			});
+			for para in [ASSET_HUB, COLLECTIVES, PUBLIC] {
+				ParaLifecycles::insert(ParaId::from(para), ParaLifecycle::Parachain);
+				Balances::make_free_balance_be(&account(para), 100 * UNITS);
+			}
+			let issuance = Balances::total_issuance();
+
+			assert_ok!(HrmpSystemChannels::establish_system_channel(
+				RuntimeOrigin::signed(AccountId::from([1; 32])),
+				ASSET_HUB.into(),
+				COLLECTIVES.into(),
+			));
+			// Only the public para itself can open a channel with a system para.
+			assert_noop!(
+				HrmpSystemChannels::establish_system_channel(
+					RuntimeOrigin::signed(AccountId::from([1; 32])),
+					COLLECTIVES.into(),
+					PUBLIC.into(),
+				),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(HrmpSystemChannels::establish_system_channel(
+				parachains_origin::Origin::Parachain(PUBLIC.into()).into(),
+				COLLECTIVES.into(),
+				PUBLIC.into(),
+			));
+
+			for (sender, recipient) in
+				[(ASSET_HUB, COLLECTIVES), (COLLECTIVES, ASSET_HUB), (COLLECTIVES, PUBLIC)]
+			{
+				let id = HrmpChannelId { sender: sender.into(), recipient: recipient.into() };
+				assert!(HrmpOpenChannelRequests::get(id).unwrap().confirmed);
+			}
+			assert_eq!(Balances::reserved_balance(&account(ASSET_HUB)), 2 * DEPOSIT);
+			assert_eq!(Balances::reserved_balance(&account(COLLECTIVES)), 3 * DEPOSIT);
+			assert_eq!(Balances::reserved_balance(&account(PUBLIC)), DEPOSIT);
+			assert_eq!(Balances::total_issuance(), issuance);
+		});
+	}
+}
+
 #[cfg(test)]
 mod multiplier_tests {
 	use super::*;
@@ -2680,6 +3100,36 @@ mod remote_tests {
 		ext.execute_with(|| Runtime::on_runtime_upgrade(UpgradeCheckSelect::PreAndPost));
 	}
 
+	#[tokio::test]
+	async fn refund_all_expired_crowdloans() {
+		if var("RUN_CROWDLOAN_REFUND_TEST").is_err() {
+			return
+		}
+
+		sp_tracing::try_init_simple();
+		let mut ext = remote_ext_test_setup().await;
+		ext.execute_with(|| {
+			use crowdloan_refunder::Funds;
+			use frame_support::traits::Hooks;
+
+			let now = System::block_number();
+			let expired: Vec<_> = Funds::<Runtime>::iter()
+				.filter(|(_, fund)| CrowdloanRefunder::is_expired(now, fund))
+				.map(|(para, _)| para)
+				.collect();
+			log::info!(target: LOG_TARGET, "{} expired crowdloans", expired.len());
+
+			let limit = <Runtime as frame_system::Config>::BlockWeights::get().max_block;
+			let mut blocks = 0;
+			while expired.iter().any(|para| Funds::<Runtime>::contains_key(para)) {
+				CrowdloanRefunder::on_idle(now, limit);
+				blocks += 1;
+				assert!(blocks < 10_000, "expired crowdloans are not refunded");
+			}
+			log::info!(target: LOG_TARGET, "refunded all expired crowdloans in {} blocks", blocks);
+		});
+	}
+
 	#[tokio::test]
 	#[ignore = "this test is meant to be executed manually"]
 	async fn try_fast_unstake_all() {
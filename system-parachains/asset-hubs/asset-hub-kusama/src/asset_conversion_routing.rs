@@ -0,0 +1,142 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Routing of swaps across the pools of `pallet_asset_conversion`.
+//!
+//! The quotes of `pallet_asset_conversion::AssetConversionApi` only cover the pool of two assets.
+//! With pools of any two assets, a swap may be cheaper through other assets, so
+//! [`AssetConversionRoutingApi`] quotes the best swap path between two assets: the paths of
+//! distinct assets with at most `MaxSwapPathLength` assets along existing pools are quoted, LP fees
+//! included, and the one with the most assets out, or the fewest assets in, is returned. The path
+//! can be passed to the swap calls as is.
+//!
+//! The number of paths grows exponentially with the number of pools, so the search is bounded by
+//! [`MAX_PATHS`] and [`MAX_EXTENDED_PATHS`]. It goes through the shortest paths first, so the pool
+//! of the two assets is always quoted.
+
+use frame_support::traits::Get;
+use pallet_asset_conversion::{Config, Pallet, Pools};
+use sp_std::{collections::vec_deque::VecDeque, prelude::*};
+
+/// The most paths quoted.
+pub const MAX_PATHS: usize = 64;
+
+/// The most paths extended by another asset while searching the paths to quote.
+pub const MAX_EXTENDED_PATHS: usize = 256;
+
+/// The best swap path from `asset1` to `asset2` for `amount` of `asset1`, and the amount of
+/// `asset2` out.
+pub fn best_path_exact_tokens_for_tokens<T: Config>(
+	asset1: T::MultiAssetId,
+	asset2: T::MultiAssetId,
+	amount: T::AssetBalance,
+) -> Option<(Vec<T::MultiAssetId>, T::AssetBalance)> {
+	paths::<T>(asset1, asset2)
+		.into_iter()
+		.filter_map(|path| {
+			let amount_out = path.windows(2).try_fold(amount, |amount_in, pair| {
+				let (reserve_in, reserve_out) =
+					Pallet::<T>::get_reserves(&pair[0], &pair[1]).ok()?;
+				Pallet::<T>::get_amount_out(&amount_in, &reserve_in, &reserve_out).ok()
+			})?;
+			Some((path, amount_out))
+		})
+		.max_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+/// The best swap path from `asset1` to `asset2` for `amount` of `asset2`, and the amount of
+/// `asset1` in.
+pub fn best_path_tokens_for_exact_tokens<T: Config>(
+	asset1: T::MultiAssetId,
+	asset2: T::MultiAssetId,
+	amount: T::AssetBalance,
+) -> Option<(Vec<T::MultiAssetId>, T::AssetBalance)> {
+	paths::<T>(asset1, asset2)
+		.into_iter()
+		.filter_map(|path| {
+			let amount_in = path.windows(2).rev().try_fold(amount, |amount_out, pair| {
+				let (reserve_in, reserve_out) =
+					Pallet::<T>::get_reserves(&pair[0], &pair[1]).ok()?;
+				Pallet::<T>::get_amount_in(&amount_out, &reserve_in, &reserve_out).ok()
+			})?;
+			Some((path, amount_in))
+		})
+		.min_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+/// The paths of distinct assets from `asset1` to `asset2` along existing pools, with at most
+/// `MaxSwapPathLength` assets, shortest first. At most [`MAX_PATHS`] paths are returned, and at
+/// most [`MAX_EXTENDED_PATHS`] paths are extended to find them.
+fn paths<T: Config>(asset1: T::MultiAssetId, asset2: T::MultiAssetId) -> Vec<Vec<T::MultiAssetId>> {
+	let pools: Vec<(T::MultiAssetId, T::MultiAssetId)> = Pools::<T>::iter_keys().collect();
+	let max_length = T::MaxSwapPathLength::get() as usize;
+	let mut paths = Vec::new();
+	let mut extended_paths = 0;
+	let mut queue = VecDeque::from(vec![vec![asset1]]);
+	while let Some(path) = queue.pop_front() {
+		let last = path.last().expect("paths are never empty; qed").clone();
+		if last == asset2 {
+			paths.push(path);
+			if paths.len() == MAX_PATHS {
+				break
+			}
+			continue
+		}
+		if path.len() == max_length || extended_paths == MAX_EXTENDED_PATHS {
+			continue
+		}
+		extended_paths += 1;
+		for (a, b) in pools.iter() {
+			let next = if *a == last {
+				b
+			} else if *b == last {
+				a
+			} else {
+				continue
+			};
+			if !path.contains(next) {
+				let mut extended = path.clone();
+				extended.push(next.clone());
+				queue.push_back(extended);
+			}
+		}
+	}
+	paths
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to quote the best swap path between two assets.
+	pub trait AssetConversionRoutingApi<Balance, MultiAssetId>
+	where
+		Balance: codec::Codec,
+		MultiAssetId: codec::Codec,
+	{
+		/// The best swap path from `asset1` to `asset2` for `amount` of `asset1`, and the amount
+		/// of `asset2` out.
+		fn quote_best_path_exact_tokens_for_tokens(
+			asset1: MultiAssetId,
+			asset2: MultiAssetId,
+			amount: Balance,
+		) -> Option<(Vec<MultiAssetId>, Balance)>;
+
+		/// The best swap path from `asset1` to `asset2` for `amount` of `asset2`, and the amount
+		/// of `asset1` in.
+		fn quote_best_path_tokens_for_exact_tokens(
+			asset1: MultiAssetId,
+			asset2: MultiAssetId,
+			amount: Balance,
+		) -> Option<(Vec<MultiAssetId>, Balance)>;
+	}
+}
@@ -24,9 +24,15 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+pub mod asset_conversion_routing;
 mod weights;
 pub mod xcm_config;
 
+pub use asset_hub_common::{
+	asset_balances_api, asset_locks, asset_vesting, foreign_asset_registry, nft_locks,
+	nft_transactor,
+};
+
 use assets_common::{
 	foreign_creators::ForeignCreators,
 	local_and_foreign_assets::{LocalAndForeignAssets, MultiLocationConverter},
@@ -38,7 +44,9 @@ use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, Verify},
+	traits::{
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, Permill,
 };
@@ -54,8 +62,8 @@ use frame_support::{
 	dispatch::DispatchClass,
 	ord_parameter_types, parameter_types,
 	traits::{
-		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, EitherOfDiverse,
-		InstanceFilter,
+		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, Contains,
+		EitherOfDiverse, EverythingBut, InstanceFilter, Locker,
 	},
 	weights::{ConstantMultiplier, Weight},
 	BoundedVec, PalletId,
@@ -69,12 +77,12 @@ use pallet_nfts::PalletFeatures;
 pub use parachains_common as common;
 use parachains_common::{
 	impls::DealWithFees,
-	kusama::{consensus::*, currency::*, fee::WeightToFee},
+	kusama::{currency::*, fee::WeightToFee},
 	AccountId, AssetIdForTrustBackedAssets, AuraId, Balance, BlockNumber, Hash, Header, Nonce,
-	Signature, AVERAGE_ON_INITIALIZE_RATIO, DAYS, HOURS, MAXIMUM_BLOCK_WEIGHT,
-	NORMAL_DISPATCH_RATIO, SLOT_DURATION,
+	Signature, AVERAGE_ON_INITIALIZE_RATIO, DAYS, HOURS, NORMAL_DISPATCH_RATIO, SLOT_DURATION,
 };
 use sp_runtime::RuntimeDebug;
+use system_parachains_constants::{consensus::*, MAXIMUM_BLOCK_WEIGHT};
 use xcm::opaque::v3::MultiLocation;
 use xcm_config::{
 	FellowshipLocation, ForeignAssetsConvertedConcreteId, GovernanceLocation, KsmLocation,
@@ -165,9 +173,30 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 2;
 }
 
+/// Burns of locked items of `Uniques` and `Nfts`.
+///
+/// Both pallets consult their `Locker` on transfers, but not on burns, so burns are filtered here.
+/// `Uniques` burns the items of the collections it destroys, so collections with locked items
+/// cannot be destroyed either; `Nfts` only destroys empty collections. Other pallets and the XCM
+/// transactors only get the NFT pallets through traits which cannot burn.
+pub struct BurnsOfLockedNfts;
+impl Contains<RuntimeCall> for BurnsOfLockedNfts {
+	fn contains(call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::Uniques(pallet_uniques::Call::burn { collection, item, .. }) =>
+				UniquesLocks::is_locked(*collection, *item),
+			RuntimeCall::Uniques(pallet_uniques::Call::destroy { collection, .. }) =>
+				UniquesLocks::has_locked_items(*collection),
+			RuntimeCall::Nfts(pallet_nfts::Call::burn { collection, item }) =>
+				NftsLocks::is_locked(*collection, *item),
+			_ => false,
+		}
+	}
+}
+
 // Configure FRAME pallets to include in runtime.
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = EverythingBut<BurnsOfLockedNfts>;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type AccountId = AccountId;
@@ -276,7 +305,7 @@ impl pallet_assets::Config<TrustBackedAssetsInstance> for Runtime {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = AssetsStringLimit;
-	type Freezer = ();
+	type Freezer = AssetLocks;
 	type Extra = ();
 	type WeightInfo = weights::pallet_assets_local::WeightInfo<Runtime>;
 	type CallbackHandle = ();
@@ -288,9 +317,14 @@ impl pallet_assets::Config<TrustBackedAssetsInstance> for Runtime {
 
 parameter_types! {
 	pub const AssetConversionPalletId: PalletId = PalletId(*b"py/ascon");
-	pub const AllowMultiAssetPools: bool = false;
-	// should be non-zero if AllowMultiAssetPools is true, otherwise can be zero
-	pub const LiquidityWithdrawalFee: Permill = Permill::from_percent(0);
+	pub const AllowMultiAssetPools: bool = true;
+	// Non-zero as `AllowMultiAssetPools` is true.
+	pub const LiquidityWithdrawalFee: Permill = Permill::from_perthousand(1);
+	// Any two assets may be paired, so every pool costs as much as the pool asset it creates.
+	pub const PoolSetupFee: Balance = AssetDeposit::get();
+	// A swap between any two assets paired with KSM takes 3 assets, so `MaxSwapPathLength` must not
+	// be below 3.
+	pub const MaxSwapPathLength: u32 = 4;
 }
 
 ord_parameter_types! {
@@ -316,14 +350,38 @@ impl pallet_assets::Config<PoolAssetsInstance> for Runtime {
 	type MetadataDepositPerByte = ConstU128<0>;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = ConstU32<50>;
-	type Freezer = ();
+	type Freezer = PoolAssetLocks;
 	type Extra = ();
 	type WeightInfo = weights::pallet_assets_pool::WeightInfo<Runtime>;
-	type CallbackHandle = ();
+	type CallbackHandle = PoolAssetsMetadata;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
 
+/// Gives every pool asset, the LP token of an `AssetConversion` pool, its metadata on creation.
+///
+/// LP tokens are named `LP Token <id>` with the symbol `LP<id>`. They have no decimals: their
+/// amounts are the geometric mean of the amounts of the two assets of their pool, which may have
+/// different decimals, and the pool is not known yet when its LP token is created.
+pub struct PoolAssetsMetadata;
+impl pallet_assets::AssetsCallback<u32, AccountId> for PoolAssetsMetadata {
+	fn created(id: &u32, _owner: &AccountId) -> Result<(), ()> {
+		let mut name = b"LP Token ".to_vec();
+		let mut symbol = b"LP".to_vec();
+		push_decimal(&mut name, *id);
+		push_decimal(&mut symbol, *id);
+		PoolAssets::force_set_metadata(RuntimeOrigin::root(), *id, name, symbol, 0, false)
+			.map_err(|_| ())
+	}
+}
+
+fn push_decimal(buffer: &mut Vec<u8>, n: u32) {
+	if n >= 10 {
+		push_decimal(buffer, n / 10);
+	}
+	buffer.push(b'0' + (n % 10) as u8);
+}
+
 impl pallet_asset_conversion::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Balance = Balance;
@@ -338,14 +396,13 @@ impl pallet_asset_conversion::Config for Runtime {
 	>;
 	type PoolAssets = PoolAssets;
 	type PoolAssetId = u32;
-	type PoolSetupFee = ConstU128<0>; // Asset class deposit fees are sufficient to prevent spam
+	type PoolSetupFee = PoolSetupFee;
 	type PoolSetupFeeReceiver = AssetConversionOrigin;
-	// should be non-zero if `AllowMultiAssetPools` is true, otherwise can be zero.
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type AllowMultiAssetPools = AllowMultiAssetPools;
-	type MaxSwapPathLength = ConstU32<4>;
+	type MaxSwapPathLength = MaxSwapPathLength;
 	type MultiAssetId = Box<MultiLocation>;
 	type MultiAssetIdConverter =
 		MultiLocationConverter<KsmLocation, LocalAndForeignAssetsMultiLocationMatcher>;
@@ -370,7 +427,7 @@ parameter_types! {
 /// this type is used in proxy definitions. We assume that a foreign location would not want to set
 /// an individual, local account as a proxy for the issuance of their assets. This issuance should
 /// be managed by the foreign location's governance.
-pub type ForeignAssetsInstance = pallet_assets::Instance2;
+pub use asset_hub_common::ForeignAssetsInstance;
 impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Balance = Balance;
@@ -388,7 +445,7 @@ impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
 	type MetadataDepositPerByte = ForeignAssetsMetadataDepositPerByte;
 	type ApprovalDeposit = ForeignAssetsApprovalDeposit;
 	type StringLimit = ForeignAssetsAssetsStringLimit;
-	type Freezer = ();
+	type Freezer = ForeignAssetLocks;
 	type Extra = ();
 	type WeightInfo = weights::pallet_assets_foreign::WeightInfo<Runtime>;
 	type CallbackHandle = ();
@@ -398,6 +455,83 @@ impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
 	type BenchmarkHelper = xcm_config::XcmBenchmarkHelper;
 }
 
+parameter_types! {
+	// GeneralAdmin pluralistic body.
+	pub const GeneralAdminBodyId: BodyId = BodyId::Administration;
+	pub const ForeignAssetRegistryPalletId: PalletId = PalletId(*b"py/frgnr");
+	pub const ForeignAssetRegistrationDeposit: Balance = 10 * ForeignAssetsAssetDeposit::get();
+}
+
+/// We allow root and the `GeneralAdmin` to register foreign assets without a deposit, to register
+/// teleported and sufficient ones and to end any registration.
+pub type ForeignAssetRegisterOrigin = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	EnsureXcm<IsVoiceOfBody<GovernanceLocation, GeneralAdminBodyId>>,
+>;
+
+impl foreign_asset_registry::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RegisterOrigin = ForeignAssetRegisterOrigin;
+	type RegistrationDeposit = ForeignAssetRegistrationDeposit;
+	// Slashed registration deposits are burnt.
+	type Slashed = ();
+	type MaxDecimals = ConstU8<18>;
+	// Signed registrations keep the minimum balance at or below a ten-thousandth of a unit.
+	type MinBalanceMagnitude = ConstU8<4>;
+	type UniversalLocation = xcm_config::UniversalLocation;
+	type PalletId = ForeignAssetRegistryPalletId;
+	type WeightInfo = foreign_asset_registry::DbWeightInfo<Runtime>;
+}
+
+parameter_types! {
+	// We allow each account to have up to 4 freezes and 4 holds on each of its assets.
+	pub const AssetLocksMaxFreezes: u32 = 4;
+	pub const AssetLocksMaxHolds: u32 = 4;
+}
+
+impl asset_locks::Config<TrustBackedAssetsInstance> for Runtime {
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type HoldReason = asset_locks::AssetHoldReason;
+	type MaxFreezes = AssetLocksMaxFreezes;
+	type MaxHolds = AssetLocksMaxHolds;
+}
+
+impl asset_locks::Config<ForeignAssetsInstance> for Runtime {
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type HoldReason = asset_locks::AssetHoldReason;
+	type MaxFreezes = AssetLocksMaxFreezes;
+	type MaxHolds = AssetLocksMaxHolds;
+}
+
+impl asset_locks::Config<PoolAssetsInstance> for Runtime {
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type HoldReason = asset_locks::AssetHoldReason;
+	type MaxFreezes = AssetLocksMaxFreezes;
+	type MaxHolds = AssetLocksMaxHolds;
+}
+
+parameter_types! {
+	pub const MaxAssetVestingSchedules: u32 = 28;
+	// A vested transfer moves at least a hundred times the minimum balance of the asset.
+	pub const MinVestedAssetTransferMultiple: u32 = 100;
+}
+
+impl asset_vesting::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = AssetIdForTrustBackedAssets;
+	type Balance = Balance;
+	type Assets = Assets;
+	type Freezer = AssetLocks;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type BlockNumberToBalance = ConvertInto;
+	type ForceOrigin = AssetsForceOrigin;
+	type MaxVestingSchedules = MaxAssetVestingSchedules;
+	type MinVestedTransferMultiple = MinVestedAssetTransferMultiple;
+	type WeightInfo = asset_vesting::DbWeightInfo<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
 parameter_types! {
 	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
 	pub const DepositBase: Balance = deposit(1, 88);
@@ -479,6 +613,7 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 				c,
 				RuntimeCall::Balances { .. } |
 					RuntimeCall::Assets { .. } |
+					RuntimeCall::AssetVesting { .. } |
 					RuntimeCall::NftFractionalization { .. } |
 					RuntimeCall::Nfts { .. } |
 					RuntimeCall::Uniques { .. }
@@ -493,6 +628,7 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 				matches!(
 					c,
 					RuntimeCall::Assets { .. } |
+						RuntimeCall::AssetVesting { .. } |
 						RuntimeCall::Utility { .. } |
 						RuntimeCall::Multisig { .. } |
 						RuntimeCall::NftFractionalization { .. } |
@@ -746,7 +882,7 @@ impl pallet_uniques::Config for Runtime {
 	#[cfg(feature = "runtime-benchmarks")]
 	type Helper = ();
 	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
-	type Locker = ();
+	type Locker = UniquesLocks;
 }
 
 parameter_types! {
@@ -767,7 +903,11 @@ impl pallet_nft_fractionalization::Config for Runtime {
 	type AssetBalance = <Self as pallet_balances::Config>::Balance;
 	type AssetId = <Self as pallet_assets::Config<TrustBackedAssetsInstance>>::AssetId;
 	type Assets = Assets;
-	type Nfts = Nfts;
+	// Fractionalized items are locked, so they cannot be burnt.
+	type Nfts = nft_locks::LockOnDisabledTransfer<
+		Nfts,
+		nft_locks::LockFor<Runtime, NftsLocksInstance, FractionalizedNftLockReason>,
+	>;
 	type PalletId = NftFractionalizationPalletId;
 	type WeightInfo = pallet_nft_fractionalization::weights::SubstrateWeight<Runtime>;
 	type RuntimeHoldReason = RuntimeHoldReason;
@@ -793,7 +933,7 @@ impl pallet_nfts::Config for Runtime {
 	type Currency = Balances;
 	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
 	type ForceOrigin = AssetsForceOrigin;
-	type Locker = ();
+	type Locker = NftsLocks;
 	type CollectionDeposit = NftsCollectionDeposit;
 	type ItemDeposit = NftsItemDeposit;
 	type MetadataDepositBase = NftsMetadataDepositBase;
@@ -815,6 +955,32 @@ impl pallet_nfts::Config for Runtime {
 	type Helper = ();
 }
 
+parameter_types! {
+	pub const NftLocksMaxLocks: u32 = 4;
+	pub const FractionalizedNftLockReason: nft_locks::NftLockReason =
+		nft_locks::NftLockReason::Fractionalized;
+	pub const XcmReserveNftLockReason: nft_locks::NftLockReason =
+		nft_locks::NftLockReason::XcmReserve;
+}
+
+pub type UniquesLocksInstance = frame_support::instances::Instance1;
+impl nft_locks::Config<UniquesLocksInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type CollectionId = <Self as pallet_uniques::Config>::CollectionId;
+	type ItemId = <Self as pallet_uniques::Config>::ItemId;
+	type LockReason = nft_locks::NftLockReason;
+	type MaxLocks = NftLocksMaxLocks;
+}
+
+pub type NftsLocksInstance = frame_support::instances::Instance2;
+impl nft_locks::Config<NftsLocksInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type CollectionId = <Self as pallet_nfts::Config>::CollectionId;
+	type ItemId = <Self as pallet_nfts::Config>::ItemId;
+	type LockReason = nft_locks::NftLockReason;
+	type MaxLocks = NftLocksMaxLocks;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime
@@ -860,6 +1026,13 @@ construct_runtime!(
 
 		PoolAssets: pallet_assets::<Instance3>::{Pallet, Call, Storage, Event<T>} = 55,
 		AssetConversion: pallet_asset_conversion::{Pallet, Call, Storage, Event<T>} = 56,
+		ForeignAssetRegistry: foreign_asset_registry::{Pallet, Call, Storage, Event<T>} = 57,
+		AssetLocks: asset_locks::<Instance1>::{Pallet, Storage} = 58,
+		ForeignAssetLocks: asset_locks::<Instance2>::{Pallet, Storage} = 59,
+		PoolAssetLocks: asset_locks::<Instance3>::{Pallet, Storage} = 60,
+		AssetVesting: asset_vesting::{Pallet, Call, Storage, Event<T>, FreezeReason} = 61,
+		UniquesLocks: nft_locks::<Instance1>::{Pallet, Storage, Event<T>} = 62,
+		NftsLocks: nft_locks::<Instance2>::{Pallet, Storage, Event<T>} = 63,
 
 		#[cfg(feature = "state-trie-version-1")]
 		StateTrieMigration: pallet_state_trie_migration = 70,
@@ -893,6 +1066,7 @@ pub type Migrations = (
 	pallet_collator_selection::migration::v1::MigrateToV1<Runtime>,
 	pallet_multisig::migrations::v1::MigrateToV1<Runtime>,
 	InitStorageVersions,
+	RegisterNisCounterpart,
 );
 
 /// Migration to initialize storage versions for pallets added after genesis.
@@ -934,6 +1108,52 @@ impl frame_support::traits::OnRuntimeUpgrade for InitStorageVersions {
 	}
 }
 
+/// Registers the NIS counterpart token (KTC) of the Relay Chain as a foreign asset owned by the
+/// Relay Chain, such that it can be teleported from the Relay Chain and traded in `AssetConversion`
+/// pools. Does nothing if the asset exists already.
+pub struct RegisterNisCounterpart;
+
+impl frame_support::traits::OnRuntimeUpgrade for RegisterNisCounterpart {
+	fn on_runtime_upgrade() -> Weight {
+		use xcm_config::{LocationToAccountId, NisCounterpartLocation};
+		use xcm_executor::traits::ConvertLocation;
+
+		/// One KTC cent, the existential deposit of KTC on the Relay Chain.
+		const MIN_BALANCE: Balance = 10_000_000_000;
+
+		let id = NisCounterpartLocation::get();
+		if pallet_assets::Asset::<Runtime, ForeignAssetsInstance>::contains_key(id) {
+			return <Runtime as frame_system::Config>::DbWeight::get().reads(1)
+		}
+
+		let Some(owner) = LocationToAccountId::convert_location(&MultiLocation::parent()) else {
+			return <Runtime as frame_system::Config>::DbWeight::get().reads(1)
+		};
+		let created = ForeignAssets::force_create(
+			RuntimeOrigin::root(),
+			id,
+			owner.into(),
+			false,
+			MIN_BALANCE,
+		)
+		.and_then(|_| {
+			ForeignAssets::force_set_metadata(
+				RuntimeOrigin::root(),
+				id,
+				b"Kusama NIS Counterpart".to_vec(),
+				b"KTC".to_vec(),
+				12,
+				false,
+			)
+		});
+		if let Err(error) = created {
+			log::error!(target: "runtime::asset-hub", "Failed to register KTC: {:?}", error);
+		}
+
+		<Runtime as frame_system::Config>::DbWeight::get().reads_writes(2, 3)
+	}
+}
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -957,6 +1177,8 @@ mod benches {
 		[pallet_assets, Pool]
 		[pallet_asset_conversion, AssetConversion]
 		[pallet_balances, Balances]
+		[foreign_asset_registry, ForeignAssetRegistry]
+		[asset_vesting, AssetVesting]
 		[pallet_multisig, Multisig]
 		[pallet_nft_fractionalization, NftFractionalization]
 		[pallet_nfts, Nfts]
@@ -972,6 +1194,7 @@ mod benches {
 		// NOTE: Make sure you point to the individual modules below.
 		[pallet_xcm_benchmarks::fungible, XcmBalances]
 		[pallet_xcm_benchmarks::generic, XcmGeneric]
+		[nft_transactor::benchmarking, XcmNonFungibles]
 	);
 }
 
@@ -1087,6 +1310,32 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl asset_conversion_routing::AssetConversionRoutingApi<
+		Block,
+		Balance,
+		Box<MultiLocation>,
+	> for Runtime
+	{
+		fn quote_best_path_exact_tokens_for_tokens(
+			asset1: Box<MultiLocation>,
+			asset2: Box<MultiLocation>,
+			amount: Balance,
+		) -> Option<(Vec<Box<MultiLocation>>, Balance)> {
+			asset_conversion_routing::best_path_exact_tokens_for_tokens::<Runtime>(
+				asset1, asset2, amount,
+			)
+		}
+		fn quote_best_path_tokens_for_exact_tokens(
+			asset1: Box<MultiLocation>,
+			asset2: Box<MultiLocation>,
+			amount: Balance,
+		) -> Option<(Vec<Box<MultiLocation>>, Balance)> {
+			asset_conversion_routing::best_path_tokens_for_exact_tokens::<Runtime>(
+				asset1, asset2, amount,
+			)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -1171,6 +1420,92 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl asset_balances_api::AssetBalancesApi<Block, AccountId> for Runtime {
+		fn query_account_assets(
+			account: AccountId,
+		) -> Result<
+			Vec<asset_balances_api::AssetBalance>,
+			assets_common::runtime_api::FungiblesAccessError,
+		> {
+			use asset_balances_api::{pallet_assets_balances, AssetBalance};
+			use assets_common::AssetIdForPoolAssetsConvert;
+			use frame_support::traits::{Everything, Nothing};
+			use xcm_config::{PoolAssetsPalletLocation, TeleportableForeignAssets};
+			use xcm_executor::traits::Identity;
+
+			let native = System::account(&account).data;
+			Ok([
+				// collect pallet_balance
+				if native.free > 0 || native.reserved > 0 {
+					vec![AssetBalance {
+						asset: KsmLocation::get().into(),
+						free: native.free,
+						held: native.reserved,
+						frozen: native.frozen,
+						existential_deposit: ExistentialDeposit::get(),
+						is_sufficient: true,
+						decimals: 12,
+						symbol: b"KSM".to_vec(),
+						// KSM is teleported to the Relay Chain and the system parachains, and
+						// reserve-transferred to other parachains.
+						teleportable: true,
+						reserve_transferable: true,
+					}]
+				} else {
+					vec![]
+				},
+				// collect pallet_assets (TrustBackedAssets), of which this chain is the reserve
+				pallet_assets_balances::<
+					Runtime,
+					TrustBackedAssetsInstance,
+					AssetIdForTrustBackedAssetsConvert<TrustBackedAssetsPalletLocation>,
+					Nothing,
+					Everything,
+				>(&account)?,
+				// collect pallet_assets (ForeignAssets), of which this chain is not the reserve
+				pallet_assets_balances::<
+					Runtime,
+					ForeignAssetsInstance,
+					Identity,
+					TeleportableForeignAssets,
+					Nothing,
+				>(&account)?,
+				// collect pallet_assets (PoolAssets), of which this chain is the reserve
+				pallet_assets_balances::<
+					Runtime,
+					PoolAssetsInstance,
+					AssetIdForPoolAssetsConvert<PoolAssetsPalletLocation>,
+					Nothing,
+					Everything,
+				>(&account)?,
+			].concat())
+		}
+	}
+
+	impl asset_vesting::AssetVestingApi<
+		Block,
+		AccountId,
+		AssetIdForTrustBackedAssets,
+		Balance,
+	> for Runtime
+	{
+		fn vesting_balances(
+			who: AccountId,
+		) -> Vec<asset_vesting::AssetVestingBalance<AssetIdForTrustBackedAssets, Balance>> {
+			AssetVesting::vesting_balances(&who)
+		}
+	}
+
+	impl nft_locks::NftLocksApi<Block, u32, u32, nft_locks::NftLockReason> for Runtime {
+		fn uniques_locks(collection: u32, item: u32) -> Vec<nft_locks::NftLockReason> {
+			UniquesLocks::locks(collection, item)
+		}
+
+		fn nfts_locks(collection: u32, item: u32) -> Vec<nft_locks::NftLockReason> {
+			NftsLocks::locks(collection, item)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -1212,6 +1547,7 @@ impl_runtime_apis! {
 			// are referenced in that call.
 			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
 			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+			type XcmNonFungibles = nft_transactor::benchmarking::Pallet::<Runtime>;
 
 			// Benchmark files generated for `Assets/ForeignAssets` instances are by default
 			// `pallet_assets_assets.rs / pallet_assets_foreign_assets`, which is not really nice,
@@ -1310,6 +1646,32 @@ impl_runtime_apis! {
 				}
 			}
 
+			impl nft_transactor::benchmarking::Config for Runtime {
+				type TransactAsset = xcm_config::NftsTransactor;
+
+				fn escrowed_item() -> Result<MultiAsset, BenchmarkError> {
+					use frame_support::traits::{tokens::nonfungibles_v2::{Create, Mutate}, Currency};
+					use pallet_nfts::{CollectionConfig, CollectionSettings, ItemConfig, MintSettings};
+
+					let escrow = xcm_config::NftEscrowAccount::get();
+					Balances::make_free_balance_be(&escrow, 1_000 * UNITS);
+					let config = CollectionConfig {
+						settings: CollectionSettings::all_enabled(),
+						max_supply: None,
+						mint_settings: MintSettings::default(),
+					};
+					let collection = <Nfts as Create<_, _>>::create_collection(&escrow, &escrow, &config)
+						.map_err(|_| BenchmarkError::Stop("the collection is created"))?;
+					let item = 0;
+					<Nfts as Mutate<_, _>>::mint_into(&collection, &item, &escrow, &ItemConfig::default(), true)
+						.map_err(|_| BenchmarkError::Stop("the item is minted"))?;
+					let location = xcm_config::NftsPalletLocation::get()
+						.pushed_with_interior(GeneralIndex(collection.into()))
+						.map_err(|_| BenchmarkError::Stop("the collection has a location"))?;
+					Ok((location, Index(item.into())).into())
+				}
+			}
+
 			impl pallet_xcm_benchmarks::generic::Config for Runtime {
 				type RuntimeCall = RuntimeCall;
 
@@ -1356,6 +1718,7 @@ impl_runtime_apis! {
 
 			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
 			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+			type XcmNonFungibles = nft_transactor::benchmarking::Pallet::<Runtime>;
 
 			type Local = pallet_assets::Pallet::<Runtime, TrustBackedAssetsInstance>;
 			type Foreign = pallet_assets::Pallet::<Runtime, ForeignAssetsInstance>;
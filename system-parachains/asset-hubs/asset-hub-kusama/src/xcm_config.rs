@@ -16,9 +16,16 @@
 use super::{
 	AccountId, AllPalletsWithSystem, Assets, Authorship, Balance, Balances, ParachainInfo,
 	ParachainSystem, PolkadotXcm, PoolAssets, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin,
-	TrustBackedAssetsInstance, WeightToFee, XcmpQueue,
+	TransactionByteFee, TrustBackedAssetsInstance, WeightToFee, XcmpQueue,
+};
+use crate::{
+	foreign_asset_registry::{
+		RegisteredReserveAssets, RegisteredTeleportableAssets, Registrations, TransferKind,
+	},
+	nft_locks::LockFor,
+	nft_transactor::{NonFungiblesEscrowAdapter, NonFungiblesV2EscrowAdapter},
+	ForeignAssets, Nfts, NftsLocksInstance, Uniques, UniquesLocksInstance, XcmReserveNftLockReason,
 };
-use crate::ForeignAssets;
 use assets_common::{
 	local_and_foreign_assets::MatchesLocalAndForeignAssetsMultiLocation,
 	matching::{
@@ -28,24 +35,30 @@ use assets_common::{
 use frame_support::{
 	match_types, parameter_types,
 	traits::{ConstU32, Contains, Everything, Nothing, PalletInfoAccess},
+	PalletId,
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
 use parachains_common::{impls::ToStakingPot, xcm_config::AssetFeeAsExistentialDepositMultiplier};
 use polkadot_parachain_primitives::primitives::Sibling;
-use sp_runtime::traits::ConvertInto;
+use sp_runtime::traits::{AccountIdConversion, ConvertInto};
+pub use system_parachains_constants::xcm_config::{kusama::BaseDeliveryFee, WaivedLocations};
 use xcm::latest::prelude::*;
 use xcm_builder::{
 	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
-	AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom, CurrencyAdapter,
-	DenyReserveTransferToRelayChain, DenyThenTry, DescribeAllTerminal, DescribeFamily,
-	EnsureXcmOrigin, FungiblesAdapter, HashedDescription, IsConcrete, LocalMint, NativeAsset,
-	NoChecking, ParentAsSuperuser, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
-	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
-	SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId, UsingComponents,
-	WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
+	AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom, AsPrefixedGeneralIndex,
+	CurrencyAdapter, DenyReserveTransferToRelayChain, DenyThenTry, DescribeAllTerminal,
+	DescribeFamily, EnsureXcmOrigin, FungiblesAdapter, HashedDescription, IsConcrete, LocalMint,
+	MatchedConvertedConcreteId, NativeAsset, NoChecking, ParentAsSuperuser, ParentIsPreset,
+	RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
+	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
+	TrailingSetTopicAsId, UsingComponents, WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
+	XcmFeeManagerFromComponents,
+};
+use xcm_executor::{
+	traits::{JustTry, WithOriginFilter},
+	XcmExecutor,
 };
-use xcm_executor::{traits::WithOriginFilter, XcmExecutor};
 
 #[cfg(feature = "runtime-benchmarks")]
 use {cumulus_primitives_core::ParaId, sp_core::Get};
@@ -63,11 +76,31 @@ parameter_types! {
 		PalletInstance(<ForeignAssets as PalletInfoAccess>::index() as u8).into();
 	pub PoolAssetsPalletLocation: MultiLocation =
 		PalletInstance(<PoolAssets as PalletInfoAccess>::index() as u8).into();
+	pub UniquesPalletLocation: MultiLocation =
+		PalletInstance(<Uniques as PalletInfoAccess>::index() as u8).into();
+	pub NftsPalletLocation: MultiLocation =
+		PalletInstance(<Nfts as PalletInfoAccess>::index() as u8).into();
 	pub CheckingAccount: AccountId = PolkadotXcm::check_account();
+	pub const NftEscrowPalletId: PalletId = PalletId(*b"py/nftes");
+	/// The account holding the items of `Uniques` and `Nfts` withdrawn over XCM.
+	pub NftEscrowAccount: AccountId = NftEscrowPalletId::get().into_account_truncating();
 	pub const GovernanceLocation: MultiLocation = MultiLocation::parent();
 	pub const FellowshipLocation: MultiLocation = MultiLocation::parent();
+	/// The location of the NIS counterpart token (KTC) of the Relay Chain, registered as a foreign
+	/// asset.
+	pub const NisCounterpartLocation: MultiLocation = MultiLocation {
+		parents: 1,
+		interior: X1(PalletInstance(NIS_COUNTERPART_PALLET_INDEX)),
+	};
+	pub NisCounterpartFromRelay: (MultiAssetFilter, MultiLocation) = (
+		Wild(AllOf { fun: WildFungible, id: Concrete(NisCounterpartLocation::get()) }),
+		KsmLocation::get(),
+	);
 }
 
+/// The index of the `NisCounterpartBalances` pallet on the Relay Chain.
+pub const NIS_COUNTERPART_PALLET_INDEX: u8 = 45;
+
 /// Type for specifying how a `MultiLocation` can be converted into an `AccountId`. This is used
 /// when determining ownership of accounts for asset transacting and when attempting to use XCM
 /// `Transact` in order to determine the dispatch Origin.
@@ -169,9 +202,65 @@ pub type PoolFungiblesTransactor = FungiblesAdapter<
 	CheckingAccount,
 >;
 
+/// Collection/item converter for `Uniques`.
+pub type UniquesConvertedConcreteId = MatchedConvertedConcreteId<
+	u32,
+	u32,
+	StartsWith<UniquesPalletLocation>,
+	AsPrefixedGeneralIndex<UniquesPalletLocation, u32, JustTry>,
+	JustTry,
+>;
+
+/// Means for transacting the items of `Uniques` on this chain.
+pub type UniquesTransactor = NonFungiblesEscrowAdapter<
+	// Use this nonfungibles implementation:
+	Uniques,
+	// Use this collection and item when it is a non-fungible asset matching the given location:
+	UniquesConvertedConcreteId,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// The account holding the withdrawn items.
+	NftEscrowAccount,
+	// The lock of the items held by other locations.
+	LockFor<Runtime, UniquesLocksInstance, XcmReserveNftLockReason>,
+>;
+
+/// Collection/item converter for `Nfts`.
+pub type NftsConvertedConcreteId = MatchedConvertedConcreteId<
+	u32,
+	u32,
+	StartsWith<NftsPalletLocation>,
+	AsPrefixedGeneralIndex<NftsPalletLocation, u32, JustTry>,
+	JustTry,
+>;
+
+/// Means for transacting the items of `Nfts` on this chain.
+pub type NftsTransactor = NonFungiblesV2EscrowAdapter<
+	// Use this nonfungibles implementation:
+	Nfts,
+	// Use this collection and item when it is a non-fungible asset matching the given location:
+	NftsConvertedConcreteId,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// The account holding the withdrawn items.
+	NftEscrowAccount,
+	// The lock of the items held by other locations.
+	LockFor<Runtime, NftsLocksInstance, XcmReserveNftLockReason>,
+>;
+
 /// Means for transacting assets on this chain.
-pub type AssetTransactors =
-	(CurrencyTransactor, FungiblesTransactor, ForeignFungiblesTransactor, PoolFungiblesTransactor);
+pub type AssetTransactors = (
+	CurrencyTransactor,
+	FungiblesTransactor,
+	ForeignFungiblesTransactor,
+	PoolFungiblesTransactor,
+	UniquesTransactor,
+	NftsTransactor,
+);
 
 /// Simple `MultiLocation` matcher for Local and Foreign asset `MultiLocation`.
 pub struct LocalAndForeignAssetsMultiLocationMatcher;
@@ -191,6 +280,24 @@ impl Contains<MultiLocation> for LocalAndForeignAssetsMultiLocationMatcher {
 	}
 }
 
+/// Foreign assets which `IsTeleporter` accepts from their origin, so which can be teleported back
+/// to it: the NIS counterpart, the assets of sibling parachains, and the assets registered as
+/// teleportable in `ForeignAssetRegistry`.
+pub struct TeleportableForeignAssets;
+impl Contains<MultiLocation> for TeleportableForeignAssets {
+	fn contains(location: &MultiLocation) -> bool {
+		let from_sibling = location.parents == 1 &&
+			matches!(
+				location.first_interior(),
+				Some(Parachain(id)) if *id != u32::from(ParachainInfo::parachain_id())
+			);
+		*location == NisCounterpartLocation::get() ||
+			from_sibling ||
+			Registrations::<Runtime>::get(location)
+				.map_or(false, |registration| registration.transfer == TransferKind::Teleport)
+	}
+}
+
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
@@ -271,6 +378,8 @@ impl Contains<RuntimeCall> for SafeCallFilter {
 				) | RuntimeCall::Session(pallet_session::Call::purge_keys { .. }) |
 				RuntimeCall::XcmpQueue(..) |
 				RuntimeCall::DmpQueue(..) |
+				RuntimeCall::ForeignAssetRegistry(..) |
+				RuntimeCall::AssetVesting(..) |
 				RuntimeCall::Assets(
 					pallet_assets::Call::create { .. } |
 						pallet_assets::Call::force_create { .. } |
@@ -476,16 +585,21 @@ impl xcm_executor::Config for XcmConfig {
 	type XcmSender = XcmRouter;
 	type AssetTransactor = AssetTransactors;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
-	// Asset Hub Kusama does not recognize a reserve location for any asset. This does not prevent
-	// Asset Hub acting _as_ a reserve location for KSM and assets created under `pallet-assets`.
-	// For KSM, users must use teleport where allowed (e.g. with the Relay Chain).
-	type IsReserve = ();
+	// Asset Hub Kusama recognizes other consensus systems as reserve locations only for the assets
+	// registered as reserve-backed in `ForeignAssetRegistry`. This does not prevent Asset Hub
+	// acting _as_ a reserve location for KSM and assets created under `pallet-assets`. For KSM,
+	// users must use teleport where allowed (e.g. with the Relay Chain).
+	type IsReserve = RegisteredReserveAssets<Runtime>;
 	// We allow:
 	// - teleportation of KSM
+	// - teleportation of the NIS counterpart (KTC) with the Relay Chain
 	// - teleportation of sibling parachain's assets (as ForeignCreators)
+	// - teleportation of assets registered as teleportable in `ForeignAssetRegistry`
 	type IsTeleporter = (
 		NativeAsset,
+		xcm_builder::Case<NisCounterpartFromRelay>,
 		IsForeignConcreteAsset<FromSiblingParachain<parachain_info::Pallet<Runtime>>>,
+		RegisteredTeleportableAssets<Runtime>,
 	);
 	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
@@ -516,7 +630,8 @@ impl xcm_executor::Config for XcmConfig {
 	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
 	type AssetLocker = ();
 	type AssetExchanger = ();
-	type FeeManager = ();
+	// Delivery fees are burnt, except for governance and the system parachains.
+	type FeeManager = XcmFeeManagerFromComponents<WaivedLocations, ()>;
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
 	type CallDispatcher = WithOriginFilter<SafeCallFilter>;
@@ -528,11 +643,24 @@ impl xcm_executor::Config for XcmConfig {
 /// Forms the basis for local origins sending/executing XCMs.
 pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
 
+parameter_types! {
+	/// The asset ID for the asset that we use to pay for message delivery fees.
+	pub FeeAssetId: AssetId = Concrete(KsmLocation::get());
+}
+
+/// The price of delivering a message to the Relay Chain.
+pub type UmpDeliveryPrice = system_parachains_constants::xcm_config::UmpDeliveryPrice<
+	Runtime,
+	FeeAssetId,
+	BaseDeliveryFee,
+	TransactionByteFee,
+>;
+
 /// The means for routing XCM messages which are not for local execution into the right message
 /// queues.
 pub type XcmRouter = WithUniqueTopic<(
 	// Two routers - use UMP to communicate with the relay chain:
-	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, PolkadotXcm, ()>,
+	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, PolkadotXcm, UmpDeliveryPrice>,
 	// ..and XCMP to communicate with the sibling chains.
 	XcmpQueue,
 )>;
@@ -395,7 +395,14 @@ fn test_asset_xcm_trader_not_possible_for_non_sufficient_assets() {
 
 #[test]
 fn test_assets_balances_api_works() {
+	use asset_hub_kusama_runtime::{
+		asset_balances_api::{runtime_decl_for_asset_balances_api::AssetBalancesApi, AssetBalance},
+		asset_locks::AssetHoldReason,
+		asset_vesting::FreezeReason,
+		AssetLocks, RuntimeFreezeReason,
+	};
 	use assets_common::runtime_api::runtime_decl_for_fungibles_api::FungiblesApi;
+	use frame_support::traits::tokens::fungibles::{MutateFreeze, MutateHold};
 
 	ExtBuilder::<Runtime>::default()
 		.with_collators(vec![AccountId::from(ALICE)])
@@ -500,9 +507,1192 @@ fn test_assets_balances_api_works() {
 				6 * foreign_asset_minimum_asset_balance
 			)
 				.into())));
+
+			// Hold and freeze some of the trusted asset, and give it metadata
+			assert_ok!(Assets::mint(
+				RuntimeHelper::origin_of(AccountId::from(ALICE)),
+				local_asset_id.into(),
+				AccountId::from(ALICE).into(),
+				2 * minimum_asset_balance
+			));
+			assert_ok!(AssetLocks::hold(
+				local_asset_id,
+				&AssetHoldReason::Governance,
+				&AccountId::from(ALICE),
+				minimum_asset_balance
+			));
+			assert_ok!(AssetLocks::set_freeze(
+				local_asset_id,
+				&RuntimeFreezeReason::AssetVesting(FreezeReason::Vesting),
+				&AccountId::from(ALICE),
+				minimum_asset_balance
+			));
+			assert_ok!(Assets::force_set_metadata(
+				RuntimeHelper::root_origin(),
+				local_asset_id.into(),
+				b"Local".to_vec(),
+				b"LOC".to_vec(),
+				6,
+				false
+			));
+
+			let assets = Runtime::query_account_assets(AccountId::from(ALICE)).unwrap();
+			assert_eq!(assets.len(), 3);
+
+			// check currency
+			assert!(assets.contains(&AssetBalance {
+				asset: KsmLocation::get().into(),
+				free: some_currency,
+				held: 0,
+				frozen: 0,
+				existential_deposit: ExistentialDeposit::get(),
+				is_sufficient: true,
+				decimals: 12,
+				symbol: b"KSM".to_vec(),
+				teleportable: true,
+				reserve_transferable: true,
+			}));
+			// check trusted asset, which is reserve-transferred from here
+			assert!(assets.contains(&AssetBalance {
+				asset: AssetIdForTrustBackedAssetsConvert::convert_back(&local_asset_id)
+					.unwrap()
+					.into(),
+				free: 2 * minimum_asset_balance,
+				held: minimum_asset_balance,
+				frozen: minimum_asset_balance,
+				existential_deposit: minimum_asset_balance,
+				is_sufficient: true,
+				decimals: 6,
+				symbol: b"LOC".to_vec(),
+				teleportable: false,
+				reserve_transferable: true,
+			}));
+			// check foreign asset, which is teleported back to its sibling parachain
+			assert!(assets.contains(&AssetBalance {
+				asset: foreign_asset_id_multilocation.into(),
+				free: 6 * foreign_asset_minimum_asset_balance,
+				held: 0,
+				frozen: 0,
+				existential_deposit: foreign_asset_minimum_asset_balance,
+				is_sufficient: false,
+				decimals: 0,
+				symbol: vec![],
+				teleportable: true,
+				reserve_transferable: false,
+			}));
+
+			// The hold keeps the account alive, so burning all of the trusted asset only burns it
+			// down to its existential deposit
+			assert_ok!(AssetLocks::thaw(
+				local_asset_id,
+				&RuntimeFreezeReason::AssetVesting(FreezeReason::Vesting),
+				&AccountId::from(ALICE)
+			));
+			assert_ok!(Assets::burn(
+				RuntimeHelper::origin_of(AccountId::from(ALICE)),
+				local_asset_id.into(),
+				AccountId::from(ALICE).into(),
+				2 * minimum_asset_balance
+			));
+			assert_eq!(
+				Assets::balance(local_asset_id, AccountId::from(ALICE)),
+				minimum_asset_balance
+			);
+			let local_asset_location =
+				AssetIdForTrustBackedAssetsConvert::convert_back(&local_asset_id).unwrap();
+			let assets = Runtime::query_account_assets(AccountId::from(ALICE)).unwrap();
+			assert_eq!(assets.len(), 3);
+			let local_asset =
+				assets.iter().find(|asset| asset.asset == local_asset_location.into()).unwrap();
+			assert_eq!(
+				(local_asset.free, local_asset.held, local_asset.frozen),
+				(minimum_asset_balance, minimum_asset_balance, 0)
+			);
+
+			// Once released, the held amount is free again
+			assert_ok!(AssetLocks::release(
+				local_asset_id,
+				&AssetHoldReason::Governance,
+				&AccountId::from(ALICE),
+				minimum_asset_balance,
+				frame_support::traits::tokens::Precision::Exact
+			));
+			let assets = Runtime::query_account_assets(AccountId::from(ALICE)).unwrap();
+			let local_asset =
+				assets.iter().find(|asset| asset.asset == local_asset_location.into()).unwrap();
+			assert_eq!((local_asset.free, local_asset.held), (2 * minimum_asset_balance, 0));
 		});
 }
 
+#[test]
+fn nis_counterpart_is_registered_as_foreign_asset() {
+	use asset_hub_kusama_runtime::{
+		xcm_config::{LocalAndForeignAssetsMultiLocationMatcher, NisCounterpartLocation},
+		RegisterNisCounterpart,
+	};
+	use assets_common::local_and_foreign_assets::MatchesLocalAndForeignAssetsMultiLocation;
+	use frame_support::traits::{fungibles::metadata::Inspect, OnRuntimeUpgrade};
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let ktc = NisCounterpartLocation::get();
+			// Pools of `AssetConversion` accept foreign assets.
+			assert!(LocalAndForeignAssetsMultiLocationMatcher::is_foreign(&ktc));
+			assert!(!pallet_assets::Asset::<Runtime, ForeignAssetsInstance>::contains_key(ktc));
+
+			RegisterNisCounterpart::on_runtime_upgrade();
+			assert!(pallet_assets::Asset::<Runtime, ForeignAssetsInstance>::contains_key(ktc));
+			assert_eq!(ForeignAssets::symbol(ktc), b"KTC".to_vec());
+			assert_eq!(ForeignAssets::decimals(ktc), 12);
+
+			// Registering again does nothing.
+			RegisterNisCounterpart::on_runtime_upgrade();
+			assert_eq!(ForeignAssets::asset_ids().filter(|id| *id == ktc).count(), 1);
+		})
+}
+
+#[test]
+fn lp_tokens_get_metadata_and_move_over_xcm() {
+	use asset_hub_kusama_runtime::{
+		xcm_config::{LocationToAccountId, PoolAssetsPalletLocation, PoolFungiblesTransactor},
+		AssetConversion, PoolAssets,
+	};
+	use frame_support::traits::{
+		fungible::Mutate,
+		fungibles::{metadata::Inspect as _, Inspect as _},
+	};
+	use parachains_common::kusama::currency::UNITS;
+	use xcm_executor::traits::{ConvertLocation, TransactAsset};
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			Balances::mint_into(&alice, 10_000 * UNITS).unwrap();
+			assert_ok!(Assets::force_create(
+				RuntimeHelper::root_origin(),
+				1.into(),
+				alice.clone().into(),
+				true,
+				1
+			));
+			assert_ok!(Assets::mint(
+				RuntimeHelper::origin_of(alice.clone()),
+				1.into(),
+				alice.clone().into(),
+				10_000 * UNITS
+			));
+			let ksm = Box::new(KsmLocation::get());
+			let asset1 = Box::new(AssetIdForTrustBackedAssetsConvert::convert_back(&1).unwrap());
+			assert_ok!(AssetConversion::create_pool(
+				RuntimeHelper::origin_of(alice.clone()),
+				ksm.clone(),
+				asset1.clone()
+			));
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeHelper::origin_of(alice.clone()),
+				ksm.clone(),
+				asset1.clone(),
+				1_000 * UNITS,
+				1_000 * UNITS,
+				1,
+				1,
+				alice.clone()
+			));
+
+			// The LP token got its metadata on pool creation.
+			let lp_token = pallet_asset_conversion::Pools::<Runtime>::get(
+				AssetConversion::get_pool_id(ksm, asset1),
+			)
+			.unwrap()
+			.lp_token;
+			assert_eq!(PoolAssets::name(lp_token), format!("LP Token {lp_token}").into_bytes());
+			assert_eq!(PoolAssets::symbol(lp_token), format!("LP{lp_token}").into_bytes());
+			assert_eq!(PoolAssets::decimals(lp_token), 0);
+
+			// LP tokens do not pay for weight.
+			RuntimeHelper::run_to_block(2, alice.clone());
+			let lp_location = PoolAssetsPalletLocation::get()
+				.pushed_with_interior(GeneralIndex(lp_token.into()))
+				.unwrap();
+			let mut trader = <XcmConfig as xcm_executor::Config>::Trader::new();
+			let ctx = XcmContext { origin: None, message_id: XcmHash::default(), topic: None };
+			let bought = Weight::from_parts(4_000_000_000u64, 0);
+			assert!(trader
+				.buy_weight(bought, MultiAsset::from((lp_location, 1_000 * UNITS)).into(), &ctx)
+				.is_err());
+
+			// LP tokens are reserve-transferred to the sovereign account of a sibling.
+			let sibling = MultiLocation::new(1, X1(Parachain(2000)));
+			let sibling_account = LocationToAccountId::convert_location(&sibling).unwrap();
+			let alice_location =
+				MultiLocation::new(0, X1(AccountId32 { network: None, id: ALICE }));
+			assert_ok!(PoolFungiblesTransactor::transfer_asset(
+				&(lp_location, 10 * UNITS).into(),
+				&alice_location,
+				&sibling,
+				&ctx
+			));
+			assert_eq!(PoolAssets::balance(lp_token, &sibling_account), 10 * UNITS);
+		})
+}
+
+#[test]
+fn swaps_are_routed_through_the_best_pools() {
+	use asset_hub_kusama_runtime::{
+		asset_conversion_routing::best_path_exact_tokens_for_tokens, AssetConversion,
+	};
+	use frame_support::traits::fungible::Mutate;
+	use parachains_common::kusama::currency::UNITS;
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			Balances::mint_into(&alice, 10_000 * UNITS).unwrap();
+			for asset_id in [1, 2] {
+				assert_ok!(Assets::force_create(
+					RuntimeHelper::root_origin(),
+					asset_id.into(),
+					alice.clone().into(),
+					true,
+					1
+				));
+				assert_ok!(Assets::mint(
+					RuntimeHelper::origin_of(alice.clone()),
+					asset_id.into(),
+					alice.clone().into(),
+					10_000 * UNITS
+				));
+			}
+			let ksm = Box::new(KsmLocation::get());
+			let asset1 = Box::new(AssetIdForTrustBackedAssetsConvert::convert_back(&1).unwrap());
+			let asset2 = Box::new(AssetIdForTrustBackedAssetsConvert::convert_back(&2).unwrap());
+
+			// Deep pools with KSM and a shallow pool of the two assets.
+			for (a, b, liquidity) in [
+				(ksm.clone(), asset1.clone(), 1_000 * UNITS),
+				(ksm.clone(), asset2.clone(), 1_000 * UNITS),
+				(asset1.clone(), asset2.clone(), 10 * UNITS),
+			] {
+				assert_ok!(AssetConversion::create_pool(
+					RuntimeHelper::origin_of(alice.clone()),
+					a.clone(),
+					b.clone()
+				));
+				assert_ok!(AssetConversion::add_liquidity(
+					RuntimeHelper::origin_of(alice.clone()),
+					a,
+					b,
+					liquidity,
+					liquidity,
+					1,
+					1,
+					alice.clone()
+				));
+			}
+
+			// Any two assets paired with KSM can be swapped through KSM.
+			assert!(asset_hub_kusama_runtime::MaxSwapPathLength::get() >= 3);
+			let (path, amount_out) =
+				best_path_exact_tokens_for_tokens::<Runtime>(asset1.clone(), asset2.clone(), UNITS)
+					.unwrap();
+			assert_eq!(path, vec![asset1.clone(), ksm, asset2.clone()]);
+			assert!(
+				amount_out >
+					AssetConversion::quote_price_exact_tokens_for_tokens(
+						asset1, asset2, UNITS, true
+					)
+					.unwrap()
+			);
+		})
+}
+
+#[test]
+fn uniques_items_are_withdrawn_into_and_deposited_from_escrow() {
+	use asset_hub_kusama_runtime::{
+		nft_locks::NftLockReason,
+		xcm_config::{
+			LocationToAccountId, NftEscrowAccount, UniquesPalletLocation, UniquesTransactor,
+		},
+		Uniques, UniquesLocks,
+	};
+	use frame_support::traits::tokens::nonfungibles::Inspect;
+	use xcm_executor::traits::{ConvertLocation, TransactAsset};
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			let bob = AccountId::from(SOME_ASSET_ADMIN);
+			let (collection, item) = (1, 7);
+			assert_ok!(Uniques::force_create(
+				RuntimeHelper::root_origin(),
+				collection,
+				alice.clone().into(),
+				true
+			));
+			assert_ok!(Uniques::mint(
+				RuntimeHelper::origin_of(alice.clone()),
+				collection,
+				item,
+				alice.clone().into()
+			));
+
+			let asset: MultiAsset = (
+				UniquesPalletLocation::get()
+					.pushed_with_interior(GeneralIndex(collection.into()))
+					.unwrap(),
+				Index(item.into()),
+			)
+				.into();
+			let alice_location =
+				MultiLocation::new(0, X1(AccountId32 { network: None, id: ALICE }));
+			let bob_location =
+				MultiLocation::new(0, X1(AccountId32 { network: None, id: SOME_ASSET_ADMIN }));
+			let sibling = MultiLocation::new(1, X1(Parachain(2000)));
+			let sibling_account = LocationToAccountId::convert_location(&sibling).unwrap();
+			let context = XcmContext { origin: None, message_id: [0; 32], topic: None };
+
+			// Items moved to other locations are locked as their reserve...
+			assert_ok!(UniquesTransactor::transfer_asset(
+				&asset,
+				&alice_location,
+				&sibling,
+				&context
+			));
+			assert_eq!(Uniques::owner(&collection, &item), Some(sibling_account.clone()));
+			assert_eq!(UniquesLocks::locks(collection, item), vec![NftLockReason::XcmReserve]);
+			assert_noop!(
+				Uniques::transfer(
+					RuntimeHelper::origin_of(sibling_account),
+					collection,
+					item,
+					alice.clone().into()
+				),
+				pallet_uniques::Error::<Runtime>::Locked
+			);
+
+			// ...and unlocked when they are withdrawn into escrow, which only takes the owner's
+			// items.
+			assert!(UniquesTransactor::withdraw_asset(&asset, &bob_location, None).is_err());
+			assert_ok!(UniquesTransactor::withdraw_asset(&asset, &sibling, None));
+			assert_eq!(Uniques::owner(&collection, &item), Some(NftEscrowAccount::get()));
+			assert!(UniquesLocks::locks(collection, item).is_empty());
+
+			// Deposited items leave escrow instead of being minted.
+			assert_ok!(UniquesTransactor::deposit_asset(&asset, &bob_location, &context));
+			assert_eq!(Uniques::owner(&collection, &item), Some(bob));
+			assert!(UniquesLocks::locks(collection, item).is_empty());
+			assert!(UniquesTransactor::deposit_asset(&asset, &alice_location, &context).is_err());
+		})
+}
+
+#[test]
+fn nfts_items_are_reserve_transferred_and_escrowed() {
+	use asset_hub_kusama_runtime::{
+		nft_locks::NftLockReason,
+		xcm_config::{LocationToAccountId, NftEscrowAccount, NftsPalletLocation, NftsTransactor},
+		Nfts, NftsLocks,
+	};
+	use frame_support::traits::{fungible::Mutate, tokens::nonfungibles_v2::Inspect};
+	use pallet_nfts::{CollectionConfig, CollectionSettings, MintSettings};
+	use xcm_executor::traits::{ConvertLocation, TransactAsset};
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			let bob = AccountId::from(SOME_ASSET_ADMIN);
+			let (collection, item) = (0, 7);
+			assert_ok!(Balances::mint_into(&alice, 1_000 * AssetDeposit::get()));
+			assert_ok!(Nfts::force_create(
+				RuntimeHelper::root_origin(),
+				alice.clone().into(),
+				CollectionConfig {
+					settings: CollectionSettings::all_enabled(),
+					max_supply: None,
+					mint_settings: MintSettings::default(),
+				}
+			));
+			assert_ok!(Nfts::mint(
+				RuntimeHelper::origin_of(alice.clone()),
+				collection,
+				item,
+				alice.clone().into(),
+				None
+			));
+
+			let asset: MultiAsset = (
+				NftsPalletLocation::get()
+					.pushed_with_interior(GeneralIndex(collection.into()))
+					.unwrap(),
+				Index(item.into()),
+			)
+				.into();
+			let alice_location =
+				MultiLocation::new(0, X1(AccountId32 { network: None, id: ALICE }));
+			let bob_location =
+				MultiLocation::new(0, X1(AccountId32 { network: None, id: SOME_ASSET_ADMIN }));
+			let sibling = MultiLocation::new(1, X1(Parachain(2000)));
+			let sibling_account = LocationToAccountId::convert_location(&sibling).unwrap();
+			let context = XcmContext { origin: None, message_id: [0; 32], topic: None };
+
+			// Items locked otherwise cannot be moved.
+			assert_ok!(NftsLocks::lock(collection, item, NftLockReason::Fractionalized));
+			assert!(NftsTransactor::withdraw_asset(&asset, &alice_location, None).is_err());
+			assert!(NftsTransactor::internal_transfer_asset(
+				&asset,
+				&alice_location,
+				&sibling,
+				&context
+			)
+			.is_err());
+			assert_ok!(NftsLocks::unlock(collection, item, NftLockReason::Fractionalized));
+
+			// A reserve transfer moves the item to the sovereign account of the destination and
+			// locks it there...
+			assert_ok!(NftsTransactor::internal_transfer_asset(
+				&asset,
+				&alice_location,
+				&sibling,
+				&context
+			));
+			assert_eq!(Nfts::owner(&collection, &item), Some(sibling_account.clone()));
+			assert_eq!(NftsLocks::locks(collection, item), vec![NftLockReason::XcmReserve]);
+			assert_noop!(
+				Nfts::transfer(
+					RuntimeHelper::origin_of(sibling_account),
+					collection,
+					item,
+					alice.clone().into()
+				),
+				pallet_nfts::Error::<Runtime>::ItemLocked
+			);
+
+			// ...until the destination sends it back, through escrow.
+			assert!(NftsTransactor::withdraw_asset(&asset, &alice_location, None).is_err());
+			assert_ok!(NftsTransactor::withdraw_asset(&asset, &sibling, None));
+			assert_eq!(Nfts::owner(&collection, &item), Some(NftEscrowAccount::get()));
+			assert!(NftsLocks::locks(collection, item).is_empty());
+			assert_ok!(NftsTransactor::deposit_asset(&asset, &bob_location, &context));
+			assert_eq!(Nfts::owner(&collection, &item), Some(bob.clone()));
+			assert!(NftsLocks::locks(collection, item).is_empty());
+
+			// Local transfers leave the items unlocked.
+			assert_ok!(NftsTransactor::internal_transfer_asset(
+				&asset,
+				&bob_location,
+				&alice_location,
+				&context
+			));
+			assert_eq!(Nfts::owner(&collection, &item), Some(alice));
+			assert!(NftsLocks::locks(collection, item).is_empty());
+		})
+}
+
+#[test]
+fn locked_nfts_cannot_be_transferred_or_burnt() {
+	use asset_hub_kusama_runtime::{
+		nft_locks::NftLockReason, NftFractionalization, NftFractionalizationPalletId, Nfts,
+		NftsLocks, Uniques, UniquesLocks,
+	};
+	use frame_support::traits::fungible::Mutate;
+	use pallet_nfts::{CollectionConfig, CollectionSettings, MintSettings};
+	use sp_runtime::traits::{AccountIdConversion, Dispatchable};
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			let bob = AccountId::from(SOME_ASSET_ADMIN);
+			let (collection, item, fractions_id) = (0, 7, 11);
+			let pallet_account: AccountId =
+				NftFractionalizationPalletId::get().into_account_truncating();
+			assert_ok!(Balances::mint_into(&alice, 1_000 * AssetDeposit::get()));
+			assert_ok!(Balances::mint_into(&pallet_account, 1_000 * AssetDeposit::get()));
+			assert_ok!(Nfts::force_create(
+				RuntimeHelper::root_origin(),
+				alice.clone().into(),
+				CollectionConfig {
+					settings: CollectionSettings::all_enabled(),
+					max_supply: None,
+					mint_settings: MintSettings::default(),
+				}
+			));
+			assert_ok!(Nfts::mint(
+				RuntimeHelper::origin_of(alice.clone()),
+				collection,
+				item,
+				alice.clone().into(),
+				None
+			));
+
+			// Fractionalized items stay with their owner, but they are locked...
+			assert_ok!(NftFractionalization::fractionalize(
+				RuntimeHelper::origin_of(alice.clone()),
+				collection,
+				item,
+				fractions_id.into(),
+				alice.clone().into(),
+				1_000
+			));
+			assert_eq!(NftsLocks::locks(collection, item), vec![NftLockReason::Fractionalized]);
+
+			// ...so they can neither be transferred...
+			assert_noop!(
+				Nfts::transfer(
+					RuntimeHelper::origin_of(alice.clone()),
+					collection,
+					item,
+					bob.clone().into()
+				),
+				pallet_nfts::Error::<Runtime>::ItemLocked
+			);
+			// ...nor burnt...
+			let burn = RuntimeCall::Nfts(pallet_nfts::Call::burn { collection, item });
+			assert_noop!(
+				burn.clone().dispatch(RuntimeHelper::origin_of(alice.clone())),
+				frame_system::Error::<Runtime>::CallFiltered
+			);
+
+			// ...until they are unified again.
+			assert_ok!(NftFractionalization::unify(
+				RuntimeHelper::origin_of(alice.clone()),
+				collection,
+				item,
+				fractions_id.into(),
+				alice.clone().into()
+			));
+			assert!(NftsLocks::locks(collection, item).is_empty());
+			assert_ok!(burn.dispatch(RuntimeHelper::origin_of(alice.clone())));
+
+			// The `Uniques` collections with locked items cannot be destroyed either.
+			assert_ok!(Uniques::force_create(
+				RuntimeHelper::root_origin(),
+				collection,
+				alice.clone().into(),
+				true
+			));
+			assert_ok!(Uniques::mint(
+				RuntimeHelper::origin_of(alice.clone()),
+				collection,
+				item,
+				alice.clone().into()
+			));
+			assert_ok!(UniquesLocks::lock(collection, item, NftLockReason::XcmReserve));
+			let burn = RuntimeCall::Uniques(pallet_uniques::Call::burn {
+				collection,
+				item,
+				check_owner: None,
+			});
+			let destroy = RuntimeCall::Uniques(pallet_uniques::Call::destroy {
+				collection,
+				witness: pallet_uniques::DestroyWitness {
+					items: 1,
+					item_metadatas: 0,
+					attributes: 0,
+				},
+			});
+			assert_noop!(
+				burn.dispatch(RuntimeHelper::origin_of(alice.clone())),
+				frame_system::Error::<Runtime>::CallFiltered
+			);
+			assert_noop!(
+				destroy.clone().dispatch(RuntimeHelper::origin_of(alice.clone())),
+				frame_system::Error::<Runtime>::CallFiltered
+			);
+			assert_ok!(UniquesLocks::unlock(collection, item, NftLockReason::XcmReserve));
+			assert_ok!(destroy.dispatch(RuntimeHelper::origin_of(alice)));
+		})
+}
+
+#[test]
+fn vesting_assets_are_frozen_until_vested() {
+	use asset_hub_kusama_runtime::{
+		asset_vesting::{AssetVestingBalance, Error, VestingInfo},
+		xcm_config::FungiblesTransactor,
+		AssetVesting, MinVestedAssetTransferMultiple,
+	};
+	use xcm_executor::traits::TransactAsset;
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			let bob = AccountId::from(SOME_ASSET_ADMIN);
+			let asset_id = 1;
+			assert_ok!(Assets::force_create(
+				RuntimeHelper::root_origin(),
+				asset_id.into(),
+				alice.clone().into(),
+				true,
+				1
+			));
+			assert_ok!(Assets::mint(
+				RuntimeHelper::origin_of(alice.clone()),
+				asset_id.into(),
+				alice.clone().into(),
+				1_000
+			));
+
+			System::set_block_number(1);
+			// Vested transfers move at least a multiple of the minimum balance.
+			let locked = MinVestedAssetTransferMultiple::get() as Balance - 1;
+			assert_noop!(
+				AssetVesting::vested_transfer(
+					RuntimeHelper::origin_of(alice.clone()),
+					bob.clone().into(),
+					asset_id,
+					VestingInfo { locked, per_block: 1, starting_block: 10 }
+				),
+				Error::<Runtime>::AmountLow
+			);
+			let schedule = VestingInfo { locked: 500, per_block: 50, starting_block: 10 };
+			assert_ok!(AssetVesting::vested_transfer(
+				RuntimeHelper::origin_of(alice.clone()),
+				bob.clone().into(),
+				asset_id,
+				schedule
+			));
+			assert_eq!(Assets::balance(asset_id, &bob), 500);
+
+			let asset_location =
+				AssetIdForTrustBackedAssetsConvert::convert_back(&asset_id).unwrap();
+			let bob_location =
+				MultiLocation::new(0, X1(AccountId32 { network: None, id: SOME_ASSET_ADMIN }));
+			let withdraw = |amount: Balance| {
+				FungiblesTransactor::withdraw_asset(
+					&(asset_location, amount).into(),
+					&bob_location,
+					None,
+				)
+			};
+
+			// Vesting assets cannot be withdrawn...
+			assert!(withdraw(1).is_err());
+
+			// ...until they are vested.
+			System::set_block_number(14);
+			assert_eq!(
+				AssetVesting::vesting_balances(&bob),
+				vec![AssetVestingBalance { asset: asset_id, locked: 300, claimable: 200 }]
+			);
+			assert!(withdraw(1).is_err());
+			assert_ok!(AssetVesting::vest(RuntimeHelper::origin_of(bob.clone()), asset_id));
+			// The minimum balance stays with the frozen assets.
+			assert_ok!(withdraw(199));
+			assert!(withdraw(1).is_err());
+			assert_eq!(Assets::balance(asset_id, &bob), 301);
+
+			System::set_block_number(20);
+			assert_ok!(AssetVesting::vest(RuntimeHelper::origin_of(bob.clone()), asset_id));
+			assert!(AssetVesting::vesting_balances(&bob).is_empty());
+			assert_ok!(withdraw(301));
+		})
+}
+
+#[test]
+fn vesting_schedules_can_be_merged_and_removed() {
+	use asset_hub_kusama_runtime::{
+		asset_vesting::{AssetVestingBalance, Error, Vesting, VestingInfo},
+		AssetVesting,
+	};
+	use sp_runtime::DispatchError;
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			let bob = AccountId::from(SOME_ASSET_ADMIN);
+			let asset_id = 1;
+			assert_ok!(Assets::force_create(
+				RuntimeHelper::root_origin(),
+				asset_id.into(),
+				alice.clone().into(),
+				true,
+				1
+			));
+			assert_ok!(Assets::mint(
+				RuntimeHelper::origin_of(alice.clone()),
+				asset_id.into(),
+				alice.clone().into(),
+				10_000
+			));
+
+			System::set_block_number(1);
+			let vested_transfer = |schedule| {
+				AssetVesting::vested_transfer(
+					RuntimeHelper::origin_of(alice.clone()),
+					bob.clone().into(),
+					asset_id,
+					schedule,
+				)
+			};
+			// Ends at block 20.
+			assert_ok!(vested_transfer(VestingInfo {
+				locked: 1_000,
+				per_block: 100,
+				starting_block: 10
+			}));
+			// Ends at block 50.
+			assert_ok!(vested_transfer(VestingInfo {
+				locked: 600,
+				per_block: 20,
+				starting_block: 20
+			}));
+
+			// The merged schedule unlocks what both still lock, from the later start to the
+			// later end.
+			System::set_block_number(15);
+			let merge = |who: &AccountId, schedule2_index| {
+				AssetVesting::merge_schedules(
+					RuntimeHelper::origin_of(who.clone()),
+					asset_id,
+					0,
+					schedule2_index,
+				)
+			};
+			assert_noop!(merge(&bob, 2), Error::<Runtime>::ScheduleIndexOutOfBounds);
+			assert_noop!(merge(&alice, 1), Error::<Runtime>::NotVesting);
+			assert_ok!(merge(&bob, 1));
+			let merged = VestingInfo { locked: 1_100, per_block: 36, starting_block: 20 };
+			assert_eq!(Vesting::<Runtime>::get(&bob, asset_id).unwrap().into_inner(), vec![merged]);
+			assert_eq!(
+				AssetVesting::vesting_balances(&bob),
+				vec![AssetVestingBalance { asset: asset_id, locked: 1_100, claimable: 0 }]
+			);
+
+			// Governance removes a schedule, unlocking what it still locks.
+			assert_ok!(vested_transfer(VestingInfo {
+				locked: 400,
+				per_block: 10,
+				starting_block: 30
+			}));
+			let remove = |origin, schedule_index| {
+				AssetVesting::force_remove_vesting_schedule(
+					origin,
+					bob.clone().into(),
+					asset_id,
+					schedule_index,
+				)
+			};
+			assert_noop!(
+				remove(RuntimeHelper::origin_of(bob.clone()), 1),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(remove(RuntimeHelper::root_origin(), 1));
+			assert_eq!(Vesting::<Runtime>::get(&bob, asset_id).unwrap().into_inner(), vec![merged]);
+			assert_eq!(
+				AssetVesting::vesting_balances(&bob),
+				vec![AssetVestingBalance { asset: asset_id, locked: 1_100, claimable: 0 }]
+			);
+			assert_noop!(
+				remove(RuntimeHelper::root_origin(), 1),
+				Error::<Runtime>::ScheduleIndexOutOfBounds
+			);
+			assert_ok!(remove(RuntimeHelper::root_origin(), 0));
+			assert!(!Vesting::<Runtime>::contains_key(&bob, asset_id));
+			assert!(AssetVesting::vesting_balances(&bob).is_empty());
+			assert_ok!(Assets::transfer(
+				RuntimeHelper::origin_of(bob.clone()),
+				asset_id.into(),
+				alice.clone().into(),
+				2_000
+			));
+		})
+}
+
+#[test]
+fn locked_assets_cannot_be_withdrawn_over_xcm() {
+	use asset_hub_kusama_runtime::{
+		asset_locks::AssetHoldReason, asset_vesting::FreezeReason, xcm_config::FungiblesTransactor,
+		AssetLocks, RuntimeFreezeReason,
+	};
+	use frame_support::traits::tokens::{
+		fungibles::{Inspect, MutateFreeze, MutateHold},
+		Precision,
+	};
+	use xcm_executor::traits::TransactAsset;
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			let asset_id = 1;
+			assert_ok!(Assets::force_create(
+				RuntimeHelper::root_origin(),
+				asset_id.into(),
+				alice.clone().into(),
+				true,
+				10
+			));
+			assert_ok!(Assets::mint(
+				RuntimeHelper::origin_of(alice.clone()),
+				asset_id.into(),
+				alice.clone().into(),
+				1_000
+			));
+
+			let asset_location =
+				AssetIdForTrustBackedAssetsConvert::convert_back(&asset_id).unwrap();
+			let alice_location =
+				MultiLocation::new(0, X1(AccountId32 { network: None, id: ALICE }));
+			let withdraw = |amount: Balance| {
+				FungiblesTransactor::withdraw_asset(
+					&(asset_location, amount).into(),
+					&alice_location,
+					None,
+				)
+			};
+
+			let hold_reason = AssetHoldReason::Governance;
+			assert_ok!(AssetLocks::hold(asset_id, &hold_reason, &alice, 600));
+			assert_eq!(Assets::balance(asset_id, &alice), 400);
+			assert_eq!(AssetLocks::total_balance(asset_id, &alice), 1_000);
+
+			// Held assets cannot be withdrawn, and the account with them is kept alive...
+			assert!(withdraw(400).is_err());
+			assert_ok!(withdraw(390));
+			assert_eq!(Assets::balance(asset_id, &alice), 10);
+			// ...until they are released.
+			assert_ok!(AssetLocks::release(asset_id, &hold_reason, &alice, 600, Precision::Exact));
+			assert_eq!(Assets::balance(asset_id, &alice), 610);
+
+			// Frozen assets cannot be withdrawn either, until they are thawed.
+			let freeze_reason = RuntimeFreezeReason::AssetVesting(FreezeReason::Vesting);
+			assert_ok!(AssetLocks::set_freeze(asset_id, &freeze_reason, &alice, 500));
+			assert!(withdraw(101).is_err());
+			assert_ok!(withdraw(100));
+			assert_eq!(Assets::balance(asset_id, &alice), 510);
+			assert_ok!(AssetLocks::thaw(asset_id, &freeze_reason, &alice));
+			assert_ok!(withdraw(510));
+			assert_eq!(Assets::balance(asset_id, &alice), 0);
+		})
+}
+
+#[test]
+fn foreign_assets_of_other_consensus_systems_can_be_registered() {
+	use asset_hub_kusama_runtime::{
+		foreign_asset_registry::{
+			Error, RegisteredReserveAssets, RegisteredTeleportableAssets, Registrations,
+			TransferKind,
+		},
+		ForeignAssetRegistrationDeposit, ForeignAssetRegistry, RuntimeOrigin,
+	};
+	use frame_support::traits::{
+		fungible::Mutate, fungibles::metadata::Inspect, ContainsPair, ReservableCurrency,
+	};
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			Balances::mint_into(&alice, 10 * ForeignAssetRegistrationDeposit::get()).unwrap();
+			let ethereum =
+				MultiLocation::new(2, X1(GlobalConsensus(NetworkId::Ethereum { chain_id: 1 })));
+			let weth = MultiLocation::new(
+				2,
+				X2(
+					GlobalConsensus(NetworkId::Ethereum { chain_id: 1 }),
+					AccountKey20 { network: None, key: [7; 20] },
+				),
+			);
+			let register = |asset_id: MultiLocation, is_sufficient| {
+				ForeignAssetRegistry::register(
+					RuntimeOrigin::signed(alice.clone()),
+					Box::new(asset_id),
+					b"Wrapped Ether".to_vec(),
+					b"WETH".to_vec(),
+					18,
+					1_000,
+					is_sufficient,
+					TransferKind::Reserve,
+				)
+			};
+
+			// Assets of this consensus system are not registered here.
+			assert_noop!(
+				register(MultiLocation::new(1, X1(Parachain(2000))), false),
+				Error::<Runtime>::NotRemoteConsensus
+			);
+			assert_noop!(
+				register(
+					MultiLocation::new(2, X2(GlobalConsensus(NetworkId::Kusama), Parachain(2000))),
+					false
+				),
+				Error::<Runtime>::NotRemoteConsensus
+			);
+			// Only governance registers sufficient assets.
+			assert_noop!(register(weth, true), Error::<Runtime>::SufficientNeedsGovernance);
+			// Only governance registers teleported assets.
+			assert_noop!(
+				ForeignAssetRegistry::register(
+					RuntimeOrigin::signed(alice.clone()),
+					Box::new(weth),
+					b"Wrapped Ether".to_vec(),
+					b"WETH".to_vec(),
+					18,
+					1_000,
+					false,
+					TransferKind::Teleport,
+				),
+				Error::<Runtime>::TeleportNeedsGovernance
+			);
+			let register_with = |name: &[u8], symbol: &[u8], min_balance| {
+				ForeignAssetRegistry::register(
+					RuntimeOrigin::signed(alice.clone()),
+					Box::new(weth),
+					name.to_vec(),
+					symbol.to_vec(),
+					18,
+					min_balance,
+					false,
+					TransferKind::Reserve,
+				)
+			};
+			// Signed registrations keep the minimum balance low enough to deposit the asset.
+			assert_noop!(
+				register_with(b"Wrapped Ether", b"WETH", 10u128.pow(14) + 1),
+				Error::<Runtime>::MinBalanceTooHigh
+			);
+			// Names and symbols are printable.
+			assert_noop!(
+				register_with(b"Wrapped\nEther", b"WETH", 1_000),
+				Error::<Runtime>::UnprintableMetadata
+			);
+			assert_noop!(
+				register_with(b"Wrapped Ether", b"W ETH", 1_000),
+				Error::<Runtime>::UnprintableMetadata
+			);
+
+			assert_ok!(register(weth, false));
+			assert_eq!(Balances::reserved_balance(&alice), ForeignAssetRegistrationDeposit::get());
+			assert_eq!(ForeignAssets::symbol(weth), b"WETH".to_vec());
+			assert!(Registrations::<Runtime>::contains_key(weth));
+
+			// Only the consensus system of the asset is its reserve.
+			let asset: MultiAsset = (weth, 1_000_000).into();
+			assert!(RegisteredReserveAssets::<Runtime>::contains(&asset, &ethereum));
+			assert!(!RegisteredReserveAssets::<Runtime>::contains(&asset, &Parent.into()));
+			assert!(!RegisteredTeleportableAssets::<Runtime>::contains(&asset, &ethereum));
+
+			assert_ok!(ForeignAssetRegistry::set_transfer_kind(
+				RuntimeHelper::root_origin(),
+				Box::new(weth),
+				TransferKind::Teleport
+			));
+			assert!(RegisteredTeleportableAssets::<Runtime>::contains(&asset, &ethereum));
+			assert!(!RegisteredReserveAssets::<Runtime>::contains(&asset, &ethereum));
+		})
+}
+
+#[test]
+fn foreign_asset_registrations_can_be_ended() {
+	use asset_hub_kusama_runtime::{
+		foreign_asset_registry::{Error, Registrations, TransferKind},
+		ForeignAssetRegistrationDeposit, ForeignAssetRegistry, RuntimeOrigin,
+	};
+	use frame_support::traits::{
+		fungible::{Inspect, Mutate},
+		fungibles::Mutate as _,
+		ReservableCurrency,
+	};
+	use sp_runtime::DispatchError;
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			let admin = AccountId::from(SOME_ASSET_ADMIN);
+			let deposit = ForeignAssetRegistrationDeposit::get();
+			Balances::mint_into(&alice, 10 * deposit).unwrap();
+			let token = |key| {
+				MultiLocation::new(
+					2,
+					X2(
+						GlobalConsensus(NetworkId::Ethereum { chain_id: 1 }),
+						AccountKey20 { network: None, key: [key; 20] },
+					),
+				)
+			};
+			let (weth, wbtc) = (token(7), token(8));
+			let register = |asset_id: MultiLocation| {
+				ForeignAssetRegistry::register(
+					RuntimeOrigin::signed(alice.clone()),
+					Box::new(asset_id),
+					b"Token".to_vec(),
+					b"TKN".to_vec(),
+					18,
+					1_000,
+					false,
+					TransferKind::Reserve,
+				)
+			};
+			assert_ok!(register(weth));
+			assert_ok!(register(wbtc));
+			assert_eq!(Balances::reserved_balance(&alice), 2 * deposit);
+			assert_ok!(ForeignAssets::mint_into(wbtc, &admin, 1_000));
+
+			// Only the depositor ends a registration, and only while nobody holds the asset.
+			assert_noop!(
+				ForeignAssetRegistry::deregister(
+					RuntimeOrigin::signed(admin.clone()),
+					Box::new(weth)
+				),
+				Error::<Runtime>::NotDepositor
+			);
+			assert_noop!(
+				ForeignAssetRegistry::deregister(
+					RuntimeOrigin::signed(alice.clone()),
+					Box::new(wbtc)
+				),
+				Error::<Runtime>::InUse
+			);
+
+			// The deposit is returned and the asset destroyed.
+			assert_ok!(ForeignAssetRegistry::deregister(
+				RuntimeOrigin::signed(alice.clone()),
+				Box::new(weth)
+			));
+			assert_eq!(Balances::reserved_balance(&alice), deposit);
+			assert!(!Registrations::<Runtime>::contains_key(weth));
+			assert_noop!(
+				register(weth),
+				pallet_assets::Error::<Runtime, ForeignAssetsInstance>::InUse
+			);
+			let signed = RuntimeOrigin::signed(alice.clone());
+			assert_ok!(ForeignAssets::destroy_accounts(signed.clone(), weth));
+			assert_ok!(ForeignAssets::destroy_approvals(signed.clone(), weth));
+			assert_ok!(ForeignAssets::finish_destroy(signed, weth));
+			assert_ok!(register(weth));
+			assert_eq!(Balances::reserved_balance(&alice), 2 * deposit);
+
+			// Governance ends any registration, slashing the deposit if it asks to.
+			assert_noop!(
+				ForeignAssetRegistry::force_deregister(
+					RuntimeOrigin::signed(alice.clone()),
+					Box::new(wbtc),
+					true
+				),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(ForeignAssetRegistry::force_deregister(
+				RuntimeHelper::root_origin(),
+				Box::new(wbtc),
+				true
+			));
+			assert_eq!(Balances::reserved_balance(&alice), deposit);
+			assert_eq!(Balances::total_balance(&alice), 9 * deposit);
+			assert!(!Registrations::<Runtime>::contains_key(wbtc));
+		})
+}
+
+#[test]
+fn ump_delivery_fees_are_charged_except_for_governance_and_system_parachains() {
+	use asset_hub_kusama_runtime::{
+		xcm_config::{BaseDeliveryFee, UmpDeliveryPrice, WaivedLocations},
+		TransactionByteFee,
+	};
+	use cumulus_primitives_utility::PriceForParentDelivery;
+	use frame_support::traits::Contains;
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			// Without a reported queue state, the base price is charged.
+			let message = Xcm::<()>(vec![ClearOrigin]);
+			let price =
+				BaseDeliveryFee::get() + TransactionByteFee::get() * message.encoded_size() as u128;
+			assert_eq!(
+				UmpDeliveryPrice::price_for_parent_delivery(&message),
+				(KsmLocation::get(), price).into()
+			);
+
+			assert!(WaivedLocations::contains(&Here.into()));
+			assert!(WaivedLocations::contains(&Parent.into()));
+			assert!(WaivedLocations::contains(&MultiLocation::new(1, X1(Parachain(1002)))));
+			assert!(!WaivedLocations::contains(&MultiLocation::new(1, X1(Parachain(2000)))));
+			assert!(!WaivedLocations::contains(&MultiLocation::new(
+				0,
+				X1(AccountId32 { network: None, id: ALICE })
+			)));
+		})
+}
+
 asset_test_utils::include_teleports_for_native_asset_works!(
 	Runtime,
 	AllPalletsWithoutSystem,
@@ -632,3 +1822,36 @@ asset_test_utils::include_create_and_manage_foreign_assets_for_local_consensus_p
 		assert_eq!(ForeignAssets::asset_ids().collect::<Vec<_>>().len(), 1);
 	})
 );
+
+#[test]
+fn async_backing_config_matches_relay_chain() {
+	use cumulus_pallet_aura_ext::FixedVelocityConsensusHook;
+	use frame_support::traits::Get;
+	use kusama_runtime_constants::{
+		async_backing::ASYNC_BACKING_PARAMS, time::MILLISECS_PER_BLOCK,
+	};
+
+	trait HookParams {
+		const PARAMS: (u32, u32, u32);
+	}
+	impl<T, const R: u32, const V: u32, const C: u32> HookParams
+		for FixedVelocityConsensusHook<T, R, V, C>
+	{
+		const PARAMS: (u32, u32, u32) = (R, V, C);
+	}
+	type Hook = <Runtime as cumulus_pallet_parachain_system::Config>::ConsensusHook;
+	let (relay_slot_duration, velocity, capacity) = <Hook as HookParams>::PARAMS;
+
+	assert_eq!(relay_slot_duration as u64, MILLISECS_PER_BLOCK);
+	// Blocks are not authored faster than the consensus hook accepts them.
+	assert!(
+		2 * <Runtime as pallet_timestamp::Config>::MinimumPeriod::get() >=
+			(relay_slot_duration / velocity) as u64
+	);
+	// A block built on the oldest allowed relay parent is still not included while the blocks
+	// built on the following relay parents are authored.
+	assert!(capacity >= velocity * (ASYNC_BACKING_PARAMS.allowed_ancestry_len + 1));
+	// The Relay Chain does not back more than `max_candidate_depth + 1` candidates of a para
+	// building on top of each other.
+	assert!(capacity <= ASYNC_BACKING_PARAMS.max_candidate_depth + 1);
+}
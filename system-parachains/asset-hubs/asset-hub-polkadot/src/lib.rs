@@ -62,6 +62,11 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 mod weights;
 pub mod xcm_config;
 
+pub use asset_hub_common::{
+	asset_balances_api, asset_locks, asset_vesting, foreign_asset_registry, nft_locks,
+	nft_transactor,
+};
+
 use assets_common::{
 	foreign_creators::ForeignCreators, matching::FromSiblingParachain, MultiLocationForAssetId,
 };
@@ -86,11 +91,11 @@ use frame_support::{
 	dispatch::DispatchClass,
 	parameter_types,
 	traits::{
-		AsEnsureOriginWithArg, ConstBool, ConstU32, ConstU64, ConstU8, EitherOfDiverse,
-		InstanceFilter,
+		AsEnsureOriginWithArg, ConstBool, ConstU32, ConstU64, ConstU8, Contains, EitherOfDiverse,
+		EverythingBut, InstanceFilter, Locker,
 	},
 	weights::{ConstantMultiplier, Weight},
-	PalletId,
+	BoundedVec, PalletId,
 };
 use frame_system::{
 	limits::{BlockLength, BlockWeights},
@@ -100,12 +105,13 @@ use pallet_nfts::PalletFeatures;
 pub use parachains_common as common;
 use parachains_common::{
 	impls::{AssetsToBlockAuthor, DealWithFees},
-	polkadot::{consensus::*, currency::*, fee::WeightToFee},
+	polkadot::{currency::*, fee::WeightToFee},
 	AccountId, AssetHubPolkadotAuraId as AuraId, AssetIdForTrustBackedAssets, Balance, BlockNumber,
-	Hash, Header, Nonce, Signature, AVERAGE_ON_INITIALIZE_RATIO, DAYS, HOURS, MAXIMUM_BLOCK_WEIGHT,
+	Hash, Header, Nonce, Signature, AVERAGE_ON_INITIALIZE_RATIO, DAYS, HOURS,
 	NORMAL_DISPATCH_RATIO, SLOT_DURATION,
 };
 use sp_runtime::RuntimeDebug;
+use system_parachains_constants::{consensus::*, MAXIMUM_BLOCK_WEIGHT};
 use xcm_config::{
 	DotLocation, FellowshipLocation, ForeignAssetsConvertedConcreteId, GovernanceLocation,
 	TrustBackedAssetsConvertedConcreteId, XcmConfig, XcmOriginToTransactDispatchOrigin,
@@ -175,9 +181,30 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 0;
 }
 
+/// Burns of locked items of `Uniques` and `Nfts`.
+///
+/// Both pallets consult their `Locker` on transfers, but not on burns, so burns are filtered here.
+/// `Uniques` burns the items of the collections it destroys, so collections with locked items
+/// cannot be destroyed either; `Nfts` only destroys empty collections. Other pallets and the XCM
+/// transactors only get the NFT pallets through traits which cannot burn.
+pub struct BurnsOfLockedNfts;
+impl Contains<RuntimeCall> for BurnsOfLockedNfts {
+	fn contains(call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::Uniques(pallet_uniques::Call::burn { collection, item, .. }) =>
+				UniquesLocks::is_locked(*collection, *item),
+			RuntimeCall::Uniques(pallet_uniques::Call::destroy { collection, .. }) =>
+				UniquesLocks::has_locked_items(*collection),
+			RuntimeCall::Nfts(pallet_nfts::Call::burn { collection, item }) =>
+				NftsLocks::is_locked(*collection, *item),
+			_ => false,
+		}
+	}
+}
+
 // Configure FRAME pallets to include in runtime.
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = EverythingBut<BurnsOfLockedNfts>;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type AccountId = AccountId;
@@ -233,7 +260,9 @@ impl pallet_balances::Config for Runtime {
 	type ReserveIdentifier = [u8; 8];
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type FreezeIdentifier = ();
-	type MaxHolds = ConstU32<0>;
+	// We allow each account to have holds on it from:
+	//   - `NftFractionalization`: 1
+	type MaxHolds = ConstU32<1>;
 	type MaxFreezes = ConstU32<0>;
 }
 
@@ -284,7 +313,7 @@ impl pallet_assets::Config<TrustBackedAssetsInstance> for Runtime {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = AssetsStringLimit;
-	type Freezer = ();
+	type Freezer = AssetLocks;
 	type Extra = ();
 	type WeightInfo = weights::pallet_assets_local::WeightInfo<Runtime>;
 	type CallbackHandle = ();
@@ -308,7 +337,7 @@ parameter_types! {
 /// this type is used in proxy definitions. We assume that a foreign location would not want to set
 /// an individual, local account as a proxy for the issuance of their assets. This issuance should
 /// be managed by the foreign location's governance.
-pub type ForeignAssetsInstance = pallet_assets::Instance2;
+pub use asset_hub_common::ForeignAssetsInstance;
 impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Balance = Balance;
@@ -326,7 +355,7 @@ impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
 	type MetadataDepositPerByte = ForeignAssetsMetadataDepositPerByte;
 	type ApprovalDeposit = ForeignAssetsApprovalDeposit;
 	type StringLimit = ForeignAssetsAssetsStringLimit;
-	type Freezer = ();
+	type Freezer = ForeignAssetLocks;
 	type Extra = ();
 	type WeightInfo = weights::pallet_assets_foreign::WeightInfo<Runtime>;
 	type CallbackHandle = ();
@@ -336,6 +365,76 @@ impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
 	type BenchmarkHelper = xcm_config::XcmBenchmarkHelper;
 }
 
+parameter_types! {
+	// GeneralAdmin pluralistic body.
+	pub const GeneralAdminBodyId: BodyId = BodyId::Administration;
+	pub const ForeignAssetRegistryPalletId: PalletId = PalletId(*b"py/frgnr");
+	pub const ForeignAssetRegistrationDeposit: Balance = 10 * ForeignAssetsAssetDeposit::get();
+}
+
+/// We allow root and the `GeneralAdmin` to register foreign assets without a deposit, to register
+/// teleported and sufficient ones and to end any registration.
+pub type ForeignAssetRegisterOrigin = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	EnsureXcm<IsVoiceOfBody<GovernanceLocation, GeneralAdminBodyId>>,
+>;
+
+impl foreign_asset_registry::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RegisterOrigin = ForeignAssetRegisterOrigin;
+	type RegistrationDeposit = ForeignAssetRegistrationDeposit;
+	// Slashed registration deposits are burnt.
+	type Slashed = ();
+	type MaxDecimals = ConstU8<18>;
+	// Signed registrations keep the minimum balance at or below a ten-thousandth of a unit.
+	type MinBalanceMagnitude = ConstU8<4>;
+	type UniversalLocation = xcm_config::UniversalLocation;
+	type PalletId = ForeignAssetRegistryPalletId;
+	type WeightInfo = foreign_asset_registry::DbWeightInfo<Runtime>;
+}
+
+parameter_types! {
+	// We allow each account to have up to 4 freezes and 4 holds on each of its assets.
+	pub const AssetLocksMaxFreezes: u32 = 4;
+	pub const AssetLocksMaxHolds: u32 = 4;
+}
+
+impl asset_locks::Config<TrustBackedAssetsInstance> for Runtime {
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type HoldReason = asset_locks::AssetHoldReason;
+	type MaxFreezes = AssetLocksMaxFreezes;
+	type MaxHolds = AssetLocksMaxHolds;
+}
+
+impl asset_locks::Config<ForeignAssetsInstance> for Runtime {
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type HoldReason = asset_locks::AssetHoldReason;
+	type MaxFreezes = AssetLocksMaxFreezes;
+	type MaxHolds = AssetLocksMaxHolds;
+}
+
+parameter_types! {
+	pub const MaxAssetVestingSchedules: u32 = 28;
+	// A vested transfer moves at least a hundred times the minimum balance of the asset.
+	pub const MinVestedAssetTransferMultiple: u32 = 100;
+}
+
+impl asset_vesting::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = AssetIdForTrustBackedAssets;
+	type Balance = Balance;
+	type Assets = Assets;
+	type Freezer = AssetLocks;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type BlockNumberToBalance = ConvertInto;
+	type ForceOrigin = AssetsForceOrigin;
+	type MaxVestingSchedules = MaxAssetVestingSchedules;
+	type MinVestedTransferMultiple = MinVestedAssetTransferMultiple;
+	type WeightInfo = asset_vesting::DbWeightInfo<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
 parameter_types! {
 	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
 	pub const DepositBase: Balance = deposit(1, 88);
@@ -417,6 +516,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 				c,
 				RuntimeCall::Balances { .. } |
 					RuntimeCall::Assets { .. } |
+					RuntimeCall::AssetVesting { .. } |
+					RuntimeCall::NftFractionalization { .. } |
 					RuntimeCall::Nfts { .. } |
 					RuntimeCall::Uniques { .. }
 			),
@@ -430,8 +531,10 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 				matches!(
 					c,
 					RuntimeCall::Assets { .. } |
+						RuntimeCall::AssetVesting { .. } |
 						RuntimeCall::Utility { .. } |
 						RuntimeCall::Multisig { .. } |
+						RuntimeCall::NftFractionalization { .. } |
 						RuntimeCall::Nfts { .. } | RuntimeCall::Uniques { .. }
 				)
 			},
@@ -686,7 +789,37 @@ impl pallet_uniques::Config for Runtime {
 	#[cfg(feature = "runtime-benchmarks")]
 	type Helper = ();
 	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
-	type Locker = ();
+	type Locker = UniquesLocks;
+}
+
+parameter_types! {
+	pub const NftFractionalizationPalletId: PalletId = PalletId(*b"fraction");
+	pub NewAssetSymbol: BoundedVec<u8, AssetsStringLimit> = (*b"FRAC").to_vec().try_into().unwrap();
+	pub NewAssetName: BoundedVec<u8, AssetsStringLimit> = (*b"Frac").to_vec().try_into().unwrap();
+}
+
+impl pallet_nft_fractionalization::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Deposit = AssetDeposit;
+	type Currency = Balances;
+	type NewAssetSymbol = NewAssetSymbol;
+	type NewAssetName = NewAssetName;
+	type StringLimit = AssetsStringLimit;
+	type NftCollectionId = <Self as pallet_nfts::Config>::CollectionId;
+	type NftId = <Self as pallet_nfts::Config>::ItemId;
+	type AssetBalance = <Self as pallet_balances::Config>::Balance;
+	type AssetId = <Self as pallet_assets::Config<TrustBackedAssetsInstance>>::AssetId;
+	type Assets = Assets;
+	// Fractionalized items are locked, so they cannot be burnt.
+	type Nfts = nft_locks::LockOnDisabledTransfer<
+		Nfts,
+		nft_locks::LockFor<Runtime, NftsLocksInstance, FractionalizedNftLockReason>,
+	>;
+	type PalletId = NftFractionalizationPalletId;
+	type WeightInfo = weights::pallet_nft_fractionalization::WeightInfo<Runtime>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 parameter_types! {
@@ -707,7 +840,7 @@ impl pallet_nfts::Config for Runtime {
 	type Currency = Balances;
 	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
 	type ForceOrigin = AssetsForceOrigin;
-	type Locker = ();
+	type Locker = NftsLocks;
 	type CollectionDeposit = NftsCollectionDeposit;
 	type ItemDeposit = NftsItemDeposit;
 	type MetadataDepositBase = NftsMetadataDepositBase;
@@ -729,6 +862,32 @@ impl pallet_nfts::Config for Runtime {
 	type Helper = ();
 }
 
+parameter_types! {
+	pub const NftLocksMaxLocks: u32 = 4;
+	pub const FractionalizedNftLockReason: nft_locks::NftLockReason =
+		nft_locks::NftLockReason::Fractionalized;
+	pub const XcmReserveNftLockReason: nft_locks::NftLockReason =
+		nft_locks::NftLockReason::XcmReserve;
+}
+
+pub type UniquesLocksInstance = frame_support::instances::Instance1;
+impl nft_locks::Config<UniquesLocksInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type CollectionId = <Self as pallet_uniques::Config>::CollectionId;
+	type ItemId = <Self as pallet_uniques::Config>::ItemId;
+	type LockReason = nft_locks::NftLockReason;
+	type MaxLocks = NftLocksMaxLocks;
+}
+
+pub type NftsLocksInstance = frame_support::instances::Instance2;
+impl nft_locks::Config<NftsLocksInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type CollectionId = <Self as pallet_nfts::Config>::CollectionId;
+	type ItemId = <Self as pallet_nfts::Config>::ItemId;
+	type LockReason = nft_locks::NftLockReason;
+	type MaxLocks = NftLocksMaxLocks;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime
@@ -770,6 +929,14 @@ construct_runtime!(
 		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>} = 51,
 		Nfts: pallet_nfts::{Pallet, Call, Storage, Event<T>} = 52,
 		ForeignAssets: pallet_assets::<Instance2>::{Pallet, Call, Storage, Event<T>} = 53,
+		NftFractionalization: pallet_nft_fractionalization::{Pallet, Call, Storage, Event<T>, HoldReason} = 54,
+
+		ForeignAssetRegistry: foreign_asset_registry::{Pallet, Call, Storage, Event<T>} = 57,
+		AssetLocks: asset_locks::<Instance1>::{Pallet, Storage} = 58,
+		ForeignAssetLocks: asset_locks::<Instance2>::{Pallet, Storage} = 59,
+		AssetVesting: asset_vesting::{Pallet, Call, Storage, Event<T>, FreezeReason} = 60,
+		UniquesLocks: nft_locks::<Instance1>::{Pallet, Storage, Event<T>} = 61,
+		NftsLocks: nft_locks::<Instance2>::{Pallet, Storage, Event<T>} = 62,
 	}
 );
 
@@ -859,7 +1026,10 @@ mod benches {
 		[pallet_assets, Local]
 		[pallet_assets, Foreign]
 		[pallet_balances, Balances]
+		[foreign_asset_registry, ForeignAssetRegistry]
+		[asset_vesting, AssetVesting]
 		[pallet_multisig, Multisig]
+		[pallet_nft_fractionalization, NftFractionalization]
 		[pallet_nfts, Nfts]
 		[pallet_proxy, Proxy]
 		[pallet_session, SessionBench::<Runtime>]
@@ -873,6 +1043,7 @@ mod benches {
 		// NOTE: Make sure you point to the individual modules below.
 		[pallet_xcm_benchmarks::fungible, XcmBalances]
 		[pallet_xcm_benchmarks::generic, XcmGeneric]
+		[nft_transactor::benchmarking, XcmNonFungibles]
 	);
 }
 
@@ -1048,6 +1219,84 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl asset_balances_api::AssetBalancesApi<Block, AccountId> for Runtime {
+		fn query_account_assets(
+			account: AccountId,
+		) -> Result<
+			Vec<asset_balances_api::AssetBalance>,
+			assets_common::runtime_api::FungiblesAccessError,
+		> {
+			use asset_balances_api::{pallet_assets_balances, AssetBalance};
+			use assets_common::AssetIdForTrustBackedAssetsConvert;
+			use frame_support::traits::{Everything, Nothing};
+			use xcm_config::{TeleportableForeignAssets, TrustBackedAssetsPalletLocation};
+			use xcm_executor::traits::Identity;
+
+			let native = System::account(&account).data;
+			Ok([
+				// collect pallet_balance
+				if native.free > 0 || native.reserved > 0 {
+					vec![AssetBalance {
+						asset: DotLocation::get().into(),
+						free: native.free,
+						held: native.reserved,
+						frozen: native.frozen,
+						existential_deposit: ExistentialDeposit::get(),
+						is_sufficient: true,
+						decimals: 10,
+						symbol: b"DOT".to_vec(),
+						// DOT is teleported to the Relay Chain and the system parachains, and
+						// reserve-transferred to other parachains.
+						teleportable: true,
+						reserve_transferable: true,
+					}]
+				} else {
+					vec![]
+				},
+				// collect pallet_assets (TrustBackedAssets), of which this chain is the reserve
+				pallet_assets_balances::<
+					Runtime,
+					TrustBackedAssetsInstance,
+					AssetIdForTrustBackedAssetsConvert<TrustBackedAssetsPalletLocation>,
+					Nothing,
+					Everything,
+				>(&account)?,
+				// collect pallet_assets (ForeignAssets), of which this chain is not the reserve
+				pallet_assets_balances::<
+					Runtime,
+					ForeignAssetsInstance,
+					Identity,
+					TeleportableForeignAssets,
+					Nothing,
+				>(&account)?,
+			].concat())
+		}
+	}
+
+	impl asset_vesting::AssetVestingApi<
+		Block,
+		AccountId,
+		AssetIdForTrustBackedAssets,
+		Balance,
+	> for Runtime
+	{
+		fn vesting_balances(
+			who: AccountId,
+		) -> Vec<asset_vesting::AssetVestingBalance<AssetIdForTrustBackedAssets, Balance>> {
+			AssetVesting::vesting_balances(&who)
+		}
+	}
+
+	impl nft_locks::NftLocksApi<Block, u32, u32, nft_locks::NftLockReason> for Runtime {
+		fn uniques_locks(collection: u32, item: u32) -> Vec<nft_locks::NftLockReason> {
+			UniquesLocks::locks(collection, item)
+		}
+
+		fn nfts_locks(collection: u32, item: u32) -> Vec<nft_locks::NftLockReason> {
+			NftsLocks::locks(collection, item)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -1089,6 +1338,7 @@ impl_runtime_apis! {
 			// are referenced in that call.
 			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
 			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+			type XcmNonFungibles = nft_transactor::benchmarking::Pallet::<Runtime>;
 
 			// Benchmark files generated for `Assets/ForeignAssets` instances are by default
 			// `pallet_assets_assets.rs / pallet_assets_foreign_assets`, which is not really nice,
@@ -1186,6 +1436,32 @@ impl_runtime_apis! {
 				}
 			}
 
+			impl nft_transactor::benchmarking::Config for Runtime {
+				type TransactAsset = xcm_config::NftsTransactor;
+
+				fn escrowed_item() -> Result<MultiAsset, BenchmarkError> {
+					use frame_support::traits::{tokens::nonfungibles_v2::{Create, Mutate}, Currency};
+					use pallet_nfts::{CollectionConfig, CollectionSettings, ItemConfig, MintSettings};
+
+					let escrow = xcm_config::NftEscrowAccount::get();
+					Balances::make_free_balance_be(&escrow, 1_000 * UNITS);
+					let config = CollectionConfig {
+						settings: CollectionSettings::all_enabled(),
+						max_supply: None,
+						mint_settings: MintSettings::default(),
+					};
+					let collection = <Nfts as Create<_, _>>::create_collection(&escrow, &escrow, &config)
+						.map_err(|_| BenchmarkError::Stop("the collection is created"))?;
+					let item = 0;
+					<Nfts as Mutate<_, _>>::mint_into(&collection, &item, &escrow, &ItemConfig::default(), true)
+						.map_err(|_| BenchmarkError::Stop("the item is minted"))?;
+					let location = xcm_config::NftsPalletLocation::get()
+						.pushed_with_interior(GeneralIndex(collection.into()))
+						.map_err(|_| BenchmarkError::Stop("the collection has a location"))?;
+					Ok((location, Index(item.into())).into())
+				}
+			}
+
 			impl pallet_xcm_benchmarks::generic::Config for Runtime {
 				type RuntimeCall = RuntimeCall;
 
@@ -1232,6 +1508,7 @@ impl_runtime_apis! {
 
 			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
 			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+			type XcmNonFungibles = nft_transactor::benchmarking::Pallet::<Runtime>;
 
 			type Local = pallet_assets::Pallet::<Runtime, TrustBackedAssetsInstance>;
 			type Foreign = pallet_assets::Pallet::<Runtime, ForeignAssetsInstance>;
@@ -23,6 +23,7 @@ pub mod pallet_assets_local;
 pub mod pallet_balances;
 pub mod pallet_collator_selection;
 pub mod pallet_multisig;
+pub mod pallet_nft_fractionalization;
 pub mod pallet_nfts;
 pub mod pallet_proxy;
 pub mod pallet_session;
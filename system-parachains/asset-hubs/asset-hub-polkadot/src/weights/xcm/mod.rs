@@ -17,15 +17,20 @@
 mod pallet_xcm_benchmarks_fungible;
 mod pallet_xcm_benchmarks_generic;
 
-use crate::{xcm_config::MaxAssetsIntoHolding, Runtime};
+use crate::{nft_transactor::WeightInfo as _, xcm_config::MaxAssetsIntoHolding, Runtime};
 use frame_support::weights::Weight;
 use pallet_xcm_benchmarks_fungible::WeightInfo as XcmFungibleWeight;
 use pallet_xcm_benchmarks_generic::WeightInfo as XcmGeneric;
 use sp_std::prelude::*;
 use xcm::{latest::prelude::*, DoubleEncoded};
 
+/// The weights of the `Uniques` and `Nfts` transactors.
+type XcmNonFungibleWeight = crate::nft_transactor::DbWeightInfo<Runtime>;
+
 trait WeighMultiAssets {
 	fn weigh_multi_assets(&self, weight: Weight) -> Weight;
+	/// Weigh the fungible assets with `weight` and the non-fungible ones with `item_weight`.
+	fn weigh_multi_assets_and_items(&self, weight: Weight, item_weight: Weight) -> Weight;
 }
 
 const MAX_ASSETS: u64 = 100;
@@ -48,25 +53,48 @@ impl WeighMultiAssets for MultiAssetFilter {
 			},
 		}
 	}
+
+	fn weigh_multi_assets_and_items(&self, weight: Weight, item_weight: Weight) -> Weight {
+		match self {
+			Self::Definite(assets) => assets.weigh_multi_assets_and_items(weight, item_weight),
+			Self::Wild(AllOf { fun: WildFungibility::Fungible, .. }) =>
+				self.weigh_multi_assets(weight),
+			Self::Wild(AllOf { fun: WildFungibility::NonFungible, .. }) =>
+				self.weigh_multi_assets(item_weight),
+			Self::Wild(_) => self.weigh_multi_assets(weight.max(item_weight)),
+		}
+	}
 }
 
 impl WeighMultiAssets for MultiAssets {
 	fn weigh_multi_assets(&self, weight: Weight) -> Weight {
 		weight.saturating_mul(self.inner().iter().count() as u64)
 	}
+
+	fn weigh_multi_assets_and_items(&self, weight: Weight, item_weight: Weight) -> Weight {
+		self.inner().iter().fold(Weight::zero(), |total, asset| match asset.fun {
+			Fungible(_) => total.saturating_add(weight),
+			NonFungible(_) => total.saturating_add(item_weight),
+		})
+	}
 }
 
 pub struct AssetHubPolkadotXcmWeight<Call>(core::marker::PhantomData<Call>);
 impl<Call> XcmWeightInfo<Call> for AssetHubPolkadotXcmWeight<Call> {
 	fn withdraw_asset(assets: &MultiAssets) -> Weight {
-		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::withdraw_asset())
-	}
-	// Currently there is no trusted reserve (`IsReserve = ()`),
-	// but we need this hack for `pallet_xcm::reserve_transfer_assets`
+		assets.weigh_multi_assets_and_items(
+			XcmFungibleWeight::<Runtime>::withdraw_asset(),
+			XcmNonFungibleWeight::withdraw_item(),
+		)
+	}
+	// The only trusted reserves are the consensus systems of the assets registered as
+	// reserve-backed in `ForeignAssetRegistry` (`IsReserve = RegisteredReserveAssets`). The XCM
+	// benchmarks have no `TrustedReserve` yet, so they report the weight of an unsupported
+	// instruction, which we cap; the cap is also needed for `pallet_xcm::reserve_transfer_assets`
 	// (TODO) fix https://github.com/paritytech/polkadot/pull/7424
 	// (TODO) fix https://github.com/paritytech/polkadot/pull/7546
 	fn reserve_asset_deposited(_assets: &MultiAssets) -> Weight {
-		// TODO: if we change `IsReserve = ...` then use this line...
+		// TODO: once the benchmarks have a `TrustedReserve`, weigh the assets with the result,
 		// TODO: or if remote weight estimation is fixed, then remove
 		// TODO: hardcoded - fix https://github.com/paritytech/cumulus/issues/1974
 		let hardcoded_weight = Weight::from_parts(1_000_000_000_u64, 0);
@@ -84,14 +112,20 @@ impl<Call> XcmWeightInfo<Call> for AssetHubPolkadotXcmWeight<Call> {
 		XcmGeneric::<Runtime>::query_response()
 	}
 	fn transfer_asset(assets: &MultiAssets, _dest: &MultiLocation) -> Weight {
-		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::transfer_asset())
+		assets.weigh_multi_assets_and_items(
+			XcmFungibleWeight::<Runtime>::transfer_asset(),
+			XcmNonFungibleWeight::transfer_item(),
+		)
 	}
 	fn transfer_reserve_asset(
 		assets: &MultiAssets,
 		_dest: &MultiLocation,
 		_xcm: &Xcm<()>,
 	) -> Weight {
-		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::transfer_reserve_asset())
+		assets.weigh_multi_assets_and_items(
+			XcmFungibleWeight::<Runtime>::transfer_reserve_asset(),
+			XcmNonFungibleWeight::transfer_item(),
+		)
 	}
 	fn transact(
 		_origin_type: &OriginKind,
@@ -130,14 +164,20 @@ impl<Call> XcmWeightInfo<Call> for AssetHubPolkadotXcmWeight<Call> {
 		// Hardcoded till the XCM pallet is fixed
 		let hardcoded_weight = Weight::from_parts(1_000_000_000_u64, 0);
 		let weight = assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::deposit_asset());
-		hardcoded_weight.min(weight)
+		// The cap is for the fungible assets only, the items are weighed on top of it.
+		let item_weight = assets
+			.weigh_multi_assets_and_items(Weight::zero(), XcmNonFungibleWeight::deposit_item());
+		hardcoded_weight.min(weight).saturating_add(item_weight)
 	}
 	fn deposit_reserve_asset(
 		assets: &MultiAssetFilter,
 		_dest: &MultiLocation,
 		_xcm: &Xcm<()>,
 	) -> Weight {
-		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::deposit_reserve_asset())
+		assets.weigh_multi_assets_and_items(
+			XcmFungibleWeight::<Runtime>::deposit_reserve_asset(),
+			XcmNonFungibleWeight::deposit_item(),
+		)
 	}
 	fn exchange_asset(_give: &MultiAssetFilter, _receive: &MultiAssets, _maximal: &bool) -> Weight {
 		Weight::MAX
@@ -14,9 +14,17 @@
 // limitations under the License.
 
 use super::{
-	AccountId, AllPalletsWithSystem, Assets, Authorship, Balance, Balances, ForeignAssets,
-	ParachainInfo, ParachainSystem, PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin,
-	TrustBackedAssetsInstance, WeightToFee, XcmpQueue,
+	AccountId, AllPalletsWithSystem, Assets, Authorship, Balance, Balances, ForeignAssets, Nfts,
+	NftsLocksInstance, ParachainInfo, ParachainSystem, PolkadotXcm, Runtime, RuntimeCall,
+	RuntimeEvent, RuntimeOrigin, TransactionByteFee, TrustBackedAssetsInstance, Uniques,
+	UniquesLocksInstance, WeightToFee, XcmReserveNftLockReason, XcmpQueue,
+};
+use crate::{
+	foreign_asset_registry::{
+		RegisteredReserveAssets, RegisteredTeleportableAssets, Registrations, TransferKind,
+	},
+	nft_locks::LockFor,
+	nft_transactor::{NonFungiblesEscrowAdapter, NonFungiblesV2EscrowAdapter},
 };
 use assets_common::matching::{
 	FromSiblingParachain, IsForeignConcreteAsset, StartsWith, StartsWithExplicitGlobalConsensus,
@@ -24,24 +32,30 @@ use assets_common::matching::{
 use frame_support::{
 	match_types, parameter_types,
 	traits::{ConstU32, Contains, Everything, Nothing, PalletInfoAccess},
+	PalletId,
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
 use parachains_common::{impls::ToStakingPot, xcm_config::AssetFeeAsExistentialDepositMultiplier};
 use polkadot_parachain_primitives::primitives::Sibling;
-use sp_runtime::traits::ConvertInto;
+use sp_runtime::traits::{AccountIdConversion, ConvertInto};
+pub use system_parachains_constants::xcm_config::{polkadot::BaseDeliveryFee, WaivedLocations};
 use xcm::latest::prelude::*;
 use xcm_builder::{
 	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
-	AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom, CurrencyAdapter,
-	DenyReserveTransferToRelayChain, DenyThenTry, DescribeFamily, DescribePalletTerminal,
-	EnsureXcmOrigin, FungiblesAdapter, HashedDescription, IsConcrete, LocalMint, NativeAsset,
-	NoChecking, ParentAsSuperuser, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
-	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
-	SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId, UsingComponents,
-	WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
+	AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom, AsPrefixedGeneralIndex,
+	CurrencyAdapter, DenyReserveTransferToRelayChain, DenyThenTry, DescribeFamily,
+	DescribePalletTerminal, EnsureXcmOrigin, FungiblesAdapter, HashedDescription, IsConcrete,
+	LocalMint, MatchedConvertedConcreteId, NativeAsset, NoChecking, ParentAsSuperuser,
+	ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
+	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
+	TrailingSetTopicAsId, UsingComponents, WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
+	XcmFeeManagerFromComponents,
+};
+use xcm_executor::{
+	traits::{JustTry, WithOriginFilter},
+	XcmExecutor,
 };
-use xcm_executor::{traits::WithOriginFilter, XcmExecutor};
 
 parameter_types! {
 	pub const DotLocation: MultiLocation = MultiLocation::parent();
@@ -52,7 +66,14 @@ parameter_types! {
 	pub UniversalLocationNetworkId: NetworkId = UniversalLocation::get().global_consensus().unwrap();
 	pub TrustBackedAssetsPalletLocation: MultiLocation =
 		PalletInstance(<Assets as PalletInfoAccess>::index() as u8).into();
+	pub UniquesPalletLocation: MultiLocation =
+		PalletInstance(<Uniques as PalletInfoAccess>::index() as u8).into();
+	pub NftsPalletLocation: MultiLocation =
+		PalletInstance(<Nfts as PalletInfoAccess>::index() as u8).into();
 	pub CheckingAccount: AccountId = PolkadotXcm::check_account();
+	pub const NftEscrowPalletId: PalletId = PalletId(*b"py/nftes");
+	/// The account holding the items of `Uniques` and `Nfts` withdrawn over XCM.
+	pub NftEscrowAccount: AccountId = NftEscrowPalletId::get().into_account_truncating();
 	pub FellowshipLocation: MultiLocation = MultiLocation::new(1, Parachain(1001));
 	pub const GovernanceLocation: MultiLocation = MultiLocation::parent();
 }
@@ -138,8 +159,81 @@ pub type ForeignFungiblesTransactor = FungiblesAdapter<
 	CheckingAccount,
 >;
 
+/// Collection/item converter for `Uniques`.
+pub type UniquesConvertedConcreteId = MatchedConvertedConcreteId<
+	u32,
+	u32,
+	StartsWith<UniquesPalletLocation>,
+	AsPrefixedGeneralIndex<UniquesPalletLocation, u32, JustTry>,
+	JustTry,
+>;
+
+/// Means for transacting the items of `Uniques` on this chain.
+pub type UniquesTransactor = NonFungiblesEscrowAdapter<
+	// Use this nonfungibles implementation:
+	Uniques,
+	// Use this collection and item when it is a non-fungible asset matching the given location:
+	UniquesConvertedConcreteId,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// The account holding the withdrawn items.
+	NftEscrowAccount,
+	// The lock of the items held by other locations.
+	LockFor<Runtime, UniquesLocksInstance, XcmReserveNftLockReason>,
+>;
+
+/// Collection/item converter for `Nfts`.
+pub type NftsConvertedConcreteId = MatchedConvertedConcreteId<
+	u32,
+	u32,
+	StartsWith<NftsPalletLocation>,
+	AsPrefixedGeneralIndex<NftsPalletLocation, u32, JustTry>,
+	JustTry,
+>;
+
+/// Means for transacting the items of `Nfts` on this chain.
+pub type NftsTransactor = NonFungiblesV2EscrowAdapter<
+	// Use this nonfungibles implementation:
+	Nfts,
+	// Use this collection and item when it is a non-fungible asset matching the given location:
+	NftsConvertedConcreteId,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// The account holding the withdrawn items.
+	NftEscrowAccount,
+	// The lock of the items held by other locations.
+	LockFor<Runtime, NftsLocksInstance, XcmReserveNftLockReason>,
+>;
+
 /// Means for transacting assets on this chain.
-pub type AssetTransactors = (CurrencyTransactor, FungiblesTransactor, ForeignFungiblesTransactor);
+pub type AssetTransactors = (
+	CurrencyTransactor,
+	FungiblesTransactor,
+	ForeignFungiblesTransactor,
+	UniquesTransactor,
+	NftsTransactor,
+);
+
+/// Foreign assets which `IsTeleporter` accepts from their origin, so which can be teleported back
+/// to it: the assets of sibling parachains and the assets registered as teleportable in
+/// `ForeignAssetRegistry`.
+pub struct TeleportableForeignAssets;
+impl Contains<MultiLocation> for TeleportableForeignAssets {
+	fn contains(location: &MultiLocation) -> bool {
+		let from_sibling = location.parents == 1 &&
+			matches!(
+				location.first_interior(),
+				Some(Parachain(id)) if *id != u32::from(ParachainInfo::parachain_id())
+			);
+		from_sibling ||
+			Registrations::<Runtime>::get(location)
+				.map_or(false, |registration| registration.transfer == TransferKind::Teleport)
+	}
+}
 
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
@@ -227,6 +321,8 @@ impl Contains<RuntimeCall> for SafeCallFilter {
 				) | RuntimeCall::Session(pallet_session::Call::purge_keys { .. }) |
 				RuntimeCall::XcmpQueue(..) |
 				RuntimeCall::DmpQueue(..) |
+				RuntimeCall::ForeignAssetRegistry(..) |
+				RuntimeCall::AssetVesting(..) |
 				RuntimeCall::Assets(
 					pallet_assets::Call::create { .. } |
 						pallet_assets::Call::force_create { .. } |
@@ -285,6 +381,9 @@ impl Contains<RuntimeCall> for SafeCallFilter {
 					pallet_assets::Call::transfer_approved { .. } |
 					pallet_assets::Call::touch { .. } |
 					pallet_assets::Call::refund { .. },
+			) | RuntimeCall::NftFractionalization(
+				pallet_nft_fractionalization::Call::fractionalize { .. } |
+					pallet_nft_fractionalization::Call::unify { .. },
 			) | RuntimeCall::Nfts(
 				pallet_nfts::Call::create { .. } |
 					pallet_nfts::Call::force_create { .. } |
@@ -396,16 +495,19 @@ impl xcm_executor::Config for XcmConfig {
 	type XcmSender = XcmRouter;
 	type AssetTransactor = AssetTransactors;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
-	// Asset Hub Polkadot does not recognize a reserve location for any asset. This does not prevent
-	// Asset Hub acting _as_ a reserve location for DOT and assets created under `pallet-assets`.
-	// For DOT, users must use teleport where allowed (e.g. with the Relay Chain).
-	type IsReserve = ();
+	// Asset Hub Polkadot recognizes other consensus systems as reserve locations only for the
+	// assets registered as reserve-backed in `ForeignAssetRegistry`. This does not prevent Asset
+	// Hub acting _as_ a reserve location for DOT and assets created under `pallet-assets`. For DOT,
+	// users must use teleport where allowed (e.g. with the Relay Chain).
+	type IsReserve = RegisteredReserveAssets<Runtime>;
 	// We allow:
 	// - teleportation of DOT
 	// - teleportation of sibling parachain's assets (as ForeignCreators)
+	// - teleportation of assets registered as teleportable in `ForeignAssetRegistry`
 	type IsTeleporter = (
 		NativeAsset,
 		IsForeignConcreteAsset<FromSiblingParachain<parachain_info::Pallet<Runtime>>>,
+		RegisteredTeleportableAssets<Runtime>,
 	);
 	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
@@ -436,7 +538,8 @@ impl xcm_executor::Config for XcmConfig {
 	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
 	type AssetLocker = ();
 	type AssetExchanger = ();
-	type FeeManager = ();
+	// Delivery fees are burnt, except for governance and the system parachains.
+	type FeeManager = XcmFeeManagerFromComponents<WaivedLocations, ()>;
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
 	type CallDispatcher = WithOriginFilter<SafeCallFilter>;
@@ -448,11 +551,24 @@ impl xcm_executor::Config for XcmConfig {
 /// Forms the basis for local origins sending/executing XCMs.
 pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
 
+parameter_types! {
+	/// The asset ID for the asset that we use to pay for message delivery fees.
+	pub FeeAssetId: AssetId = Concrete(DotLocation::get());
+}
+
+/// The price of delivering a message to the Relay Chain.
+pub type UmpDeliveryPrice = system_parachains_constants::xcm_config::UmpDeliveryPrice<
+	Runtime,
+	FeeAssetId,
+	BaseDeliveryFee,
+	TransactionByteFee,
+>;
+
 /// The means for routing XCM messages which are not for local execution into the right message
 /// queues.
 pub type XcmRouter = WithUniqueTopic<(
 	// Two routers - use UMP to communicate with the relay chain:
-	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, PolkadotXcm, ()>,
+	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, PolkadotXcm, UmpDeliveryPrice>,
 	// ..and XCMP to communicate with the sibling chains.
 	XcmpQueue,
 )>;
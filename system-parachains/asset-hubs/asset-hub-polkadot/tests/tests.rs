@@ -408,7 +408,14 @@ fn test_asset_xcm_trader_not_possible_for_non_sufficient_assets() {
 
 #[test]
 fn test_assets_balances_api_works() {
+	use asset_hub_polkadot_runtime::{
+		asset_balances_api::{runtime_decl_for_asset_balances_api::AssetBalancesApi, AssetBalance},
+		asset_locks::AssetHoldReason,
+		asset_vesting::FreezeReason,
+		AssetLocks, RuntimeFreezeReason,
+	};
 	use assets_common::runtime_api::runtime_decl_for_fungibles_api::FungiblesApi;
+	use frame_support::traits::tokens::fungibles::{MutateFreeze, MutateHold};
 
 	ExtBuilder::<Runtime>::default()
 		.with_collators(vec![AccountId::from(ALICE)])
@@ -513,9 +520,178 @@ fn test_assets_balances_api_works() {
 				6 * foreign_asset_minimum_asset_balance
 			)
 				.into())));
+
+			// Hold and freeze some of the trusted asset, and give it metadata
+			assert_ok!(Assets::mint(
+				RuntimeHelper::origin_of(AccountId::from(ALICE)),
+				local_asset_id.into(),
+				AccountId::from(ALICE).into(),
+				2 * minimum_asset_balance
+			));
+			assert_ok!(AssetLocks::hold(
+				local_asset_id,
+				&AssetHoldReason::Governance,
+				&AccountId::from(ALICE),
+				minimum_asset_balance
+			));
+			assert_ok!(AssetLocks::set_freeze(
+				local_asset_id,
+				&RuntimeFreezeReason::AssetVesting(FreezeReason::Vesting),
+				&AccountId::from(ALICE),
+				minimum_asset_balance
+			));
+			assert_ok!(Assets::force_set_metadata(
+				RuntimeHelper::root_origin(),
+				local_asset_id.into(),
+				b"Local".to_vec(),
+				b"LOC".to_vec(),
+				6,
+				false
+			));
+
+			let assets = Runtime::query_account_assets(AccountId::from(ALICE)).unwrap();
+			assert_eq!(assets.len(), 3);
+
+			// check currency
+			assert!(assets.contains(&AssetBalance {
+				asset: DotLocation::get().into(),
+				free: some_currency,
+				held: 0,
+				frozen: 0,
+				existential_deposit: ExistentialDeposit::get(),
+				is_sufficient: true,
+				decimals: 10,
+				symbol: b"DOT".to_vec(),
+				teleportable: true,
+				reserve_transferable: true,
+			}));
+			// check trusted asset, which is reserve-transferred from here
+			assert!(assets.contains(&AssetBalance {
+				asset: AssetIdForTrustBackedAssetsConvert::convert_back(&local_asset_id)
+					.unwrap()
+					.into(),
+				free: 2 * minimum_asset_balance,
+				held: minimum_asset_balance,
+				frozen: minimum_asset_balance,
+				existential_deposit: minimum_asset_balance,
+				is_sufficient: true,
+				decimals: 6,
+				symbol: b"LOC".to_vec(),
+				teleportable: false,
+				reserve_transferable: true,
+			}));
+			// check foreign asset, which is teleported back to its sibling parachain
+			assert!(assets.contains(&AssetBalance {
+				asset: foreign_asset_id_multilocation.into(),
+				free: 6 * foreign_asset_minimum_asset_balance,
+				held: 0,
+				frozen: 0,
+				existential_deposit: foreign_asset_minimum_asset_balance,
+				is_sufficient: false,
+				decimals: 0,
+				symbol: vec![],
+				teleportable: true,
+				reserve_transferable: false,
+			}));
+
+			// The hold keeps the account alive, so burning all of the trusted asset only burns it
+			// down to its existential deposit
+			assert_ok!(AssetLocks::thaw(
+				local_asset_id,
+				&RuntimeFreezeReason::AssetVesting(FreezeReason::Vesting),
+				&AccountId::from(ALICE)
+			));
+			assert_ok!(Assets::burn(
+				RuntimeHelper::origin_of(AccountId::from(ALICE)),
+				local_asset_id.into(),
+				AccountId::from(ALICE).into(),
+				2 * minimum_asset_balance
+			));
+			assert_eq!(
+				Assets::balance(local_asset_id, AccountId::from(ALICE)),
+				minimum_asset_balance
+			);
+			let local_asset_location =
+				AssetIdForTrustBackedAssetsConvert::convert_back(&local_asset_id).unwrap();
+			let assets = Runtime::query_account_assets(AccountId::from(ALICE)).unwrap();
+			assert_eq!(assets.len(), 3);
+			let local_asset =
+				assets.iter().find(|asset| asset.asset == local_asset_location.into()).unwrap();
+			assert_eq!(
+				(local_asset.free, local_asset.held, local_asset.frozen),
+				(minimum_asset_balance, minimum_asset_balance, 0)
+			);
+
+			// Once released, the held amount is free again
+			assert_ok!(AssetLocks::release(
+				local_asset_id,
+				&AssetHoldReason::Governance,
+				&AccountId::from(ALICE),
+				minimum_asset_balance,
+				frame_support::traits::tokens::Precision::Exact
+			));
+			let assets = Runtime::query_account_assets(AccountId::from(ALICE)).unwrap();
+			let local_asset =
+				assets.iter().find(|asset| asset.asset == local_asset_location.into()).unwrap();
+			assert_eq!((local_asset.free, local_asset.held), (2 * minimum_asset_balance, 0));
 		});
 }
 
+#[test]
+fn nfts_can_be_fractionalized_and_unified() {
+	use asset_hub_polkadot_runtime::{NftFractionalization, Nfts};
+	use frame_support::traits::fungible::Mutate;
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::ed25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			let alice = AccountId::from(ALICE);
+			let (collection, item, asset_id) = (0, 1, 10);
+			Balances::mint_into(&alice, 100 * AssetDeposit::get()).unwrap();
+			assert_ok!(Nfts::force_create(
+				RuntimeHelper::root_origin(),
+				alice.clone().into(),
+				pallet_nfts::CollectionConfig {
+					settings: pallet_nfts::CollectionSettings::all_enabled(),
+					max_supply: None,
+					mint_settings: pallet_nfts::MintSettings::default(),
+				}
+			));
+			assert_ok!(Nfts::mint(
+				RuntimeHelper::origin_of(alice.clone()),
+				collection,
+				item,
+				alice.clone().into(),
+				None
+			));
+
+			assert_ok!(NftFractionalization::fractionalize(
+				RuntimeHelper::origin_of(alice.clone()),
+				collection,
+				item,
+				asset_id,
+				alice.clone().into(),
+				1_000
+			));
+			assert_eq!(Assets::balance(asset_id, &alice), 1_000);
+
+			assert_ok!(NftFractionalization::unify(
+				RuntimeHelper::origin_of(alice.clone()),
+				collection,
+				item,
+				asset_id,
+				alice.clone().into()
+			));
+			assert_eq!(Assets::balance(asset_id, &alice), 0);
+		})
+}
+
 asset_test_utils::include_teleports_for_native_asset_works!(
 	Runtime,
 	AllPalletsWithoutSystem,
@@ -657,3 +833,36 @@ asset_test_utils::include_create_and_manage_foreign_assets_for_local_consensus_p
 		assert_eq!(ForeignAssets::asset_ids().collect::<Vec<_>>().len(), 1);
 	})
 );
+
+#[test]
+fn async_backing_config_matches_relay_chain() {
+	use cumulus_pallet_aura_ext::FixedVelocityConsensusHook;
+	use frame_support::traits::Get;
+	use polkadot_runtime_constants::{
+		async_backing::ASYNC_BACKING_PARAMS, time::MILLISECS_PER_BLOCK,
+	};
+
+	trait HookParams {
+		const PARAMS: (u32, u32, u32);
+	}
+	impl<T, const R: u32, const V: u32, const C: u32> HookParams
+		for FixedVelocityConsensusHook<T, R, V, C>
+	{
+		const PARAMS: (u32, u32, u32) = (R, V, C);
+	}
+	type Hook = <Runtime as cumulus_pallet_parachain_system::Config>::ConsensusHook;
+	let (relay_slot_duration, velocity, capacity) = <Hook as HookParams>::PARAMS;
+
+	assert_eq!(relay_slot_duration as u64, MILLISECS_PER_BLOCK);
+	// Blocks are not authored faster than the consensus hook accepts them.
+	assert!(
+		2 * <Runtime as pallet_timestamp::Config>::MinimumPeriod::get() >=
+			(relay_slot_duration / velocity) as u64
+	);
+	// A block built on the oldest allowed relay parent is still not included while the blocks
+	// built on the following relay parents are authored.
+	assert!(capacity >= velocity * (ASYNC_BACKING_PARAMS.allowed_ancestry_len + 1));
+	// The Relay Chain does not back more than `max_candidate_depth + 1` candidates of a para
+	// building on top of each other.
+	assert!(capacity <= ASYNC_BACKING_PARAMS.max_candidate_depth + 1);
+}
@@ -0,0 +1,122 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The balances of the assets of an account, with the details of the assets.
+//!
+//! `assets_common::runtime_api::FungiblesApi` only returns the positive free balances of an
+//! account. [`AssetBalancesApi`] returns every asset an account has free or held, with its held and
+//! frozen amounts and what a wallet needs to show and move it: its existential deposit and
+//! sufficiency, its decimals and symbol, and whether it can be teleported or reserve-transferred
+//! from this chain.
+
+use assets_common::runtime_api::FungiblesAccessError;
+use codec::{Decode, Encode};
+use frame_support::traits::{
+	tokens::fungibles::{metadata, Inspect, InspectEnumerable},
+	Contains,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{MaybeEquivalence, Zero},
+	RuntimeDebug,
+};
+use sp_std::prelude::*;
+use xcm::{latest::MultiLocation, VersionedMultiAssets, VersionedMultiLocation};
+
+/// The balance of an account in an asset, with the details of the asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AssetBalance {
+	/// The location of the asset.
+	pub asset: VersionedMultiLocation,
+	/// The amount not held, including the frozen amount.
+	pub free: u128,
+	/// The amount held or reserved, which is part of the total balance but cannot be used.
+	pub held: u128,
+	/// The largest freeze, which overlaps with the held amount as in `pallet_balances`.
+	pub frozen: u128,
+	/// The existential deposit of the asset.
+	pub existential_deposit: u128,
+	/// Whether the asset keeps an account alive by itself.
+	pub is_sufficient: bool,
+	/// The number of decimals of the asset.
+	pub decimals: u8,
+	/// The symbol of the asset.
+	pub symbol: Vec<u8>,
+	/// Whether the asset can be teleported from this chain.
+	pub teleportable: bool,
+	/// Whether this chain is the reserve of the asset, so it can be reserve-transferred from here.
+	pub reserve_transferable: bool,
+}
+
+/// The assets `who` has free or held in the `pallet_assets` instance `I`, with the holds and
+/// freezes of the `asset_locks` instance `I`.
+pub fn pallet_assets_balances<T, I, ConvertAssetId, Teleportable, Reserve>(
+	who: &T::AccountId,
+) -> Result<Vec<AssetBalance>, FungiblesAccessError>
+where
+	T: crate::asset_locks::Config<I>,
+	I: 'static,
+	T::Balance: Into<u128>,
+	ConvertAssetId: MaybeEquivalence<MultiLocation, T::AssetId>,
+	Teleportable: Contains<MultiLocation>,
+	Reserve: Contains<MultiLocation>,
+{
+	type Assets<T, I> = pallet_assets::Pallet<T, I>;
+	type Locks<T, I> = crate::asset_locks::Pallet<T, I>;
+
+	<Assets<T, I> as InspectEnumerable<T::AccountId>>::asset_ids()
+		.into_iter()
+		.filter_map(|asset| {
+			let free = <Assets<T, I> as Inspect<T::AccountId>>::balance(asset.clone(), who);
+			let held = Locks::<T, I>::total_held(asset.clone(), who);
+			(!free.is_zero() || !held.is_zero()).then_some((asset, free, held))
+		})
+		.map(|(asset, free, held)| {
+			let location = ConvertAssetId::convert_back(&asset)
+				.ok_or(FungiblesAccessError::AssetIdConversionFailed)?;
+			Ok(AssetBalance {
+				asset: location.into(),
+				free: free.into(),
+				held: held.into(),
+				frozen: Locks::<T, I>::max_frozen(asset.clone(), who).into(),
+				existential_deposit: <Assets<T, I> as Inspect<T::AccountId>>::minimum_balance(
+					asset.clone(),
+				)
+				.into(),
+				is_sufficient: pallet_assets::Asset::<T, I>::get(asset.clone())
+					.map_or(false, |details| details.is_sufficient),
+				decimals: <Assets<T, I> as metadata::Inspect<T::AccountId>>::decimals(
+					asset.clone(),
+				),
+				symbol: <Assets<T, I> as metadata::Inspect<T::AccountId>>::symbol(asset),
+				teleportable: Teleportable::contains(&location),
+				reserve_transferable: Reserve::contains(&location),
+			})
+		})
+		.collect()
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to query the assets of an account, with their details.
+	pub trait AssetBalancesApi<AccountId>
+	where
+		AccountId: codec::Codec,
+	{
+		/// The assets `account` has free or held, with their held and frozen amounts and details.
+		fn query_account_assets(
+			account: AccountId,
+		) -> Result<Vec<AssetBalance>, FungiblesAccessError>;
+	}
+}
@@ -0,0 +1,354 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Freezes and holds for a `pallet_assets` instance.
+//!
+//! `pallet_assets` only knows a single frozen amount per account, provided by its `Freezer`. An
+//! instance of this pallet is the `Freezer` of the `pallet_assets` instance of the same name and
+//! implements the `fungibles` freeze and hold traits on top of it, keyed by the freeze reasons of
+//! the runtime and by [`AssetHoldReason`], such that other pallets can lock assets for their own
+//! purposes.
+//!
+//! Like the holds of `pallet_balances`, held assets are moved out of the balance of the account in
+//! `pallet_assets`, but remain part of its total balance and the total issuance. Freezes overlap
+//! with each other and with held assets: the largest freeze on an account is frozen, reduced by the
+//! assets on hold.
+//!
+//! An account with assets on hold is kept alive by `pallet_assets`, which keeps the minimum balance
+//! in any account with a frozen balance, so the holds are not orphaned when the rest of its assets
+//! leave. It only dies with its holds when the asset is destroyed, or when `refund` burns its
+//! balance.
+//!
+//! The pallet only adds storage, so it needs no migration when added to a runtime.
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::tokens::{
+		fungibles, DepositConsequence, Fortitude, Precision, Preservation, Provenance,
+		WithdrawConsequence,
+	},
+};
+pub use pallet_asset_locks::*;
+use pallet_assets::FrozenBalance;
+use sp_runtime::traits::{Saturating, Zero};
+
+/// The reasons for which the Asset Hubs hold assets.
+///
+/// The hold reasons of the runtime are about the native currency, so the assets have their own.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AssetHoldReason {
+	/// Held by governance, e.g. while it settles a dispute over the assets.
+	#[codec(index = 0)]
+	Governance,
+}
+
+/// An amount frozen or held for a reason.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct IdAmount<Id, Balance> {
+	/// The reason of the freeze or hold.
+	pub id: Id,
+	/// The amount frozen or held.
+	pub amount: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet_asset_locks {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config + pallet_assets::Config<I> {
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: Parameter + Member + MaxEncodedLen + Copy;
+
+		/// The reason of a hold.
+		type HoldReason: Parameter + Member + MaxEncodedLen + Copy;
+
+		/// The maximum number of freezes on the assets of an account, per asset.
+		#[pallet::constant]
+		type MaxFreezes: Get<u32>;
+
+		/// The maximum number of holds on the assets of an account, per asset.
+		#[pallet::constant]
+		type MaxHolds: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	/// The freezes on the assets of an account, by asset and account.
+	#[pallet::storage]
+	pub type Freezes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<IdAmount<T::RuntimeFreezeReason, T::Balance>, T::MaxFreezes>,
+		ValueQuery,
+	>;
+
+	/// The holds on the assets of an account, by asset and account.
+	#[pallet::storage]
+	pub type Holds<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<IdAmount<T::HoldReason, T::Balance>, T::MaxHolds>,
+		ValueQuery,
+	>;
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The account has `MaxFreezes` freezes on the asset already.
+		TooManyFreezes,
+		/// The account has `MaxHolds` holds on the asset already.
+		TooManyHolds,
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// The largest freeze on `asset` of `who`.
+		pub fn max_frozen(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+			Freezes::<T, I>::get(asset, who)
+				.iter()
+				.map(|freeze| freeze.amount)
+				.max()
+				.unwrap_or_else(Zero::zero)
+		}
+
+		/// The total amount of `asset` held from `who`.
+		pub fn total_held(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+			Holds::<T, I>::get(asset, who)
+				.iter()
+				.fold(Zero::zero(), |total: T::Balance, hold| total.saturating_add(hold.amount))
+		}
+	}
+}
+
+type Assets<T, I> = pallet_assets::Pallet<T, I>;
+
+impl<T: Config<I>, I: 'static> FrozenBalance<T::AssetId, T::AccountId, T::Balance>
+	for Pallet<T, I>
+{
+	fn frozen_balance(asset: T::AssetId, who: &T::AccountId) -> Option<T::Balance> {
+		let held = Self::total_held(asset.clone(), who);
+		let frozen = Self::max_frozen(asset, who).saturating_sub(held);
+		// Even with nothing frozen, some frozen balance keeps the account with the holds alive.
+		(!frozen.is_zero() || !held.is_zero()).then_some(frozen)
+	}
+
+	fn died(asset: T::AssetId, who: &T::AccountId) {
+		Freezes::<T, I>::remove(asset.clone(), who);
+		Holds::<T, I>::remove(asset, who);
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::Inspect<T::AccountId> for Pallet<T, I> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn total_issuance(asset: T::AssetId) -> T::Balance {
+		<Assets<T, I> as fungibles::Inspect<_>>::total_issuance(asset)
+	}
+
+	fn minimum_balance(asset: T::AssetId) -> T::Balance {
+		<Assets<T, I> as fungibles::Inspect<_>>::minimum_balance(asset)
+	}
+
+	fn total_balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		<Assets<T, I> as fungibles::Inspect<_>>::total_balance(asset.clone(), who)
+			.saturating_add(Self::total_held(asset, who))
+	}
+
+	fn balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		<Assets<T, I> as fungibles::Inspect<_>>::balance(asset, who)
+	}
+
+	fn reducible_balance(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> T::Balance {
+		<Assets<T, I> as fungibles::Inspect<_>>::reducible_balance(asset, who, preservation, force)
+	}
+
+	fn can_deposit(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		provenance: Provenance,
+	) -> DepositConsequence {
+		<Assets<T, I> as fungibles::Inspect<_>>::can_deposit(asset, who, amount, provenance)
+	}
+
+	fn can_withdraw(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> WithdrawConsequence<T::Balance> {
+		<Assets<T, I> as fungibles::Inspect<_>>::can_withdraw(asset, who, amount)
+	}
+
+	fn asset_exists(asset: T::AssetId) -> bool {
+		<Assets<T, I> as fungibles::Inspect<_>>::asset_exists(asset)
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::Unbalanced<T::AccountId> for Pallet<T, I> {
+	fn handle_dust(dust: fungibles::Dust<T::AccountId, Self>) {
+		<Assets<T, I> as fungibles::Unbalanced<_>>::handle_dust(fungibles::Dust(dust.0, dust.1))
+	}
+
+	fn write_balance(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> Result<Option<T::Balance>, DispatchError> {
+		<Assets<T, I> as fungibles::Unbalanced<_>>::write_balance(asset, who, amount)
+	}
+
+	fn set_total_issuance(asset: T::AssetId, amount: T::Balance) {
+		<Assets<T, I> as fungibles::Unbalanced<_>>::set_total_issuance(asset, amount)
+	}
+
+	fn decrease_balance(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		precision: Precision,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Result<T::Balance, DispatchError> {
+		<Assets<T, I> as fungibles::Unbalanced<_>>::decrease_balance(
+			asset,
+			who,
+			amount,
+			precision,
+			preservation,
+			force,
+		)
+	}
+
+	fn increase_balance(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		precision: Precision,
+	) -> Result<T::Balance, DispatchError> {
+		<Assets<T, I> as fungibles::Unbalanced<_>>::increase_balance(asset, who, amount, precision)
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::InspectFreeze<T::AccountId> for Pallet<T, I> {
+	type Id = T::RuntimeFreezeReason;
+
+	fn balance_frozen(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> T::Balance {
+		Freezes::<T, I>::get(asset, who)
+			.iter()
+			.find(|freeze| freeze.id == *id)
+			.map_or_else(Zero::zero, |freeze| freeze.amount)
+	}
+
+	fn can_freeze(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> bool {
+		let freezes = Freezes::<T, I>::get(asset, who);
+		!freezes.is_full() || freezes.iter().any(|freeze| freeze.id == *id)
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::MutateFreeze<T::AccountId> for Pallet<T, I> {
+	fn set_freeze(
+		asset: T::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Self::thaw(asset, id, who)
+		}
+		Freezes::<T, I>::try_mutate(asset, who, |freezes| {
+			freezes.retain(|freeze| freeze.id != *id);
+			freezes
+				.try_push(IdAmount { id: *id, amount })
+				.map_err(|_| Error::<T, I>::TooManyFreezes.into())
+		})
+	}
+
+	fn extend_freeze(
+		asset: T::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let frozen = <Self as fungibles::InspectFreeze<_>>::balance_frozen(asset.clone(), id, who);
+		Self::set_freeze(asset, id, who, frozen.max(amount))
+	}
+
+	fn thaw(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		Freezes::<T, I>::mutate_exists(asset, who, |maybe_freezes| {
+			if let Some(freezes) = maybe_freezes {
+				freezes.retain(|freeze| freeze.id != *id);
+				if freezes.is_empty() {
+					*maybe_freezes = None;
+				}
+			}
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::InspectHold<T::AccountId> for Pallet<T, I> {
+	type Reason = T::HoldReason;
+
+	fn total_balance_on_hold(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		Self::total_held(asset, who)
+	}
+
+	fn balance_on_hold(asset: T::AssetId, reason: &Self::Reason, who: &T::AccountId) -> T::Balance {
+		Holds::<T, I>::get(asset, who)
+			.iter()
+			.find(|hold| hold.id == *reason)
+			.map_or_else(Zero::zero, |hold| hold.amount)
+	}
+
+	fn hold_available(asset: T::AssetId, reason: &Self::Reason, who: &T::AccountId) -> bool {
+		let holds = Holds::<T, I>::get(asset, who);
+		!holds.is_full() || holds.iter().any(|hold| hold.id == *reason)
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::UnbalancedHold<T::AccountId> for Pallet<T, I> {
+	fn set_balance_on_hold(
+		asset: T::AssetId,
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		Holds::<T, I>::try_mutate_exists(asset, who, |maybe_holds| {
+			let mut holds = maybe_holds.take().unwrap_or_default();
+			holds.retain(|hold| hold.id != *reason);
+			if !amount.is_zero() {
+				holds
+					.try_push(IdAmount { id: *reason, amount })
+					.map_err(|_| Error::<T, I>::TooManyHolds)?;
+			}
+			*maybe_holds = (!holds.is_empty()).then_some(holds);
+			Ok(())
+		})
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::MutateHold<T::AccountId> for Pallet<T, I> {}
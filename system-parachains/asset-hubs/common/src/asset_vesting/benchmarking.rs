@@ -0,0 +1,169 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the asset vesting.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::traits::{
+	fungibles::{Create, Mutate},
+	EnsureOrigin,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::traits::StaticLookup;
+
+/// The amount locked by each vesting schedule of the benchmarks, unless a vested transfer needs
+/// more.
+const LOCKED: u32 = 1_000;
+
+/// The amount locked by each vesting schedule of the benchmarks, of an asset with a minimum
+/// balance of one.
+fn locked<T: Config>() -> T::Balance {
+	T::Balance::from(LOCKED).max(T::MinVestedTransferMultiple::get().into())
+}
+
+/// Create the asset of the benchmarks and give `source` enough of it for `MaxVestingSchedules`
+/// vested transfers.
+fn create_asset<T: Config>(source: &T::AccountId) -> T::AssetId
+where
+	T::Assets: Create<T::AccountId>,
+{
+	let asset = T::BenchmarkHelper::asset_id();
+	T::Assets::create(asset, source.clone(), true, 1u32.into())
+		.expect("the asset does not exist yet");
+	let amount = locked::<T>().saturating_mul(T::MaxVestingSchedules::get().into());
+	T::Assets::mint_into(asset, source, amount).expect("the asset exists");
+	asset
+}
+
+/// A schedule which starts to unlock in ten blocks.
+fn schedule<T: Config>() -> VestingInfo<T::Balance, BlockNumberFor<T>> {
+	let now = frame_system::Pallet::<T>::block_number();
+	VestingInfo {
+		locked: locked::<T>(),
+		per_block: 10u32.into(),
+		starting_block: now.saturating_add(10u32.into()),
+	}
+}
+
+/// Give `target` `s` vesting schedules of a new asset and return the asset.
+fn add_schedules<T: Config>(target: &T::AccountId, s: u32) -> T::AssetId
+where
+	T::Assets: Create<T::AccountId>,
+{
+	let source: T::AccountId = account("source", 0, 0);
+	let asset = create_asset::<T>(&source);
+	for _ in 0..s {
+		Pallet::<T>::vested_transfer(
+			RawOrigin::Signed(source.clone()).into(),
+			T::Lookup::unlookup(target.clone()),
+			asset,
+			schedule::<T>(),
+		)
+		.expect("the source holds enough of the asset");
+	}
+	asset
+}
+
+/// Move to a block at which every schedule of [`schedule`] unlocked some of its amount.
+fn start_vesting<T: Config>() {
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now.saturating_add(15u32.into()));
+}
+
+#[benchmarks(where T::Assets: Create<T::AccountId>)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn vest(s: Linear<1, { T::MaxVestingSchedules::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset = add_schedules::<T>(&caller, s);
+		start_vesting::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), asset);
+
+		assert_eq!(
+			Vesting::<T>::get(&caller, asset).map(|schedules| schedules.len()),
+			Some(s as usize)
+		);
+	}
+
+	#[benchmark]
+	fn vest_other(s: Linear<1, { T::MaxVestingSchedules::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let target: T::AccountId = account("target", 0, 0);
+		let asset = add_schedules::<T>(&target, s);
+		start_vesting::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), T::Lookup::unlookup(target.clone()), asset);
+
+		assert_eq!(
+			Vesting::<T>::get(&target, asset).map(|schedules| schedules.len()),
+			Some(s as usize)
+		);
+	}
+
+	#[benchmark]
+	fn vested_transfer(s: Linear<0, { T::MaxVestingSchedules::get() - 1 }>) {
+		let target: T::AccountId = account("target", 0, 0);
+		let asset = add_schedules::<T>(&target, s);
+		let source: T::AccountId = account("source", 0, 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(source), T::Lookup::unlookup(target.clone()), asset, schedule::<T>());
+
+		assert_eq!(
+			Vesting::<T>::get(&target, asset).map(|schedules| schedules.len()),
+			Some(s as usize + 1)
+		);
+	}
+
+	#[benchmark]
+	fn merge_schedules(s: Linear<2, { T::MaxVestingSchedules::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset = add_schedules::<T>(&caller, s);
+		start_vesting::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), asset, 0, 1);
+
+		assert_eq!(
+			Vesting::<T>::get(&caller, asset).map(|schedules| schedules.len()),
+			Some(s as usize - 1)
+		);
+	}
+
+	#[benchmark]
+	fn force_remove_vesting_schedule(
+		s: Linear<1, { T::MaxVestingSchedules::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let origin =
+			T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let target: T::AccountId = account("target", 0, 0);
+		let asset = add_schedules::<T>(&target, s);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, T::Lookup::unlookup(target.clone()), asset, 0);
+
+		assert_eq!(
+			Vesting::<T>::get(&target, asset).map_or(0, |schedules| schedules.len()),
+			s as usize - 1
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,461 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Vesting schedules for assets.
+//!
+//! The counterpart of `pallet_vesting` for the assets of a `fungibles` implementation, keyed by
+//! asset. A vested transfer moves the assets to the beneficiary and freezes them there with
+//! [`FreezeReason::Vesting`]; the freeze shrinks linearly from the starting block of the schedule
+//! whenever the beneficiary, or anyone on their behalf, calls [`Pallet::vest`].
+//!
+//! [`AssetVestingApi`] exposes the locked and claimable amounts of the vesting assets of an
+//! account.
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use frame_support::{pallet_prelude::*, weights::Weight};
+use frame_system::pallet_prelude::BlockNumberFor;
+pub use pallet_asset_vesting::*;
+use sp_runtime::traits::{AtLeast32BitUnsigned, Convert, Saturating, Zero};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Weight functions of the asset vesting, by the number of vesting schedules `s` of the account
+/// for the asset.
+pub trait WeightInfo {
+	fn vest(s: u32) -> Weight;
+	fn vest_other(s: u32) -> Weight;
+	fn vested_transfer(s: u32) -> Weight;
+	fn merge_schedules(s: u32) -> Weight;
+	fn force_remove_vesting_schedule(s: u32) -> Weight;
+}
+
+/// The estimated execution time of handling one vesting schedule, in picoseconds.
+const SCHEDULE_REF_TIME: u64 = 200_000;
+
+/// Weights of the asset vesting from its storage accesses and an estimate of the time and proof
+/// size of each vesting schedule only, until the runtime provides benchmarked weights.
+pub struct DbWeightInfo<T>(PhantomData<T>);
+impl<T: Config> DbWeightInfo<T> {
+	/// The weight of handling `s` vesting schedules.
+	fn schedules(s: u32) -> Weight {
+		let proof_size = VestingInfo::<T::Balance, BlockNumberFor<T>>::max_encoded_len() as u64;
+		Weight::from_parts(SCHEDULE_REF_TIME, proof_size).saturating_mul(s.into())
+	}
+}
+impl<T: Config> WeightInfo for DbWeightInfo<T> {
+	fn vest(s: u32) -> Weight {
+		T::DbWeight::get().reads_writes(3, 2).saturating_add(Self::schedules(s))
+	}
+	fn vest_other(s: u32) -> Weight {
+		T::DbWeight::get().reads_writes(3, 2).saturating_add(Self::schedules(s))
+	}
+	fn vested_transfer(s: u32) -> Weight {
+		T::DbWeight::get()
+			.reads_writes(6, 5)
+			.saturating_add(Self::schedules(s.saturating_add(1)))
+	}
+	fn merge_schedules(s: u32) -> Weight {
+		T::DbWeight::get().reads_writes(3, 2).saturating_add(Self::schedules(s))
+	}
+	fn force_remove_vesting_schedule(s: u32) -> Weight {
+		T::DbWeight::get().reads_writes(3, 2).saturating_add(Self::schedules(s))
+	}
+}
+
+/// Provides the asset which vests in the benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AssetId> {
+	/// The id of an asset which does not exist yet.
+	fn asset_id() -> AssetId;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl<AssetId: From<u32>> BenchmarkHelper<AssetId> for () {
+	fn asset_id() -> AssetId {
+		1u32.into()
+	}
+}
+
+/// A vesting schedule of an asset.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct VestingInfo<Balance, BlockNumber> {
+	/// The amount locked at the start of the schedule.
+	pub locked: Balance,
+	/// The amount unlocked per block from the starting block on.
+	pub per_block: Balance,
+	/// The block from which the amount starts to unlock.
+	pub starting_block: BlockNumber,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy, BlockNumber: AtLeast32BitUnsigned + Copy>
+	VestingInfo<Balance, BlockNumber>
+{
+	/// Whether the schedule locks and unlocks anything.
+	pub fn is_valid(&self) -> bool {
+		!self.locked.is_zero() && !self.per_block.is_zero()
+	}
+
+	/// The amount still locked at block `n`.
+	pub fn locked_at<C: Convert<BlockNumber, Balance>>(&self, n: BlockNumber) -> Balance {
+		let vested_blocks = C::convert(n.saturating_sub(self.starting_block));
+		self.locked.saturating_sub(vested_blocks.saturating_mul(self.per_block))
+	}
+
+	/// The block at which the schedule unlocks everything, as a balance.
+	fn ending_block<C: Convert<BlockNumber, Balance>>(&self) -> Balance {
+		let blocks = self.locked / self.per_block +
+			if (self.locked % self.per_block).is_zero() { Zero::zero() } else { 1u32.into() };
+		C::convert(self.starting_block).saturating_add(blocks)
+	}
+}
+
+/// The vesting assets of an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AssetVestingBalance<AssetId, Balance> {
+	/// The vesting asset.
+	pub asset: AssetId,
+	/// The amount still locked by the vesting schedules.
+	pub locked: Balance,
+	/// The amount unlocked by the schedules, but still frozen until vested.
+	pub claimable: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet_asset_vesting {
+	use super::*;
+	use frame_support::traits::tokens::{
+		fungibles::{self, InspectFreeze, MutateFreeze},
+		Preservation,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::StaticLookup;
+
+	type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
+	/// The vesting schedules of an account for an asset.
+	pub type SchedulesOf<T> = BoundedVec<
+		VestingInfo<<T as Config>::Balance, BlockNumberFor<T>>,
+		<T as Config>::MaxVestingSchedules,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Identifier of an asset.
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+
+		/// The balance of an asset.
+		type Balance: Member + Parameter + AtLeast32BitUnsigned + Copy + MaxEncodedLen;
+
+		/// The assets which vest.
+		type Assets: fungibles::Mutate<
+			Self::AccountId,
+			AssetId = Self::AssetId,
+			Balance = Self::Balance,
+		>;
+
+		/// The freezes on `Assets`.
+		type Freezer: fungibles::MutateFreeze<
+			Self::AccountId,
+			AssetId = Self::AssetId,
+			Balance = Self::Balance,
+			Id = Self::RuntimeFreezeReason,
+		>;
+
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: From<FreezeReason>;
+
+		/// Convert a block number into a balance.
+		type BlockNumberToBalance: Convert<BlockNumberFor<Self>, Self::Balance>;
+
+		/// Origin allowed to remove vesting schedules.
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of vesting schedules of an account for an asset.
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+
+		/// The minimum amount of a vested transfer, as a multiple of the minimum balance of the
+		/// asset, so that filling the vesting schedules of an account is costly.
+		#[pallet::constant]
+		type MinVestedTransferMultiple: Get<u32>;
+
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Provides the asset which vests in the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AssetId>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The reasons for which this pallet freezes assets.
+	#[pallet::composite_enum]
+	pub enum FreezeReason {
+		/// The assets are vesting.
+		#[codec(index = 0)]
+		Vesting,
+	}
+
+	/// The vesting schedules, by account and asset.
+	#[pallet::storage]
+	pub type Vesting<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AssetId,
+		SchedulesOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The amount of `asset` still locked for `who` changed.
+		VestingUpdated { who: T::AccountId, asset: T::AssetId, unvested: T::Balance },
+		/// All of `asset` vested for `who`.
+		VestingCompleted { who: T::AccountId, asset: T::AssetId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account has no vesting schedule for the asset.
+		NotVesting,
+		/// The account has `MaxVestingSchedules` vesting schedules for the asset already.
+		AtMaxVestingSchedules,
+		/// The amount to vest is below `MinVestedTransferMultiple` times the minimum balance of
+		/// the asset.
+		AmountLow,
+		/// No vesting schedule has the given index.
+		ScheduleIndexOutOfBounds,
+		/// The vesting schedule locks or unlocks nothing.
+		InvalidScheduleParams,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Unlock the vested amount of `asset` of the sender.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::vest(T::MaxVestingSchedules::get()))]
+		pub fn vest(origin: OriginFor<T>, asset: T::AssetId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_vest(&who, asset)
+		}
+
+		/// Unlock the vested amount of `asset` of `target`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::vest_other(T::MaxVestingSchedules::get()))]
+		pub fn vest_other(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			asset: T::AssetId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_vest(&T::Lookup::lookup(target)?, asset)
+		}
+
+		/// Transfer `schedule.locked` of `asset` from the sender to `target`, vesting according
+		/// to `schedule`.
+		///
+		/// The amount must be at least `MinVestedTransferMultiple` times the minimum balance of
+		/// the asset.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::vested_transfer(T::MaxVestingSchedules::get()))]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			asset: T::AssetId,
+			schedule: VestingInfo<T::Balance, BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let source = ensure_signed(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			ensure!(schedule.is_valid(), Error::<T>::InvalidScheduleParams);
+			ensure!(schedule.locked >= Self::min_vested_transfer(asset), Error::<T>::AmountLow);
+
+			let mut schedules = Vesting::<T>::get(&target, asset).unwrap_or_default();
+			schedules.try_push(schedule).map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+			<T::Assets as fungibles::Mutate<_>>::transfer(
+				asset,
+				&source,
+				&target,
+				schedule.locked,
+				Preservation::Expendable,
+			)?;
+			Self::write_vesting(&target, asset, schedules)
+		}
+
+		/// Merge the vesting schedules of `asset` of the sender at `schedule1_index` and
+		/// `schedule2_index` into one, which unlocks the amount still locked by both from now
+		/// until the later one of them ends.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::merge_schedules(T::MaxVestingSchedules::get()))]
+		pub fn merge_schedules(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			schedule1_index: u32,
+			schedule2_index: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if schedule1_index == schedule2_index {
+				return Ok(())
+			}
+			let mut schedules = Vesting::<T>::get(&who, asset).ok_or(Error::<T>::NotVesting)?;
+			let (first, second) = (
+				schedule1_index.min(schedule2_index) as usize,
+				schedule1_index.max(schedule2_index) as usize,
+			);
+			ensure!(second < schedules.len(), Error::<T>::ScheduleIndexOutOfBounds);
+			let schedule2 = schedules.remove(second);
+			let schedule1 = schedules.remove(first);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some(merged) = Self::merge(now, schedule1, schedule2) {
+				// Two schedules were removed, so there is room for one.
+				let _ = schedules.try_push(merged);
+			}
+			Self::write_vesting(&who, asset, schedules)
+		}
+
+		/// Remove the vesting schedule of `asset` of `target` at `schedule_index`, unlocking the
+		/// amount it still locks.
+		#[pallet::call_index(4)]
+		#[pallet::weight(
+			T::WeightInfo::force_remove_vesting_schedule(T::MaxVestingSchedules::get())
+		)]
+		pub fn force_remove_vesting_schedule(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			asset: T::AssetId,
+			schedule_index: u32,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			let mut schedules = Vesting::<T>::get(&target, asset).ok_or(Error::<T>::NotVesting)?;
+			ensure!(
+				(schedule_index as usize) < schedules.len(),
+				Error::<T>::ScheduleIndexOutOfBounds
+			);
+			schedules.remove(schedule_index as usize);
+			Self::write_vesting(&target, asset, schedules)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The minimum amount of a vested transfer of `asset`.
+		pub fn min_vested_transfer(asset: T::AssetId) -> T::Balance {
+			<T::Assets as fungibles::Inspect<_>>::minimum_balance(asset)
+				.saturating_mul(T::MinVestedTransferMultiple::get().into())
+		}
+
+		/// The amount of `asset` still locked by `schedules` at block `now`.
+		fn locked_at(
+			now: BlockNumberFor<T>,
+			schedules: &[VestingInfo<T::Balance, BlockNumberFor<T>>],
+		) -> T::Balance {
+			schedules.iter().fold(Zero::zero(), |locked: T::Balance, schedule| {
+				locked.saturating_add(schedule.locked_at::<T::BlockNumberToBalance>(now))
+			})
+		}
+
+		/// The schedule unlocking what `schedule1` and `schedule2` still lock at `now`, from the
+		/// later of `now` and their starting blocks until the later of their ending blocks.
+		fn merge(
+			now: BlockNumberFor<T>,
+			schedule1: VestingInfo<T::Balance, BlockNumberFor<T>>,
+			schedule2: VestingInfo<T::Balance, BlockNumberFor<T>>,
+		) -> Option<VestingInfo<T::Balance, BlockNumberFor<T>>> {
+			let locked = Self::locked_at(now, &[schedule1, schedule2]);
+			if locked.is_zero() {
+				return None
+			}
+			let starting_block = now.max(schedule1.starting_block).max(schedule2.starting_block);
+			let ending_block = schedule1
+				.ending_block::<T::BlockNumberToBalance>()
+				.max(schedule2.ending_block::<T::BlockNumberToBalance>());
+			let duration =
+				ending_block.saturating_sub(T::BlockNumberToBalance::convert(starting_block));
+			let per_block =
+				if duration.is_zero() { locked } else { (locked / duration).max(1u32.into()) };
+			Some(VestingInfo { locked, per_block, starting_block })
+		}
+
+		fn do_vest(who: &T::AccountId, asset: T::AssetId) -> DispatchResult {
+			let schedules = Vesting::<T>::get(who, asset).ok_or(Error::<T>::NotVesting)?;
+			Self::write_vesting(who, asset, schedules)
+		}
+
+		/// Store `schedules` without the ones which ended and freeze what they still lock.
+		fn write_vesting(
+			who: &T::AccountId,
+			asset: T::AssetId,
+			mut schedules: SchedulesOf<T>,
+		) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			schedules
+				.retain(|schedule| !schedule.locked_at::<T::BlockNumberToBalance>(now).is_zero());
+			let locked = Self::locked_at(now, &schedules);
+			let reason = FreezeReason::Vesting.into();
+
+			if locked.is_zero() {
+				T::Freezer::thaw(asset, &reason, who)?;
+				Vesting::<T>::remove(who, asset);
+				Self::deposit_event(Event::<T>::VestingCompleted { who: who.clone(), asset });
+			} else {
+				T::Freezer::set_freeze(asset, &reason, who, locked)?;
+				Vesting::<T>::insert(who, asset, schedules);
+				Self::deposit_event(Event::<T>::VestingUpdated {
+					who: who.clone(),
+					asset,
+					unvested: locked,
+				});
+			}
+			Ok(())
+		}
+
+		/// The locked and claimable amounts of the vesting assets of `who`.
+		pub fn vesting_balances(
+			who: &T::AccountId,
+		) -> Vec<AssetVestingBalance<T::AssetId, T::Balance>> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let reason = FreezeReason::Vesting.into();
+			Vesting::<T>::iter_prefix(who)
+				.map(|(asset, schedules)| {
+					let locked = Self::locked_at(now, &schedules);
+					let frozen = T::Freezer::balance_frozen(asset, &reason, who);
+					AssetVestingBalance { asset, locked, claimable: frozen.saturating_sub(locked) }
+				})
+				.collect()
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to query the vesting assets of an account.
+	pub trait AssetVestingApi<AccountId, AssetId, Balance>
+	where
+		AccountId: codec::Codec,
+		AssetId: codec::Codec,
+		Balance: codec::Codec,
+	{
+		/// The locked and claimable amounts of the vesting assets of `who`.
+		fn vesting_balances(who: AccountId) -> Vec<AssetVestingBalance<AssetId, Balance>>;
+	}
+}
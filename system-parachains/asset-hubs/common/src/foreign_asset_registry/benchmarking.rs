@@ -0,0 +1,124 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the foreign asset registry.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::traits::{Currency, EnsureOrigin};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Saturating;
+use sp_std::{boxed::Box, vec};
+
+type CurrencyOf<T> = <T as pallet_assets::Config<ForeignAssetsInstance>>::Currency;
+type StringLimitOf<T> = <T as pallet_assets::Config<ForeignAssetsInstance>>::StringLimit;
+
+/// An asset of a consensus system other than the one of any Relay Chain.
+fn remote_asset() -> MultiLocationForAssetId {
+	MultiLocation::new(2, X2(GlobalConsensus(Ethereum { chain_id: 1 }), GeneralIndex(0)))
+}
+
+/// An account able to pay for a few registrations.
+fn funded_depositor<T: Config>() -> T::AccountId {
+	let depositor: T::AccountId = whitelisted_caller();
+	let balance = T::RegistrationDeposit::get()
+		.saturating_mul(10u32.into())
+		.saturating_add(CurrencyOf::<T>::minimum_balance());
+	CurrencyOf::<T>::make_free_balance_be(&depositor, balance);
+	depositor
+}
+
+/// Register the [`remote_asset`] with a deposit of `depositor`.
+fn register_remote_asset<T: Config>(depositor: &T::AccountId) -> MultiLocationForAssetId {
+	let asset_id = remote_asset();
+	Pallet::<T>::register(
+		RawOrigin::Signed(depositor.clone()).into(),
+		Box::new(asset_id),
+		b"Remote".to_vec(),
+		b"RMT".to_vec(),
+		12,
+		1u32.into(),
+		false,
+		TransferKind::Reserve,
+	)
+	.expect("the remote asset is registered once");
+	asset_id
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn register(
+		n: Linear<1, { StringLimitOf::<T>::get() }>,
+		s: Linear<1, { StringLimitOf::<T>::get() }>,
+	) {
+		let depositor = funded_depositor::<T>();
+		let asset_id = remote_asset();
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(depositor),
+			Box::new(asset_id),
+			vec![b'a'; n as usize],
+			vec![b'A'; s as usize],
+			12,
+			1u32.into(),
+			false,
+			TransferKind::Reserve,
+		);
+
+		assert!(Registrations::<T>::contains_key(asset_id));
+	}
+
+	#[benchmark]
+	fn set_transfer_kind() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegisterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let asset_id = register_remote_asset::<T>(&funded_depositor::<T>());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(asset_id), TransferKind::Teleport);
+
+		let registration = Registrations::<T>::get(asset_id).expect("the asset is registered");
+		assert_eq!(registration.transfer, TransferKind::Teleport);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn deregister() {
+		let depositor = funded_depositor::<T>();
+		let asset_id = register_remote_asset::<T>(&depositor);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(depositor), Box::new(asset_id));
+
+		assert!(!Registrations::<T>::contains_key(asset_id));
+	}
+
+	#[benchmark]
+	fn force_deregister() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegisterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let asset_id = register_remote_asset::<T>(&funded_depositor::<T>());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(asset_id), true);
+
+		assert!(!Registrations::<T>::contains_key(asset_id));
+		Ok(())
+	}
+}
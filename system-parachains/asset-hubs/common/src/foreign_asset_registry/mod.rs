@@ -0,0 +1,454 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Registration of `ForeignAssets` from other consensus systems.
+//!
+//! Sibling parachains create their assets themselves through `ForeignCreators`. Assets of other
+//! consensus systems, such as the other Relay Chain network or Ethereum, have no local origin
+//! which could create them, so they are registered here instead: by governance, or by anyone who
+//! reserves [`Config::RegistrationDeposit`].
+//!
+//! Registered assets are owned by the account of this pallet, so no local account can mint them.
+//! Only governance may register sufficient assets and assets teleported from their consensus
+//! system, as this chain mints the latter on the word of that system. Signed registrations must
+//! keep the minimum balance of the asset at [`Config::MinBalanceMagnitude`] orders of magnitude
+//! below one unit of it, so that nobody can register an asset which cannot be deposited. Every registration states
+//! whether the asset is teleported or reserve-transferred from its consensus system, which
+//! [`RegisteredTeleportableAssets`] and [`RegisteredReserveAssets`] expose to the XCM executor.
+//! Metadata and status of registered assets remain under the force origin of `ForeignAssets`,
+//! which must accept `Root`, as registration creates assets through it.
+//!
+//! A registration ends with [`Pallet::deregister`], by its depositor while nobody holds the
+//! asset, which returns the deposit, or with [`Pallet::force_deregister`], by governance, which
+//! may slash the deposit. Either starts the destruction of the asset in `ForeignAssets`, which
+//! anyone may then complete, after which the asset may be registered again.
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use crate::ForeignAssetsInstance;
+use assets_common::MultiLocationForAssetId;
+use frame_support::{
+	traits::{ContainsPair, Get},
+	weights::Weight,
+};
+pub use pallet_foreign_asset_registry::*;
+use sp_std::marker::PhantomData;
+use xcm::latest::prelude::*;
+
+/// Weight functions of the foreign asset registry.
+pub trait WeightInfo {
+	fn register(n: u32, s: u32) -> Weight;
+	fn set_transfer_kind() -> Weight;
+	fn deregister() -> Weight;
+	fn force_deregister() -> Weight;
+}
+
+/// Weights of the foreign asset registry on top of the weights of `ForeignAssets`, until the
+/// runtime provides benchmarked weights.
+pub struct DbWeightInfo<T>(PhantomData<T>);
+impl<T: Config> WeightInfo for DbWeightInfo<T> {
+	fn register(n: u32, s: u32) -> Weight {
+		use pallet_assets::WeightInfo as _;
+		<T as pallet_assets::Config<ForeignAssetsInstance>>::WeightInfo::force_create()
+			.saturating_add(
+				<T as pallet_assets::Config<ForeignAssetsInstance>>::WeightInfo::force_set_metadata(
+					n, s,
+				),
+			)
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+	fn set_transfer_kind() -> Weight {
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+	fn deregister() -> Weight {
+		use pallet_assets::WeightInfo as _;
+		<T as pallet_assets::Config<ForeignAssetsInstance>>::WeightInfo::start_destroy()
+			.saturating_add(T::DbWeight::get().reads_writes(3, 2))
+	}
+	fn force_deregister() -> Weight {
+		use pallet_assets::WeightInfo as _;
+		<T as pallet_assets::Config<ForeignAssetsInstance>>::WeightInfo::start_destroy()
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet_foreign_asset_registry {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, EnsureOrigin, OnUnbalanced, ReservableCurrency},
+		PalletId,
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use sp_runtime::traits::{AccountIdConversion, Saturating, StaticLookup, Zero};
+	use sp_std::prelude::*;
+
+	type CurrencyOf<T> = <T as pallet_assets::Config<ForeignAssetsInstance>>::Currency;
+
+	/// The balance of the registration deposits.
+	pub type DepositBalanceOf<T> =
+		<CurrencyOf<T> as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The imbalance of a slashed registration deposit.
+	pub type NegativeImbalanceOf<T> =
+		<CurrencyOf<T> as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+	/// How a registered asset is transferred from its consensus system to this chain.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum TransferKind {
+		/// The asset is teleported; its consensus system trusts this chain to mint and burn it.
+		Teleport,
+		/// The asset is held in reserve by its consensus system.
+		Reserve,
+	}
+
+	/// The registration of a foreign asset.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Registration<AccountId, Balance> {
+		/// The account which reserved the deposit, if the asset was not registered by governance.
+		pub depositor: Option<AccountId>,
+		/// The reserved deposit.
+		pub deposit: Balance,
+		/// How the asset is transferred to this chain.
+		pub transfer: TransferKind,
+	}
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ pallet_assets::Config<
+			ForeignAssetsInstance,
+			AssetId = MultiLocationForAssetId,
+			AssetIdParameter = MultiLocationForAssetId,
+		>
+	{
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to register assets without a deposit, to register sufficient and
+		/// teleported assets, to change how registered assets are transferred and to end any
+		/// registration.
+		type RegisterOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// The deposit reserved for a registration by a signed origin.
+		#[pallet::constant]
+		type RegistrationDeposit: Get<DepositBalanceOf<Self>>;
+
+		/// Handler for the slashed registration deposits.
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The maximum number of decimals of a registered asset.
+		#[pallet::constant]
+		type MaxDecimals: Get<u8>;
+
+		/// How many orders of magnitude below one unit of the asset the minimum balance of a
+		/// signed registration must at least be.
+		#[pallet::constant]
+		type MinBalanceMagnitude: Get<u8>;
+
+		/// The location of this chain, to tell assets of other consensus systems.
+		type UniversalLocation: Get<InteriorMultiLocation>;
+
+		/// The identifier of the account owning the registered assets.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The registrations of foreign assets, by asset location.
+	#[pallet::storage]
+	pub type Registrations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		MultiLocationForAssetId,
+		Registration<T::AccountId, DepositBalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A foreign asset was registered, by `depositor` or, if `None`, by governance.
+		Registered {
+			asset_id: MultiLocationForAssetId,
+			depositor: Option<T::AccountId>,
+			transfer: TransferKind,
+		},
+		/// How a registered asset is transferred was changed.
+		TransferKindSet { asset_id: MultiLocationForAssetId, transfer: TransferKind },
+		/// The registration of a foreign asset ended and the destruction of the asset started.
+		/// The deposit was returned to `depositor`, less the `slashed` amount.
+		Deregistered {
+			asset_id: MultiLocationForAssetId,
+			depositor: Option<T::AccountId>,
+			slashed: DepositBalanceOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The asset does not belong to another consensus system.
+		NotRemoteConsensus,
+		/// The name or the symbol of the asset is empty.
+		EmptyMetadata,
+		/// The name of the asset is not text or contains control characters, or the symbol
+		/// contains other characters than printable ASCII ones.
+		UnprintableMetadata,
+		/// The asset has more than `MaxDecimals` decimals.
+		TooManyDecimals,
+		/// Only governance may register sufficient assets.
+		SufficientNeedsGovernance,
+		/// The minimum balance is too high for a signed registration.
+		MinBalanceTooHigh,
+		/// Only governance may register teleported assets.
+		TeleportNeedsGovernance,
+		/// The asset is not registered.
+		NotRegistered,
+		/// The origin is not the depositor of the registration.
+		NotDepositor,
+		/// The asset is still held by some account.
+		InUse,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the asset of another consensus system at `asset_id` as a foreign asset with
+		/// the given metadata and minimum balance.
+		///
+		/// Governance registers for free; a signed origin reserves `RegistrationDeposit` and may
+		/// only register reserve-transferred assets which are not sufficient, with a minimum
+		/// balance of at most `10^(decimals - MinBalanceMagnitude)`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::register(name.len() as u32, symbol.len() as u32))]
+		pub fn register(
+			origin: OriginFor<T>,
+			asset_id: Box<MultiLocationForAssetId>,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			min_balance: T::Balance,
+			is_sufficient: bool,
+			transfer: TransferKind,
+		) -> DispatchResult {
+			let depositor = match T::RegisterOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+			let asset_id = *asset_id;
+			ensure!(Self::is_remote(&asset_id), Error::<T>::NotRemoteConsensus);
+			ensure!(!name.is_empty() && !symbol.is_empty(), Error::<T>::EmptyMetadata);
+			ensure!(Self::is_printable(&name, &symbol), Error::<T>::UnprintableMetadata);
+			ensure!(decimals <= T::MaxDecimals::get(), Error::<T>::TooManyDecimals);
+			ensure!(depositor.is_none() || !is_sufficient, Error::<T>::SufficientNeedsGovernance);
+			ensure!(
+				depositor.is_none() || min_balance <= Self::max_signed_min_balance(decimals),
+				Error::<T>::MinBalanceTooHigh
+			);
+			ensure!(
+				depositor.is_none() || transfer != TransferKind::Teleport,
+				Error::<T>::TeleportNeedsGovernance
+			);
+
+			let deposit = match depositor {
+				Some(ref who) => {
+					let deposit = T::RegistrationDeposit::get();
+					CurrencyOf::<T>::reserve(who, deposit)?;
+					deposit
+				},
+				None => Zero::zero(),
+			};
+			pallet_assets::Pallet::<T, ForeignAssetsInstance>::force_create(
+				RawOrigin::Root.into(),
+				asset_id,
+				T::Lookup::unlookup(Self::account_id()),
+				is_sufficient,
+				min_balance,
+			)?;
+			pallet_assets::Pallet::<T, ForeignAssetsInstance>::force_set_metadata(
+				RawOrigin::Root.into(),
+				asset_id,
+				name,
+				symbol,
+				decimals,
+				false,
+			)?;
+
+			Registrations::<T>::insert(
+				asset_id,
+				Registration { depositor: depositor.clone(), deposit, transfer },
+			);
+			Self::deposit_event(Event::<T>::Registered { asset_id, depositor, transfer });
+			Ok(())
+		}
+
+		/// Set how the registered asset at `asset_id` is transferred to this chain.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_transfer_kind())]
+		pub fn set_transfer_kind(
+			origin: OriginFor<T>,
+			asset_id: Box<MultiLocationForAssetId>,
+			transfer: TransferKind,
+		) -> DispatchResult {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			let asset_id = *asset_id;
+			Registrations::<T>::try_mutate(asset_id, |registration| {
+				let registration = registration.as_mut().ok_or(Error::<T>::NotRegistered)?;
+				registration.transfer = transfer;
+				Ok::<_, DispatchError>(())
+			})?;
+			Self::deposit_event(Event::<T>::TransferKindSet { asset_id, transfer });
+			Ok(())
+		}
+
+		/// End the registration of the asset at `asset_id`, which the origin registered, and
+		/// return the deposit.
+		///
+		/// Nobody may hold the asset. The destruction of the asset starts.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::deregister())]
+		pub fn deregister(
+			origin: OriginFor<T>,
+			asset_id: Box<MultiLocationForAssetId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let asset_id = *asset_id;
+			let registration =
+				Registrations::<T>::get(asset_id).ok_or(Error::<T>::NotRegistered)?;
+			ensure!(registration.depositor.as_ref() == Some(&who), Error::<T>::NotDepositor);
+			ensure!(
+				pallet_assets::Pallet::<T, ForeignAssetsInstance>::total_supply(asset_id).is_zero(),
+				Error::<T>::InUse
+			);
+			Self::end_registration(asset_id, registration, false)
+		}
+
+		/// End the registration of the asset at `asset_id` and return the deposit, unless
+		/// `slash`, in which case it goes to `Slashed`.
+		///
+		/// The destruction of the asset starts, whether it is held or not.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::force_deregister())]
+		pub fn force_deregister(
+			origin: OriginFor<T>,
+			asset_id: Box<MultiLocationForAssetId>,
+			slash: bool,
+		) -> DispatchResult {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			let asset_id = *asset_id;
+			let registration =
+				Registrations::<T>::get(asset_id).ok_or(Error::<T>::NotRegistered)?;
+			Self::end_registration(asset_id, registration, slash)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account owning the registered assets.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Whether `location` belongs to a consensus system other than the one of this chain.
+		pub fn is_remote(location: &MultiLocationForAssetId) -> bool {
+			let local = T::UniversalLocation::get().global_consensus().ok();
+			location.parents == 2 &&
+				matches!(
+					location.first_interior(),
+					Some(GlobalConsensus(network)) if Some(*network) != local
+				)
+		}
+
+		/// The highest minimum balance of an asset with `decimals` registered by a signed origin.
+		pub fn max_signed_min_balance(decimals: u8) -> T::Balance {
+			let magnitude = decimals.saturating_sub(T::MinBalanceMagnitude::get());
+			T::Balance::from(10u32).saturating_pow(magnitude as usize)
+		}
+
+		/// Whether `name` is text without control characters and `symbol` is printable ASCII.
+		fn is_printable(name: &[u8], symbol: &[u8]) -> bool {
+			let name_is_printable = sp_std::str::from_utf8(name)
+				.map_or(false, |name| !name.chars().any(char::is_control));
+			name_is_printable && symbol.iter().all(u8::is_ascii_graphic)
+		}
+
+		/// Whether `asset` is registered with `transfer` and `origin` is, or is within, the
+		/// consensus system of `asset`.
+		pub fn is_registered_from(
+			asset: &MultiAsset,
+			origin: &MultiLocation,
+			transfer: TransferKind,
+		) -> bool {
+			let Concrete(asset_id) = &asset.id else { return false };
+			Self::is_remote(origin) &&
+				asset_id.starts_with(origin) &&
+				Registrations::<T>::get(asset_id)
+					.map_or(false, |registration| registration.transfer == transfer)
+		}
+
+		/// Remove the `registration` of `asset_id`, return or `slash` its deposit and start the
+		/// destruction of the asset.
+		fn end_registration(
+			asset_id: MultiLocationForAssetId,
+			registration: Registration<T::AccountId, DepositBalanceOf<T>>,
+			slash: bool,
+		) -> DispatchResult {
+			pallet_assets::Pallet::<T, ForeignAssetsInstance>::start_destroy(
+				RawOrigin::Root.into(),
+				asset_id,
+			)?;
+			Registrations::<T>::remove(asset_id);
+
+			let mut slashed = Zero::zero();
+			if let Some(ref depositor) = registration.depositor {
+				if slash {
+					let (imbalance, unslashed) =
+						CurrencyOf::<T>::slash_reserved(depositor, registration.deposit);
+					slashed = registration.deposit.saturating_sub(unslashed);
+					T::Slashed::on_unbalanced(imbalance);
+				} else {
+					CurrencyOf::<T>::unreserve(depositor, registration.deposit);
+				}
+			}
+			Self::deposit_event(Event::<T>::Deregistered {
+				asset_id,
+				depositor: registration.depositor,
+				slashed,
+			});
+			Ok(())
+		}
+	}
+}
+
+/// Registered assets teleported from their consensus system.
+pub struct RegisteredTeleportableAssets<T>(PhantomData<T>);
+impl<T: Config> ContainsPair<MultiAsset, MultiLocation> for RegisteredTeleportableAssets<T> {
+	fn contains(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		Pallet::<T>::is_registered_from(asset, origin, TransferKind::Teleport)
+	}
+}
+
+/// Registered assets held in reserve by their consensus system.
+pub struct RegisteredReserveAssets<T>(PhantomData<T>);
+impl<T: Config> ContainsPair<MultiAsset, MultiLocation> for RegisteredReserveAssets<T> {
+	fn contains(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		Pallet::<T>::is_registered_from(asset, origin, TransferKind::Reserve)
+	}
+}
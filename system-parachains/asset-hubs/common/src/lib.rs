@@ -0,0 +1,28 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pallets and XCM adapters shared by the Asset Hubs of Polkadot and Kusama.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod asset_balances_api;
+pub mod asset_locks;
+pub mod asset_vesting;
+pub mod foreign_asset_registry;
+pub mod nft_locks;
+pub mod nft_transactor;
+
+/// The instance of `pallet_assets` holding the assets of other locations on the Asset Hubs.
+pub type ForeignAssetsInstance = pallet_assets::Instance2;
@@ -0,0 +1,265 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Locks on the items of an NFT pallet.
+//!
+//! An instance of this pallet is the `Locker` of `Uniques` or `Nfts`. Other pallets and the XCM
+//! configuration lock an item with an [`NftLockReason`] of their own, for as long as they use the
+//! item elsewhere, usually through an [`ItemLock`]:
+//! - `NftFractionalization` locks the items it fractionalizes, through [`LockOnDisabledTransfer`];
+//! - the XCM transactors of the items lock the items held by the sovereign accounts of other
+//!   locations, which minted derivatives of them.
+//!
+//! An item is locked while it has any lock. Its owner can then neither transfer it, which the NFT
+//! pallets check with their `Locker`, nor burn or destroy it, which the runtime filters.
+//!
+//! [`NftLocksApi`] exposes the locks of an item.
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{tokens::nonfungibles_v2, Locker},
+};
+pub use pallet_nft_locks::*;
+use sp_std::prelude::*;
+
+/// The reasons for which the items of `Uniques` and `Nfts` are locked.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum NftLockReason {
+	/// The item is fractionalized by `NftFractionalization`.
+	#[codec(index = 0)]
+	Fractionalized,
+	/// The item is held by the sovereign account of another location, which minted a derivative
+	/// of it.
+	#[codec(index = 1)]
+	XcmReserve,
+}
+
+#[frame_support::pallet]
+pub mod pallet_nft_locks {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Identifier of a collection of the NFT pallet.
+		type CollectionId: Member + Parameter + Copy + MaxEncodedLen;
+
+		/// Identifier of an item within a collection of the NFT pallet.
+		type ItemId: Member + Parameter + Copy + MaxEncodedLen;
+
+		/// The reason of a lock.
+		type LockReason: Member + Parameter + Copy + MaxEncodedLen;
+
+		/// The maximum number of locks on an item.
+		#[pallet::constant]
+		type MaxLocks: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	/// The reasons for which items are locked, by collection and item.
+	#[pallet::storage]
+	pub type Locks<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		BoundedVec<T::LockReason, T::MaxLocks>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// `item` of `collection` was locked for `reason`.
+		Locked { collection: T::CollectionId, item: T::ItemId, reason: T::LockReason },
+		/// The lock of `item` of `collection` for `reason` was removed.
+		Unlocked { collection: T::CollectionId, item: T::ItemId, reason: T::LockReason },
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The item is locked for the reason already.
+		AlreadyLocked,
+		/// The item has `MaxLocks` locks already.
+		TooManyLocks,
+		/// The item is not locked for the reason.
+		NotLocked,
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Lock `item` of `collection` for `reason`.
+		pub fn lock(
+			collection: T::CollectionId,
+			item: T::ItemId,
+			reason: T::LockReason,
+		) -> DispatchResult {
+			Locks::<T, I>::try_mutate(collection, item, |locks| {
+				ensure!(!locks.contains(&reason), Error::<T, I>::AlreadyLocked);
+				locks.try_push(reason).map_err(|_| Error::<T, I>::TooManyLocks)
+			})?;
+			Self::deposit_event(Event::<T, I>::Locked { collection, item, reason });
+			Ok(())
+		}
+
+		/// Remove the lock of `item` of `collection` for `reason`.
+		pub fn unlock(
+			collection: T::CollectionId,
+			item: T::ItemId,
+			reason: T::LockReason,
+		) -> DispatchResult {
+			Locks::<T, I>::try_mutate_exists(collection, item, |maybe_locks| {
+				let locks = maybe_locks.as_mut().ok_or(Error::<T, I>::NotLocked)?;
+				let position =
+					locks.iter().position(|r| *r == reason).ok_or(Error::<T, I>::NotLocked)?;
+				locks.remove(position);
+				if locks.is_empty() {
+					*maybe_locks = None;
+				}
+				Ok::<_, DispatchError>(())
+			})?;
+			Self::deposit_event(Event::<T, I>::Unlocked { collection, item, reason });
+			Ok(())
+		}
+
+		/// The reasons for which `item` of `collection` is locked.
+		pub fn locks(collection: T::CollectionId, item: T::ItemId) -> Vec<T::LockReason> {
+			Locks::<T, I>::get(collection, item).into_inner()
+		}
+
+		/// Whether any item of `collection` is locked.
+		pub fn has_locked_items(collection: T::CollectionId) -> bool {
+			Locks::<T, I>::iter_prefix(collection).next().is_some()
+		}
+	}
+}
+
+/// A lock on the items of an NFT pallet for a single reason.
+pub trait ItemLock<CollectionId, ItemId> {
+	/// Whether `item` of `collection` has the lock.
+	fn is_locked(collection: &CollectionId, item: &ItemId) -> bool;
+
+	/// Lock `item` of `collection`.
+	fn lock(collection: &CollectionId, item: &ItemId) -> DispatchResult;
+
+	/// Remove the lock of `item` of `collection`.
+	fn unlock(collection: &CollectionId, item: &ItemId) -> DispatchResult;
+}
+
+/// The lock of the `nft_locks` instance `I` for `Reason`.
+pub struct LockFor<T, I, Reason>(PhantomData<(T, I, Reason)>);
+impl<T: Config<I>, I: 'static, Reason: Get<T::LockReason>> ItemLock<T::CollectionId, T::ItemId>
+	for LockFor<T, I, Reason>
+{
+	fn is_locked(collection: &T::CollectionId, item: &T::ItemId) -> bool {
+		Locks::<T, I>::get(collection, item).contains(&Reason::get())
+	}
+
+	fn lock(collection: &T::CollectionId, item: &T::ItemId) -> DispatchResult {
+		Pallet::<T, I>::lock(*collection, *item, Reason::get())
+	}
+
+	fn unlock(collection: &T::CollectionId, item: &T::ItemId) -> DispatchResult {
+		Pallet::<T, I>::unlock(*collection, *item, Reason::get())
+	}
+}
+
+/// `Items`, whose `disable_transfer` and `enable_transfer` also lock and unlock the item with
+/// `Lock`, such that it can neither be transferred nor burnt while its transfers are disabled.
+///
+/// Only the owner and the plain attributes of the items are inspected through it, which is all
+/// `NftFractionalization` needs.
+pub struct LockOnDisabledTransfer<Items, Lock>(PhantomData<(Items, Lock)>);
+impl<AccountId, Items, Lock> nonfungibles_v2::Inspect<AccountId>
+	for LockOnDisabledTransfer<Items, Lock>
+where
+	Items: nonfungibles_v2::Inspect<AccountId>,
+	Lock: ItemLock<Items::CollectionId, Items::ItemId>,
+{
+	type ItemId = Items::ItemId;
+	type CollectionId = Items::CollectionId;
+
+	fn owner(collection: &Self::CollectionId, item: &Self::ItemId) -> Option<AccountId> {
+		Items::owner(collection, item)
+	}
+
+	fn collection_owner(collection: &Self::CollectionId) -> Option<AccountId> {
+		Items::collection_owner(collection)
+	}
+
+	fn attribute(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		key: &[u8],
+	) -> Option<Vec<u8>> {
+		Items::attribute(collection, item, key)
+	}
+
+	fn can_transfer(collection: &Self::CollectionId, item: &Self::ItemId) -> bool {
+		Items::can_transfer(collection, item)
+	}
+}
+
+impl<AccountId, Items, Lock> nonfungibles_v2::Transfer<AccountId>
+	for LockOnDisabledTransfer<Items, Lock>
+where
+	Items: nonfungibles_v2::Transfer<AccountId>,
+	Lock: ItemLock<Items::CollectionId, Items::ItemId>,
+{
+	fn transfer(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		destination: &AccountId,
+	) -> DispatchResult {
+		Items::transfer(collection, item, destination)
+	}
+
+	fn disable_transfer(collection: &Self::CollectionId, item: &Self::ItemId) -> DispatchResult {
+		Items::disable_transfer(collection, item)?;
+		Lock::lock(collection, item)
+	}
+
+	fn enable_transfer(collection: &Self::CollectionId, item: &Self::ItemId) -> DispatchResult {
+		Lock::unlock(collection, item)?;
+		Items::enable_transfer(collection, item)
+	}
+}
+
+impl<T: Config<I>, I: 'static> Locker<T::CollectionId, T::ItemId> for Pallet<T, I> {
+	fn is_locked(collection: T::CollectionId, item: T::ItemId) -> bool {
+		Locks::<T, I>::contains_key(collection, item)
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to query the locks of the items of `Uniques` and `Nfts`.
+	pub trait NftLocksApi<CollectionId, ItemId, LockReason>
+	where
+		CollectionId: codec::Codec,
+		ItemId: codec::Codec,
+		LockReason: codec::Codec,
+	{
+		/// The reasons for which `item` of `collection` of `Uniques` is locked.
+		fn uniques_locks(collection: CollectionId, item: ItemId) -> Vec<LockReason>;
+
+		/// The reasons for which `item` of `collection` of `Nfts` is locked.
+		fn nfts_locks(collection: CollectionId, item: ItemId) -> Vec<LockReason>;
+	}
+}
@@ -0,0 +1,112 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the item transactors, for the [`WeightInfo`](super::WeightInfo) of the XCM
+//! weigher.
+//!
+//! Like `pallet_xcm_benchmarks`, this is a pallet only so that the runtime can list it among its
+//! benchmarks; it is not part of the runtime. Every benchmark moves an item held by a sibling
+//! parachain, so that its reserve lock is removed and, if it goes to another location, set again.
+
+use frame_benchmarking::v2::*;
+pub use pallet::*;
+use xcm::latest::prelude::*;
+use xcm_executor::traits::TransactAsset;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The item transactor to benchmark.
+		type TransactAsset: TransactAsset;
+
+		/// Create an item held by the escrow account of `TransactAsset` and return it.
+		fn escrowed_item() -> Result<MultiAsset, BenchmarkError>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+}
+
+/// The sibling parachain with the given `index`.
+fn sibling(index: u32) -> MultiLocation {
+	MultiLocation::new(1, X1(Parachain(2000 + index)))
+}
+
+fn context() -> XcmContext {
+	XcmContext { origin: None, message_id: [0; 32], topic: None }
+}
+
+/// Create an item and deposit it with the sibling parachain `0`, which locks it.
+fn item_of_sibling<T: Config>() -> Result<MultiAsset, BenchmarkError> {
+	let item = T::escrowed_item()?;
+	T::TransactAsset::deposit_asset(&item, &sibling(0), &context())
+		.map_err(|_| BenchmarkError::Stop("the escrowed item is deposited"))?;
+	Ok(item)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn withdraw_item() -> Result<(), BenchmarkError> {
+		let item = item_of_sibling::<T>()?;
+		let result;
+
+		#[block]
+		{
+			result = T::TransactAsset::withdraw_asset(&item, &sibling(0), None);
+		}
+
+		assert!(result.is_ok());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn deposit_item() -> Result<(), BenchmarkError> {
+		let item = T::escrowed_item()?;
+		let result;
+
+		#[block]
+		{
+			result = T::TransactAsset::deposit_asset(&item, &sibling(0), &context());
+		}
+
+		assert!(result.is_ok());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_item() -> Result<(), BenchmarkError> {
+		let item = item_of_sibling::<T>()?;
+		let result;
+
+		#[block]
+		{
+			result = T::TransactAsset::internal_transfer_asset(
+				&item,
+				&sibling(0),
+				&sibling(1),
+				&context(),
+			);
+		}
+
+		assert!(result.is_ok());
+		Ok(())
+	}
+}
@@ -0,0 +1,244 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transactors for the items of `Uniques` and `Nfts`.
+//!
+//! This chain is the reserve of its NFTs, so items are never burnt or minted over XCM. Withdrawn
+//! items are moved into an escrow account, and deposited items are moved out of it again. A
+//! reserve transfer moves the item to the sovereign account of the destination, which mints a
+//! derivative of it in a collection of its own; when the derivative is sent back, the item is
+//! withdrawn from the sovereign account and deposited with the beneficiary. Items held by the
+//! sovereign accounts of other locations are locked with `ReserveLock` meanwhile, so that these
+//! locations can only move them over XCM. Items locked otherwise cannot be withdrawn or
+//! transferred.
+//!
+//! `xcm_builder::NonFungiblesAdapter` burns and mints items instead, losing their metadata and
+//! attributes, and only supports `nonfungibles` (v1), which `Nfts` does not implement.
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+use crate::nft_locks::ItemLock;
+use frame_support::{
+	storage::with_storage_layer,
+	traits::{
+		tokens::{nonfungibles, nonfungibles_v2},
+		Get,
+	},
+	weights::Weight,
+};
+use sp_runtime::{DispatchError, DispatchResult};
+use sp_std::marker::PhantomData;
+use xcm::latest::prelude::*;
+use xcm_executor::{
+	traits::{ConvertLocation, MatchesNonFungibles, TransactAsset},
+	Assets,
+};
+
+/// Weights of the transactors, for the XCM weigher to charge per item.
+pub trait WeightInfo {
+	/// Withdraw an item into escrow.
+	fn withdraw_item() -> Weight;
+	/// Deposit an item from escrow, locking it if it is deposited with another location.
+	fn deposit_item() -> Weight;
+	/// Transfer an item between two locations, locking it if it moves to another location.
+	fn transfer_item() -> Weight;
+}
+
+/// Weights of the transactors from the storage accesses of `Uniques` and `Nfts` transfers and of
+/// the reserve lock only, until the runtime provides weights benchmarked with [`benchmarking`].
+pub struct DbWeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for DbWeightInfo<T> {
+	fn withdraw_item() -> Weight {
+		T::DbWeight::get().reads_writes(6, 5)
+	}
+	fn deposit_item() -> Weight {
+		T::DbWeight::get().reads_writes(6, 6)
+	}
+	fn transfer_item() -> Weight {
+		T::DbWeight::get().reads_writes(6, 6)
+	}
+}
+
+/// Transacts the items of a `nonfungibles` implementation, such as `Uniques`.
+pub struct NonFungiblesEscrowAdapter<
+	Items,
+	Matcher,
+	AccountIdConverter,
+	AccountId,
+	EscrowAccount,
+	ReserveLock,
+>(PhantomData<(Items, Matcher, AccountIdConverter, AccountId, EscrowAccount, ReserveLock)>);
+impl<
+		Items: nonfungibles::Inspect<AccountId> + nonfungibles::Transfer<AccountId>,
+		Matcher: MatchesNonFungibles<Items::CollectionId, Items::ItemId>,
+		AccountIdConverter: ConvertLocation<AccountId>,
+		AccountId: Clone + Eq,
+		EscrowAccount: Get<AccountId>,
+		ReserveLock: ItemLock<Items::CollectionId, Items::ItemId>,
+	> TransactAsset
+	for NonFungiblesEscrowAdapter<
+		Items,
+		Matcher,
+		AccountIdConverter,
+		AccountId,
+		EscrowAccount,
+		ReserveLock,
+	>
+{
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, _context: &XcmContext) -> XcmResult {
+		let (collection, item) = Matcher::matches_nonfungibles(what)?;
+		let to = convert_location::<AccountIdConverter, _>(who)?;
+		ensure_owner(Items::owner(&collection, &item), &EscrowAccount::get())?;
+		move_item::<ReserveLock, _, _>(&collection, &item, who, || {
+			Items::transfer(&collection, &item, &to)
+		})
+	}
+
+	fn withdraw_asset(
+		what: &MultiAsset,
+		who: &MultiLocation,
+		_maybe_context: Option<&XcmContext>,
+	) -> Result<Assets, XcmError> {
+		let (collection, item) = Matcher::matches_nonfungibles(what)?;
+		let from = convert_location::<AccountIdConverter, _>(who)?;
+		ensure_owner(Items::owner(&collection, &item), &from)?;
+		move_item::<ReserveLock, _, _>(&collection, &item, &MultiLocation::here(), || {
+			Items::transfer(&collection, &item, &EscrowAccount::get())
+		})?;
+		Ok(what.clone().into())
+	}
+
+	fn internal_transfer_asset(
+		what: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+		_context: &XcmContext,
+	) -> Result<Assets, XcmError> {
+		let (collection, item) = Matcher::matches_nonfungibles(what)?;
+		let from = convert_location::<AccountIdConverter, _>(from)?;
+		let to_account = convert_location::<AccountIdConverter, _>(to)?;
+		ensure_owner(Items::owner(&collection, &item), &from)?;
+		move_item::<ReserveLock, _, _>(&collection, &item, to, || {
+			Items::transfer(&collection, &item, &to_account)
+		})?;
+		Ok(what.clone().into())
+	}
+}
+
+/// Transacts the items of a `nonfungibles_v2` implementation, such as `Nfts`.
+pub struct NonFungiblesV2EscrowAdapter<
+	Items,
+	Matcher,
+	AccountIdConverter,
+	AccountId,
+	EscrowAccount,
+	ReserveLock,
+>(PhantomData<(Items, Matcher, AccountIdConverter, AccountId, EscrowAccount, ReserveLock)>);
+impl<
+		Items: nonfungibles_v2::Inspect<AccountId> + nonfungibles_v2::Transfer<AccountId>,
+		Matcher: MatchesNonFungibles<Items::CollectionId, Items::ItemId>,
+		AccountIdConverter: ConvertLocation<AccountId>,
+		AccountId: Clone + Eq,
+		EscrowAccount: Get<AccountId>,
+		ReserveLock: ItemLock<Items::CollectionId, Items::ItemId>,
+	> TransactAsset
+	for NonFungiblesV2EscrowAdapter<
+		Items,
+		Matcher,
+		AccountIdConverter,
+		AccountId,
+		EscrowAccount,
+		ReserveLock,
+	>
+{
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, _context: &XcmContext) -> XcmResult {
+		let (collection, item) = Matcher::matches_nonfungibles(what)?;
+		let to = convert_location::<AccountIdConverter, _>(who)?;
+		ensure_owner(Items::owner(&collection, &item), &EscrowAccount::get())?;
+		move_item::<ReserveLock, _, _>(&collection, &item, who, || {
+			Items::transfer(&collection, &item, &to)
+		})
+	}
+
+	fn withdraw_asset(
+		what: &MultiAsset,
+		who: &MultiLocation,
+		_maybe_context: Option<&XcmContext>,
+	) -> Result<Assets, XcmError> {
+		let (collection, item) = Matcher::matches_nonfungibles(what)?;
+		let from = convert_location::<AccountIdConverter, _>(who)?;
+		ensure_owner(Items::owner(&collection, &item), &from)?;
+		move_item::<ReserveLock, _, _>(&collection, &item, &MultiLocation::here(), || {
+			Items::transfer(&collection, &item, &EscrowAccount::get())
+		})?;
+		Ok(what.clone().into())
+	}
+
+	fn internal_transfer_asset(
+		what: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+		_context: &XcmContext,
+	) -> Result<Assets, XcmError> {
+		let (collection, item) = Matcher::matches_nonfungibles(what)?;
+		let from = convert_location::<AccountIdConverter, _>(from)?;
+		let to_account = convert_location::<AccountIdConverter, _>(to)?;
+		ensure_owner(Items::owner(&collection, &item), &from)?;
+		move_item::<ReserveLock, _, _>(&collection, &item, to, || {
+			Items::transfer(&collection, &item, &to_account)
+		})?;
+		Ok(what.clone().into())
+	}
+}
+
+/// Move `item` of `collection` to `to` with `transfer`, which fails for locked items, removing
+/// the `ReserveLock` of the item first and locking it again if `to` is another location.
+fn move_item<ReserveLock: ItemLock<CollectionId, ItemId>, CollectionId, ItemId>(
+	collection: &CollectionId,
+	item: &ItemId,
+	to: &MultiLocation,
+	transfer: impl FnOnce() -> DispatchResult,
+) -> XcmResult {
+	with_storage_layer(|| {
+		if ReserveLock::is_locked(collection, item) {
+			ReserveLock::unlock(collection, item)?;
+		}
+		transfer()?;
+		if to.parents > 0 {
+			ReserveLock::lock(collection, item)?;
+		}
+		Ok::<_, DispatchError>(())
+	})
+	.map_err(|_| transfer_failed())
+}
+
+fn convert_location<AccountIdConverter: ConvertLocation<AccountId>, AccountId>(
+	location: &MultiLocation,
+) -> Result<AccountId, XcmError> {
+	AccountIdConverter::convert_location(location)
+		.ok_or(XcmError::FailedToTransactAsset("AccountIdConversionFailed"))
+}
+
+fn ensure_owner<AccountId: Eq>(owner: Option<AccountId>, who: &AccountId) -> XcmResult {
+	match owner {
+		Some(owner) if owner == *who => Ok(()),
+		_ => Err(XcmError::AssetNotFound),
+	}
+}
+
+fn transfer_failed() -> XcmError {
+	XcmError::FailedToTransactAsset("ItemTransferFailed")
+}
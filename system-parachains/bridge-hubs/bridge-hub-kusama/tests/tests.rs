@@ -55,3 +55,36 @@ bridge_hub_test_utils::test_cases::include_teleports_for_native_asset_works!(
 	}),
 	1002
 );
+
+#[test]
+fn async_backing_config_matches_relay_chain() {
+	use cumulus_pallet_aura_ext::FixedVelocityConsensusHook;
+	use frame_support::traits::Get;
+	use kusama_runtime_constants::{
+		async_backing::ASYNC_BACKING_PARAMS, time::MILLISECS_PER_BLOCK,
+	};
+
+	trait HookParams {
+		const PARAMS: (u32, u32, u32);
+	}
+	impl<T, const R: u32, const V: u32, const C: u32> HookParams
+		for FixedVelocityConsensusHook<T, R, V, C>
+	{
+		const PARAMS: (u32, u32, u32) = (R, V, C);
+	}
+	type Hook = <Runtime as cumulus_pallet_parachain_system::Config>::ConsensusHook;
+	let (relay_slot_duration, velocity, capacity) = <Hook as HookParams>::PARAMS;
+
+	assert_eq!(relay_slot_duration as u64, MILLISECS_PER_BLOCK);
+	// Blocks are not authored faster than the consensus hook accepts them.
+	assert!(
+		2 * <Runtime as pallet_timestamp::Config>::MinimumPeriod::get() >=
+			(relay_slot_duration / velocity) as u64
+	);
+	// A block built on the oldest allowed relay parent is still not included while the blocks
+	// built on the following relay parents are authored.
+	assert!(capacity >= velocity * (ASYNC_BACKING_PARAMS.allowed_ancestry_len + 1));
+	// The Relay Chain does not back more than `max_candidate_depth + 1` candidates of a para
+	// building on top of each other.
+	assert!(capacity <= ASYNC_BACKING_PARAMS.max_candidate_depth + 1);
+}
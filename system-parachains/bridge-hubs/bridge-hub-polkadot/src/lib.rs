@@ -69,10 +69,11 @@ use weights::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight};
 
 use parachains_common::{
 	impls::DealWithFees,
-	polkadot::{consensus::*, currency::*, fee::WeightToFee},
+	polkadot::{currency::*, fee::WeightToFee},
 	AccountId, Balance, BlockNumber, Hash, Header, Nonce, Signature, AVERAGE_ON_INITIALIZE_RATIO,
-	HOURS, MAXIMUM_BLOCK_WEIGHT, NORMAL_DISPATCH_RATIO, SLOT_DURATION,
+	HOURS, NORMAL_DISPATCH_RATIO, SLOT_DURATION,
 };
+use system_parachains_constants::{consensus::*, MAXIMUM_BLOCK_WEIGHT};
 // XCM Imports
 use xcm::latest::prelude::BodyId;
 use xcm_executor::XcmExecutor;
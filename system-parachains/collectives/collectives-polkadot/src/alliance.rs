@@ -0,0 +1,293 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Alliance membership criteria.
+//!
+//! Identities of Alliance applicants are registered and judged on the Relay Chain, which this
+//! chain cannot read. The facts of an identity relevant to the Alliance are therefore cached in
+//! the [`pallet_alliance_identity`] pallet by the Relay Chain governance, which sends them with an
+//! XCM `Transact` executed as Root. [`CachedIdentityVerifier`] checks Alliance applicants against
+//! this cache and the unscrupulous websites of the Alliance, while [`AllianceProposalFilter`]
+//! rejects nominating or elevating unscrupulous accounts.
+
+use crate::{AccountId, Runtime, RuntimeCall};
+use frame_support::traits::Contains;
+use pallet_alliance::{IdentityVerifier, UnscrupulousAccounts, UnscrupulousWebsites};
+pub use pallet_alliance_identity::*;
+use sp_runtime::traits::StaticLookup;
+
+/// `pallet_identity::IdentityField::Web` as a bit of an identity field bitmask.
+const IDENTITY_FIELD_WEB: u64 = 1 << 2;
+
+/// Weight functions needed for `pallet_alliance_identity`.
+pub trait WeightInfo {
+	fn note_identity() -> frame_support::weights::Weight;
+	fn clear_identity() -> frame_support::weights::Weight;
+}
+
+#[frame_support::pallet]
+pub mod pallet_alliance_identity {
+	use super::WeightInfo;
+	use cumulus_primitives_core::relay_chain::BlockNumber as RelayBlockNumber;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to note and clear cached identities.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum length of a website URL.
+		#[pallet::constant]
+		type MaxWebsiteUrlLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The facts of a Relay Chain identity relevant to the Alliance.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(MaxWebsiteUrlLength))]
+	pub struct IdentityProof<AccountId, MaxWebsiteUrlLength: Get<u32>> {
+		/// The fields set on the identity, as a bitmask of `pallet_identity::IdentityField`.
+		pub fields: u64,
+		/// The website of the identity, if it was verified by the judging registrar.
+		pub web: Option<BoundedVec<u8, MaxWebsiteUrlLength>>,
+		/// Whether the identity has a `Reasonable` or `KnownGood` judgement and no negative one.
+		pub good_judgement: bool,
+		/// The account of the parent identity, if this is a sub-identity.
+		pub super_account: Option<AccountId>,
+		/// The Relay Chain block at which the identity was read.
+		pub relay_block: RelayBlockNumber,
+	}
+
+	pub type IdentityProofOf<T> =
+		IdentityProof<<T as frame_system::Config>::AccountId, <T as Config>::MaxWebsiteUrlLength>;
+
+	/// The cached identities of the Relay Chain accounts.
+	#[pallet::storage]
+	pub type Identities<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, IdentityProofOf<T>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The identity of an account was noted, as of a Relay Chain block.
+		IdentityNoted { who: T::AccountId, relay_block: RelayBlockNumber },
+		/// The identity of an account was cleared.
+		IdentityCleared { who: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The noted identity was read before the one already cached.
+		StaleIdentity,
+		/// No identity is cached for the account.
+		NoIdentity,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Note the identity of `who`, as read on the Relay Chain at `proof.relay_block`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::note_identity())]
+		pub fn note_identity(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			proof: IdentityProofOf<T>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(cached) = Identities::<T>::get(&who) {
+				ensure!(cached.relay_block <= proof.relay_block, Error::<T>::StaleIdentity);
+			}
+			let relay_block = proof.relay_block;
+			Identities::<T>::insert(&who, proof);
+			Self::deposit_event(Event::<T>::IdentityNoted { who, relay_block });
+			Ok(())
+		}
+
+		/// Clear the identity of `who`, e.g. after it was cleared or killed on the Relay Chain.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::clear_identity())]
+		pub fn clear_identity(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Identities::<T>::contains_key(&who), Error::<T>::NoIdentity);
+			Identities::<T>::remove(&who);
+			Self::deposit_event(Event::<T>::IdentityCleared { who });
+			Ok(())
+		}
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking {
+	use super::*;
+	use frame_benchmarking::v2::*;
+	use frame_support::{traits::EnsureOrigin, BoundedVec};
+	use sp_std::prelude::*;
+
+	/// An identity with all fields set and a website of the largest length.
+	fn identity_proof<T: Config>(relay_block: u32) -> IdentityProofOf<T> {
+		let web = BoundedVec::truncate_from(vec![b'w'; T::MaxWebsiteUrlLength::get() as usize]);
+		IdentityProof {
+			fields: u64::MAX,
+			web: Some(web),
+			good_judgement: true,
+			super_account: Some(account("super", 0, 0)),
+			relay_block,
+		}
+	}
+
+	#[benchmarks]
+	mod benchmarks {
+		use super::*;
+
+		#[benchmark]
+		fn note_identity() -> Result<(), BenchmarkError> {
+			let origin =
+				T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+			let who: T::AccountId = account("who", 0, 0);
+			// Replacing a cached identity is the worst case.
+			Identities::<T>::insert(&who, identity_proof::<T>(1));
+
+			#[extrinsic_call]
+			_(origin as T::RuntimeOrigin, who.clone(), identity_proof::<T>(2));
+
+			assert_eq!(Identities::<T>::get(&who).map(|proof| proof.relay_block), Some(2));
+			Ok(())
+		}
+
+		#[benchmark]
+		fn clear_identity() -> Result<(), BenchmarkError> {
+			let origin =
+				T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+			let who: T::AccountId = account("who", 0, 0);
+			Identities::<T>::insert(&who, identity_proof::<T>(1));
+
+			#[extrinsic_call]
+			_(origin as T::RuntimeOrigin, who.clone());
+
+			assert!(!Identities::<T>::contains_key(&who));
+			Ok(())
+		}
+	}
+}
+
+/// Implements [`IdentityVerifier`] on the identities cached in [`Identities`].
+///
+/// A website is only considered set if it was verified, and identities with a website listed as
+/// unscrupulous by the Alliance are rejected.
+pub struct CachedIdentityVerifier;
+
+impl IdentityVerifier<AccountId> for CachedIdentityVerifier {
+	fn has_identity(who: &AccountId, fields: u64) -> bool {
+		let Some(identity) = Identities::<Runtime>::get(who) else { return false };
+		if identity.fields & fields != fields {
+			return false
+		}
+		match identity.web {
+			Some(web) => !UnscrupulousWebsites::<Runtime>::get()
+				.iter()
+				.any(|unscrupulous| unscrupulous.as_slice() == web.as_slice()),
+			None => fields & IDENTITY_FIELD_WEB == 0,
+		}
+	}
+
+	fn has_good_judgement(who: &AccountId) -> bool {
+		Identities::<Runtime>::get(who).map_or(false, |identity| identity.good_judgement)
+	}
+
+	fn super_account_id(who: &AccountId) -> Option<AccountId> {
+		Identities::<Runtime>::get(who).and_then(|identity| identity.super_account)
+	}
+}
+
+/// Rejects Alliance motions, proposed through either `Alliance` or `AllianceMotion`, which
+/// nominate or elevate an account listed as unscrupulous by the Alliance, including within any
+/// call wrapping other calls.
+///
+/// The nomination or elevation itself is also rejected when it is dispatched, which covers calls
+/// a motion only dispatches indirectly, e.g. through XCM or a preimage.
+pub struct AllianceProposalFilter;
+
+impl AllianceProposalFilter {
+	fn involves_unscrupulous(call: &RuntimeCall) -> bool {
+		if Self::is_unscrupulous_nomination(call) {
+			return true
+		}
+		match call {
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } |
+				pallet_utility::Call::batch_all { calls } |
+				pallet_utility::Call::force_batch { calls },
+			) => calls.iter().any(Self::involves_unscrupulous),
+			RuntimeCall::Utility(
+				pallet_utility::Call::as_derivative { call, .. } |
+				pallet_utility::Call::dispatch_as { call, .. } |
+				pallet_utility::Call::with_weight { call, .. },
+			) |
+			RuntimeCall::Multisig(
+				pallet_multisig::Call::as_multi { call, .. } |
+				pallet_multisig::Call::as_multi_threshold_1 { call, .. },
+			) |
+			RuntimeCall::Proxy(
+				pallet_proxy::Call::proxy { call, .. } |
+				pallet_proxy::Call::proxy_announced { call, .. },
+			) |
+			RuntimeCall::Scheduler(
+				pallet_scheduler::Call::schedule { call, .. } |
+				pallet_scheduler::Call::schedule_named { call, .. } |
+				pallet_scheduler::Call::schedule_after { call, .. } |
+				pallet_scheduler::Call::schedule_named_after { call, .. },
+			) |
+			RuntimeCall::Alliance(pallet_alliance::Call::propose { proposal: call, .. }) |
+			RuntimeCall::AllianceMotion(pallet_collective::Call::propose { proposal: call, .. }) =>
+				Self::involves_unscrupulous(call),
+			_ => false,
+		}
+	}
+
+	fn is_unscrupulous_nomination(call: &RuntimeCall) -> bool {
+		let is_unscrupulous =
+			|who: &<<Runtime as frame_system::Config>::Lookup as StaticLookup>::Source| {
+				<Runtime as frame_system::Config>::Lookup::lookup(who.clone())
+					.map_or(false, |who| UnscrupulousAccounts::<Runtime>::get().contains(&who))
+			};
+		match call {
+			RuntimeCall::Alliance(pallet_alliance::Call::nominate_ally { who }) =>
+				is_unscrupulous(who),
+			RuntimeCall::Alliance(pallet_alliance::Call::elevate_ally { ally }) =>
+				is_unscrupulous(ally),
+			_ => false,
+		}
+	}
+}
+
+impl Contains<RuntimeCall> for AllianceProposalFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::Alliance(pallet_alliance::Call::propose { proposal, .. }) |
+			RuntimeCall::AllianceMotion(pallet_collective::Call::propose { proposal, .. }) =>
+				!Self::involves_unscrupulous(proposal),
+			call => !Self::is_unscrupulous_nomination(call),
+		}
+	}
+}
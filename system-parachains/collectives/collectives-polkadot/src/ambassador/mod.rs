@@ -0,0 +1,281 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The Ambassador Program.
+//!
+//! The Ambassador Program is a ranked collective for non-technical contributors to the Polkadot
+//! network. It mirrors the structure of the Fellowship: a ranked collective, a referenda
+//! instance with one track per rank, a core instance handling evidence, retention and promotion,
+//! a salary instance paying over XCM on the Asset Hub and a treasury instance paying its spends
+//! over XCM on the Asset Hub.
+//!
+//! The ranked collective, referenda, core, salary and treasury instances use the weights
+//! benchmarked for the Fellowship instances of the same pallets. The weights of these pallets depend on the pallet
+//! logic and on the configured bounds, not on the instance, and the Ambassador Program has the
+//! same number of ranks, the same `MaxQueued` and `EvidenceSize` and no more tracks than the
+//! Fellowship.
+
+mod origins;
+mod tracks;
+use crate::{
+	fellowship::{
+		treasury::pallet_fellowship_treasury, ReferendaPalletAccount, TreasuryAsset,
+		TreasuryAssetToLocatableAsset, TreasurySpendLimit, UnlimitedTreasurySpend, UsdtAsset,
+		USDT_UNITS,
+	},
+	impls::ToParentTreasury,
+	weights, AccountId, AmbassadorReferenda, Balance, Balances, PolkadotTreasuryAccount, Preimage,
+	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Scheduler, DAYS,
+};
+use frame_support::{
+	parameter_types,
+	traits::{EitherOf, EitherOfDiverse, MapSuccess, OriginTrait, TryWithMorphedArg},
+};
+use frame_system::{EnsureRoot, EnsureRootWithSuccess};
+pub use origins::{
+	pallet_origins as pallet_ambassador_origins, AmbassadorSpender, Ambassadors,
+	EnsureAmbassadorsVoice, EnsureCanPromoteTo, HeadAmbassadors, MasterAmbassadors,
+	SeniorAmbassadors, ToVoice,
+};
+use pallet_ranked_collective::EnsureOfRank;
+use polkadot_runtime_constants::time::HOURS;
+use sp_core::{ConstU128, ConstU32};
+use sp_runtime::traits::{ConstU16, ConvertToValue, TakeFirst};
+use xcm::prelude::*;
+use xcm_builder::{AliasesIntoAccountId32, PayOverXcm};
+
+#[cfg(feature = "runtime-benchmarks")]
+use crate::impls::benchmarks::{OpenHrmpChannel, PayWithEnsure};
+
+/// The Ambassador Program's member ranks.
+pub mod ranks {
+	use pallet_ranked_collective::Rank;
+
+	pub const CANDIDATE: Rank = 0;
+	pub const AMBASSADOR_TIER_1: Rank = 1; // aka Ambassadors.
+	pub const AMBASSADOR_TIER_2: Rank = 2;
+	pub const SENIOR_AMBASSADOR_TIER_3: Rank = 3; // aka Senior Ambassadors.
+	pub const SENIOR_AMBASSADOR_TIER_4: Rank = 4;
+	pub const HEAD_AMBASSADOR_TIER_5: Rank = 5; // aka Head Ambassadors.
+	pub const HEAD_AMBASSADOR_TIER_6: Rank = 6;
+	pub const HEAD_AMBASSADOR_TIER_7: Rank = 7;
+	pub const MASTER_AMBASSADOR_TIER_8: Rank = 8; // aka Master Ambassadors.
+	pub const MASTER_AMBASSADOR_TIER_9: Rank = 9;
+}
+
+impl pallet_ambassador_origins::Config for Runtime {}
+
+pub type AmbassadorReferendaInstance = pallet_referenda::Instance2;
+
+impl pallet_referenda::Config<AmbassadorReferendaInstance> for Runtime {
+	type WeightInfo = weights::pallet_referenda::WeightInfo<Runtime>;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type Scheduler = Scheduler;
+	type Currency = Balances;
+	// Any Ambassador of at least Tier 1 can submit proposals, as well as the voice of any rank on
+	// the tracks it may vote on.
+	type SubmitOrigin = EitherOf<
+		pallet_ranked_collective::EnsureMember<
+			Runtime,
+			AmbassadorCollectiveInstance,
+			{ ranks::AMBASSADOR_TIER_1 },
+		>,
+		MapSuccess<
+			TryWithMorphedArg<
+				RuntimeOrigin,
+				<RuntimeOrigin as OriginTrait>::PalletsOrigin,
+				ToVoice,
+				EnsureOfRank<Runtime, AmbassadorCollectiveInstance>,
+				(AccountId, u16),
+			>,
+			TakeFirst,
+		>,
+	>;
+	type CancelOrigin = EitherOfDiverse<EnsureRoot<AccountId>, HeadAmbassadors>;
+	type KillOrigin = EitherOfDiverse<EnsureRoot<AccountId>, MasterAmbassadors>;
+	type Slash = ToParentTreasury<PolkadotTreasuryAccount, ReferendaPalletAccount, Runtime>;
+	type Votes = pallet_ranked_collective::Votes;
+	type Tally = pallet_ranked_collective::TallyOf<Runtime, AmbassadorCollectiveInstance>;
+	type SubmissionDeposit = ConstU128<0>;
+	type MaxQueued = ConstU32<100>;
+	type UndecidingTimeout = ConstU32<{ 7 * DAYS }>;
+	type AlarmInterval = ConstU32<1>;
+	type Tracks = tracks::TracksInfo;
+	type Preimages = Preimage;
+}
+
+pub type AmbassadorCollectiveInstance = pallet_ranked_collective::Instance2;
+
+impl pallet_ranked_collective::Config<AmbassadorCollectiveInstance> for Runtime {
+	type WeightInfo = weights::pallet_ranked_collective::WeightInfo<Runtime>;
+	type RuntimeEvent = RuntimeEvent;
+
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	// Promotions and the induction of new members are serviced by `AmbassadorCore` pallet
+	// instance.
+	type PromoteOrigin = frame_system::EnsureNever<pallet_ranked_collective::Rank>;
+	#[cfg(feature = "runtime-benchmarks")]
+	// The maximum value of `u16` set as a success value for the root to ensure the benchmarks will
+	// pass.
+	type PromoteOrigin = EnsureRootWithSuccess<Self::AccountId, ConstU16<65535>>;
+
+	// Demotion is by any of:
+	// - Root can demote arbitrarily;
+	// - a vote by the rank two above the current rank.
+	//
+	// The maximum value of `u16` set as a success value for the root to ensure the benchmarks will
+	// pass.
+	type DemoteOrigin =
+		EitherOf<EnsureRootWithSuccess<Self::AccountId, ConstU16<65535>>, EnsureCanPromoteTo>;
+	type Polls = AmbassadorReferenda;
+	type MinRankOfClass = tracks::MinRankOfClass;
+	type VoteWeight = pallet_ranked_collective::Geometric;
+}
+
+pub type AmbassadorCoreInstance = pallet_core_fellowship::Instance2;
+
+impl pallet_core_fellowship::Config<AmbassadorCoreInstance> for Runtime {
+	type WeightInfo = weights::pallet_core_fellowship::WeightInfo<Runtime>;
+	type RuntimeEvent = RuntimeEvent;
+	type Members = pallet_ranked_collective::Pallet<Runtime, AmbassadorCollectiveInstance>;
+	type Balance = Balance;
+	// Parameters (i.e. salaries, demotion and promotion periods) are set by any of:
+	// - Root;
+	// - a vote among all Head Ambassadors.
+	type ParamsOrigin = EitherOfDiverse<EnsureRoot<AccountId>, HeadAmbassadors>;
+	// Induction (creating a candidate) is by any of:
+	// - Root;
+	// - a single Senior Ambassador;
+	// - a vote among all Ambassadors.
+	type InductOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		EitherOfDiverse<
+			pallet_ranked_collective::EnsureMember<
+				Runtime,
+				AmbassadorCollectiveInstance,
+				{ ranks::SENIOR_AMBASSADOR_TIER_3 },
+			>,
+			Ambassadors,
+		>,
+	>;
+	// Approval (rank-retention) of a Member's current rank is by any of:
+	// - Root;
+	// - a vote by the rank two above the current rank.
+	type ApproveOrigin = EitherOf<
+		EnsureRootWithSuccess<Self::AccountId, ConstU16<{ ranks::MASTER_AMBASSADOR_TIER_9 }>>,
+		EnsureCanPromoteTo,
+	>;
+	// Promotion is by any of:
+	// - Root can promote arbitrarily;
+	// - a vote by the rank two above the new rank.
+	type PromoteOrigin = EitherOf<
+		EnsureRootWithSuccess<Self::AccountId, ConstU16<{ ranks::MASTER_AMBASSADOR_TIER_9 }>>,
+		EnsureCanPromoteTo,
+	>;
+	type EvidenceSize = ConstU32<65536>;
+}
+
+pub type AmbassadorSalaryInstance = pallet_salary::Instance2;
+
+parameter_types! {
+	// The interior location on AssetHub for the paying account. This is the Ambassador Salary
+	// pallet instance (which sits at index 74). This sovereign account will need funding.
+	pub AmbassadorSalaryLocation: InteriorMultiLocation = PalletInstance(74).into();
+}
+
+/// [`PayOverXcm`] setup to pay the Ambassador salary on the AssetHub in USDT.
+pub type AmbassadorSalaryPaymaster = PayOverXcm<
+	AmbassadorSalaryLocation,
+	crate::xcm_config::XcmRouter,
+	crate::PolkadotXcm,
+	ConstU32<{ 6 * HOURS }>,
+	AccountId,
+	(),
+	ConvertToValue<UsdtAsset>,
+	AliasesIntoAccountId32<(), AccountId>,
+>;
+
+impl pallet_salary::Config<AmbassadorSalaryInstance> for Runtime {
+	type WeightInfo = weights::pallet_salary::WeightInfo<Runtime>;
+	type RuntimeEvent = RuntimeEvent;
+
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type Paymaster = AmbassadorSalaryPaymaster;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Paymaster = PayWithEnsure<AmbassadorSalaryPaymaster, OpenHrmpChannel<ConstU32<1000>>>;
+	type Members = pallet_ranked_collective::Pallet<Runtime, AmbassadorCollectiveInstance>;
+
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type Salary = pallet_core_fellowship::Pallet<Runtime, AmbassadorCoreInstance>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Salary = frame_support::traits::tokens::ConvertRank<
+		crate::impls::benchmarks::RankToSalary<Balances>,
+	>;
+	// 15 days to register for a salary payment.
+	type RegistrationPeriod = ConstU32<{ 15 * DAYS }>;
+	// 15 days to claim the salary payment.
+	type PayoutPeriod = ConstU32<{ 15 * DAYS }>;
+	// The USDT paid out to the registered ambassadors per 30-day salary cycle.
+	type Budget = ConstU128<{ 25_000 * USDT_UNITS }>;
+}
+
+pub type AmbassadorTreasuryInstance = pallet_fellowship_treasury::Instance1;
+
+parameter_types! {
+	// The interior location on the Asset Hub of the account holding the funds of the Ambassador
+	// treasury. This is the Ambassador Treasury pallet instance (which sits at index 75). This
+	// sovereign account will need funding.
+	pub AmbassadorTreasuryInterior: InteriorMultiLocation = PalletInstance(75).into();
+}
+
+/// [`PayOverXcm`] setup to pay out the spends of the Ambassador treasury on the Asset Hub.
+pub type AmbassadorTreasuryPaymaster = PayOverXcm<
+	AmbassadorTreasuryInterior,
+	crate::xcm_config::XcmRouter,
+	crate::PolkadotXcm,
+	ConstU32<{ 6 * HOURS }>,
+	AccountId,
+	TreasuryAsset,
+	TreasuryAssetToLocatableAsset,
+	AliasesIntoAccountId32<(), AccountId>,
+>;
+
+impl pallet_fellowship_treasury::Config<AmbassadorTreasuryInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetKind = TreasuryAsset;
+	type Beneficiary = AccountId;
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type Paymaster = AmbassadorTreasuryPaymaster;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Paymaster = PayWithEnsure<AmbassadorTreasuryPaymaster, OpenHrmpChannel<ConstU32<1000>>>;
+	// Spending is by any of:
+	// - Root;
+	// - the voice of the Senior, Head or Master Ambassadors, up to their respective limits.
+	type SpendOrigin =
+		EitherOf<EnsureRootWithSuccess<AccountId, UnlimitedTreasurySpend>, AmbassadorSpender>;
+	type SpendLimit = TreasurySpendLimit;
+	// Spends not paid out yet are voided by any of:
+	// - Root;
+	// - the voice of the Head Ambassadors.
+	type VoidOrigin = EitherOfDiverse<EnsureRoot<AccountId>, HeadAmbassadors>;
+	// 30 days to pay out an approved spend.
+	type PayoutPeriod = ConstU32<{ 30 * DAYS }>;
+	// The same pallet as the Fellowship treasury, benchmarked on this runtime.
+	type WeightInfo = weights::pallet_fellowship_treasury::WeightInfo<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = crate::fellowship::TreasuryArguments;
+}
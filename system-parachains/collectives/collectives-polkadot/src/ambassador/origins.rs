@@ -0,0 +1,204 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ambassador Program custom origins.
+
+use super::ranks;
+pub use pallet_origins::*;
+
+#[frame_support::pallet]
+pub mod pallet_origins {
+	use super::ranks;
+	use crate::{fellowship::TreasurySpendLimit, UNITS};
+	use frame_support::pallet_prelude::*;
+	use pallet_ranked_collective::Rank;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[derive(PartialEq, Eq, Clone, MaxEncodedLen, Encode, Decode, TypeInfo, RuntimeDebug)]
+	#[pallet::origin]
+	pub enum Origin {
+		/// Origin aggregated through weighted votes of those with rank 1 or above; `Success` is 1.
+		/// Aka the "voice" of all Ambassadors.
+		Ambassadors,
+		/// Origin aggregated through weighted votes of those with rank 2 or above; `Success` is 2.
+		/// Aka the "voice" of Ambassadors of at least Tier 2.
+		AmbassadorsTier2,
+		/// Origin aggregated through weighted votes of those with rank 3 or above; `Success` is 3.
+		/// Aka the "voice" of all Senior Ambassadors.
+		SeniorAmbassadors,
+		/// Origin aggregated through weighted votes of those with rank 4 or above; `Success` is 4.
+		/// Aka the "voice" of Senior Ambassadors of at least Tier 4.
+		SeniorAmbassadorsTier4,
+		/// Origin aggregated through weighted votes of those with rank 5 or above; `Success` is 5.
+		/// Aka the "voice" of all Head Ambassadors.
+		HeadAmbassadors,
+		/// Origin aggregated through weighted votes of those with rank 6 or above; `Success` is 6.
+		/// Aka the "voice" of Head Ambassadors of at least Tier 6.
+		HeadAmbassadorsTier6,
+		/// Origin aggregated through weighted votes of those with rank 7 or above; `Success` is 7.
+		/// Aka the "voice" of Head Ambassadors of at least Tier 7.
+		HeadAmbassadorsTier7,
+		/// Origin aggregated through weighted votes of those with rank 8 or above; `Success` is 8.
+		/// Aka the "voice" of all Master Ambassadors.
+		MasterAmbassadors,
+		/// Origin aggregated through weighted votes of those with rank 9 or above; `Success` is 9.
+		/// Aka the "voice" of Master Ambassadors of at least Tier 9.
+		MasterAmbassadorsTier9,
+	}
+
+	impl Origin {
+		/// Returns the rank that the origin `self` speaks for, or `None` if it doesn't speak for
+		/// any.
+		pub fn as_voice(&self) -> Option<pallet_ranked_collective::Rank> {
+			Some(match &self {
+				Origin::Ambassadors => ranks::AMBASSADOR_TIER_1,
+				Origin::AmbassadorsTier2 => ranks::AMBASSADOR_TIER_2,
+				Origin::SeniorAmbassadors => ranks::SENIOR_AMBASSADOR_TIER_3,
+				Origin::SeniorAmbassadorsTier4 => ranks::SENIOR_AMBASSADOR_TIER_4,
+				Origin::HeadAmbassadors => ranks::HEAD_AMBASSADOR_TIER_5,
+				Origin::HeadAmbassadorsTier6 => ranks::HEAD_AMBASSADOR_TIER_6,
+				Origin::HeadAmbassadorsTier7 => ranks::HEAD_AMBASSADOR_TIER_7,
+				Origin::MasterAmbassadors => ranks::MASTER_AMBASSADOR_TIER_8,
+				Origin::MasterAmbassadorsTier9 => ranks::MASTER_AMBASSADOR_TIER_9,
+			})
+		}
+	}
+
+	/// A `TryMorph` implementation which is designed to convert an aggregate `RuntimeOrigin`
+	/// value into the Ambassador Program voice it represents if it is an Ambassador pallet origin.
+	/// See also [Origin::as_voice].
+	pub struct ToVoice;
+	impl<'a, O: 'a + TryInto<&'a Origin>> sp_runtime::traits::TryMorph<O> for ToVoice {
+		type Outcome = pallet_ranked_collective::Rank;
+		fn try_morph(o: O) -> Result<pallet_ranked_collective::Rank, ()> {
+			o.try_into().ok().and_then(Origin::as_voice).ok_or(())
+		}
+	}
+
+	macro_rules! decl_unit_ensures {
+		( $name:ident: $success_type:ty = $success:expr ) => {
+			pub struct $name;
+			impl<O: Into<Result<Origin, O>> + From<Origin>>
+				EnsureOrigin<O> for $name
+			{
+				type Success = $success_type;
+				fn try_origin(o: O) -> Result<Self::Success, O> {
+					o.into().and_then(|o| match o {
+						Origin::$name => Ok($success),
+						r => Err(O::from(r)),
+					})
+				}
+				#[cfg(feature = "runtime-benchmarks")]
+				fn try_successful_origin() -> Result<O, ()> {
+					Ok(O::from(Origin::$name))
+				}
+			}
+		};
+		( $name:ident ) => { decl_unit_ensures! { $name : () = () } };
+		( $name:ident: $success_type:ty = $success:expr, $( $rest:tt )* ) => {
+			decl_unit_ensures! { $name: $success_type = $success }
+			decl_unit_ensures! { $( $rest )* }
+		};
+		( $name:ident, $( $rest:tt )* ) => {
+			decl_unit_ensures! { $name }
+			decl_unit_ensures! { $( $rest )* }
+		};
+		() => {}
+	}
+	decl_unit_ensures!(
+		Ambassadors: Rank = ranks::AMBASSADOR_TIER_1,
+		SeniorAmbassadors: Rank = ranks::SENIOR_AMBASSADOR_TIER_3,
+		HeadAmbassadors: Rank = ranks::HEAD_AMBASSADOR_TIER_5,
+		MasterAmbassadors: Rank = ranks::MASTER_AMBASSADOR_TIER_8,
+	);
+
+	macro_rules! decl_ensure {
+		(
+			$vis:vis type $name:ident: EnsureOrigin<Success = $success_type:ty> {
+				$( $item:ident = $success:expr, )*
+			}
+		) => {
+			$vis struct $name;
+			impl<O: Into<Result<Origin, O>> + From<Origin>>
+				EnsureOrigin<O> for $name
+			{
+				type Success = $success_type;
+				fn try_origin(o: O) -> Result<Self::Success, O> {
+					o.into().and_then(|o| match o {
+						$(
+							Origin::$item => Ok($success),
+						)*
+						r => Err(O::from(r)),
+					})
+				}
+				#[cfg(feature = "runtime-benchmarks")]
+				fn try_successful_origin() -> Result<O, ()> {
+					// By convention the more privileged origins go later, so for greatest chance
+					// of success, we want the last one.
+					let _result: Result<O, ()> = Err(());
+					$(
+						let _result: Result<O, ()> = Ok(O::from(Origin::$item));
+					)*
+					_result
+				}
+			}
+		}
+	}
+
+	// Ambassador Program origin indicating weighted voting from at least the rank of `Success`.
+	decl_ensure! {
+		pub type EnsureAmbassadorsVoice: EnsureOrigin<Success = Rank> {
+			Ambassadors = ranks::AMBASSADOR_TIER_1,
+			AmbassadorsTier2 = ranks::AMBASSADOR_TIER_2,
+			SeniorAmbassadors = ranks::SENIOR_AMBASSADOR_TIER_3,
+			SeniorAmbassadorsTier4 = ranks::SENIOR_AMBASSADOR_TIER_4,
+			HeadAmbassadors = ranks::HEAD_AMBASSADOR_TIER_5,
+			HeadAmbassadorsTier6 = ranks::HEAD_AMBASSADOR_TIER_6,
+			HeadAmbassadorsTier7 = ranks::HEAD_AMBASSADOR_TIER_7,
+			MasterAmbassadors = ranks::MASTER_AMBASSADOR_TIER_8,
+			MasterAmbassadorsTier9 = ranks::MASTER_AMBASSADOR_TIER_9,
+		}
+	}
+
+	// Ambassador Program origin indicating weighted voting from at least the rank of
+	// `Success + 2`; needed for the rank retention, promotion and demotion of Ambassadors.
+	decl_ensure! {
+		pub type EnsureCanPromoteTo: EnsureOrigin<Success = Rank> {
+			SeniorAmbassadors = ranks::AMBASSADOR_TIER_1,
+			SeniorAmbassadorsTier4 = ranks::AMBASSADOR_TIER_2,
+			HeadAmbassadors = ranks::SENIOR_AMBASSADOR_TIER_3,
+			HeadAmbassadorsTier6 = ranks::SENIOR_AMBASSADOR_TIER_4,
+			HeadAmbassadorsTier7 = ranks::HEAD_AMBASSADOR_TIER_5,
+			MasterAmbassadors = ranks::HEAD_AMBASSADOR_TIER_6,
+			MasterAmbassadorsTier9 = ranks::HEAD_AMBASSADOR_TIER_7,
+		}
+	}
+
+	// Ambassador Program origin able to spend from the Ambassador treasury; `Success` is the
+	// maximum amount that may be spent at once. These origins only spend DOT.
+	decl_ensure! {
+		pub type AmbassadorSpender: EnsureOrigin<Success = TreasurySpendLimit> {
+			SeniorAmbassadors = TreasurySpendLimit { dot: 1_000 * UNITS, usdt: 0 },
+			HeadAmbassadors = TreasurySpendLimit { dot: 10_000 * UNITS, usdt: 0 },
+			MasterAmbassadors = TreasurySpendLimit { dot: 50_000 * UNITS, usdt: 0 },
+		}
+	}
+}
@@ -0,0 +1,237 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Track configurations for the Ambassador Program.
+
+use super::origins::Origin;
+use crate::{Balance, BlockNumber, RuntimeOrigin, DAYS, DOLLARS, HOURS, MINUTES};
+use pallet_ranked_collective::Rank;
+use sp_runtime::{traits::Convert, Perbill};
+
+/// Referendum `TrackId` type.
+pub type TrackId = u16;
+
+/// Referendum track IDs.
+pub mod constants {
+	use super::TrackId;
+
+	// Regular tracks (7 days) used for general operations. The required rank for voting is the
+	// same as that which is named (and also the track ID).
+	pub const AMBASSADOR_TIER_1: TrackId = 1;
+	pub const AMBASSADOR_TIER_2: TrackId = 2;
+	pub const SENIOR_AMBASSADOR_TIER_3: TrackId = 3;
+	pub const SENIOR_AMBASSADOR_TIER_4: TrackId = 4;
+	pub const HEAD_AMBASSADOR_TIER_5: TrackId = 5;
+	pub const HEAD_AMBASSADOR_TIER_6: TrackId = 6;
+	pub const HEAD_AMBASSADOR_TIER_7: TrackId = 7;
+	pub const MASTER_AMBASSADOR_TIER_8: TrackId = 8;
+	pub const MASTER_AMBASSADOR_TIER_9: TrackId = 9;
+}
+
+/// Convert the track ID (defined above) into the minimum rank (i.e. Ambassador tier) required to
+/// vote on the track.
+pub struct MinRankOfClass;
+impl Convert<TrackId, Rank> for MinRankOfClass {
+	fn convert(a: TrackId) -> Rank {
+		match a {
+			// Just a regular vote: the track ID is conveniently the same as the minimum rank.
+			regular @ 1..=9 => regular,
+			_ => Rank::max_value(),
+		}
+	}
+}
+
+const MAX_DECIDING: u32 = 10;
+const DECISION_DEPOSIT: Balance = 5 * DOLLARS;
+const PREPARE_PERIOD: BlockNumber = 30 * MINUTES;
+const DECISION_PERIOD: BlockNumber = 7 * DAYS;
+const CONFIRM_PERIOD: BlockNumber = 1 * HOURS;
+const MIN_ENACTMENT_PERIOD: BlockNumber = 5 * MINUTES;
+const MIN_APPROVAL: pallet_referenda::Curve = pallet_referenda::Curve::LinearDecreasing {
+	length: Perbill::from_percent(100),
+	floor: Perbill::from_percent(50),
+	ceil: Perbill::from_percent(100),
+};
+const MIN_SUPPORT: pallet_referenda::Curve = pallet_referenda::Curve::LinearDecreasing {
+	length: Perbill::from_percent(100),
+	floor: Perbill::from_percent(0),
+	ceil: Perbill::from_percent(100),
+};
+
+pub struct TracksInfo;
+impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
+	type Id = TrackId;
+	type RuntimeOrigin = <RuntimeOrigin as frame_support::traits::OriginTrait>::PalletsOrigin;
+	fn tracks() -> &'static [(Self::Id, pallet_referenda::TrackInfo<Balance, BlockNumber>)] {
+		use constants as tracks;
+		static DATA: [(TrackId, pallet_referenda::TrackInfo<Balance, BlockNumber>); 9] = [
+			(
+				tracks::AMBASSADOR_TIER_1,
+				pallet_referenda::TrackInfo {
+					name: "ambassador tier 1",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::AMBASSADOR_TIER_2,
+				pallet_referenda::TrackInfo {
+					name: "ambassador tier 2",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::SENIOR_AMBASSADOR_TIER_3,
+				pallet_referenda::TrackInfo {
+					name: "senior ambassador tier 3",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::SENIOR_AMBASSADOR_TIER_4,
+				pallet_referenda::TrackInfo {
+					name: "senior ambassador tier 4",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::HEAD_AMBASSADOR_TIER_5,
+				pallet_referenda::TrackInfo {
+					name: "head ambassador tier 5",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::HEAD_AMBASSADOR_TIER_6,
+				pallet_referenda::TrackInfo {
+					name: "head ambassador tier 6",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::HEAD_AMBASSADOR_TIER_7,
+				pallet_referenda::TrackInfo {
+					name: "head ambassador tier 7",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::MASTER_AMBASSADOR_TIER_8,
+				pallet_referenda::TrackInfo {
+					name: "master ambassador tier 8",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::MASTER_AMBASSADOR_TIER_9,
+				pallet_referenda::TrackInfo {
+					name: "master ambassador tier 9",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+		];
+		&DATA[..]
+	}
+	fn track_for(id: &Self::RuntimeOrigin) -> Result<Self::Id, ()> {
+		use constants as tracks;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		{
+			// For benchmarks, we enable a root origin.
+			// It is important that this is not available in production!
+			let root: Self::RuntimeOrigin = frame_system::RawOrigin::Root.into();
+			if &root == id {
+				return Ok(tracks::MASTER_AMBASSADOR_TIER_9)
+			}
+		}
+
+		match Origin::try_from(id.clone()) {
+			Ok(Origin::Ambassadors) => Ok(tracks::AMBASSADOR_TIER_1),
+			Ok(Origin::AmbassadorsTier2) => Ok(tracks::AMBASSADOR_TIER_2),
+			Ok(Origin::SeniorAmbassadors) => Ok(tracks::SENIOR_AMBASSADOR_TIER_3),
+			Ok(Origin::SeniorAmbassadorsTier4) => Ok(tracks::SENIOR_AMBASSADOR_TIER_4),
+			Ok(Origin::HeadAmbassadors) => Ok(tracks::HEAD_AMBASSADOR_TIER_5),
+			Ok(Origin::HeadAmbassadorsTier6) => Ok(tracks::HEAD_AMBASSADOR_TIER_6),
+			Ok(Origin::HeadAmbassadorsTier7) => Ok(tracks::HEAD_AMBASSADOR_TIER_7),
+			Ok(Origin::MasterAmbassadors) => Ok(tracks::MASTER_AMBASSADOR_TIER_8),
+			Ok(Origin::MasterAmbassadorsTier9) => Ok(tracks::MASTER_AMBASSADOR_TIER_9),
+			_ => Err(()),
+		}
+	}
+}
+pallet_referenda::impl_tracksinfo_get!(TracksInfo, Balance, BlockNumber);
@@ -18,28 +18,39 @@
 
 pub(crate) mod migration;
 mod origins;
-mod tracks;
+pub mod runtime_api;
+pub(crate) mod tracks;
+pub mod treasury;
 use crate::{
 	impls::ToParentTreasury, weights, AccountId, Balance, Balances, FellowshipReferenda,
 	GovernanceLocation, PolkadotTreasuryAccount, Preimage, Runtime, RuntimeCall, RuntimeEvent,
 	RuntimeOrigin, Scheduler, DAYS,
 };
+use codec::{Decode, Encode, MaxEncodedLen};
 use cumulus_primitives_core::Junction::GeneralIndex;
 use frame_support::{
 	parameter_types,
 	traits::{EitherOf, EitherOfDiverse, MapSuccess, OriginTrait, TryWithMorphedArg},
+	PalletId,
 };
-use frame_system::EnsureRootWithSuccess;
+use frame_system::{EnsureRoot, EnsureRootWithSuccess};
 pub use origins::{
 	pallet_origins as pallet_fellowship_origins, Architects, EnsureCanPromoteTo, EnsureCanRetainAt,
-	EnsureFellowship, Fellows, Masters, Members, ToVoice,
+	EnsureFellowship, Fellows, FellowshipSpender, Masters, Members, ToVoice,
 };
 use pallet_ranked_collective::EnsureOfRank;
 use pallet_xcm::{EnsureXcm, IsVoiceOfBody};
 use parachains_common::polkadot::account;
-use polkadot_runtime_constants::{time::HOURS, xcm::body::FELLOWSHIP_ADMIN_INDEX};
+use polkadot_runtime_constants::{
+	system_parachain::collectives::FELLOWSHIP_TREASURY_PALLET_INDEX, time::HOURS,
+	xcm::body::FELLOWSHIP_ADMIN_INDEX,
+};
+use scale_info::TypeInfo;
 use sp_core::{ConstU128, ConstU32};
-use sp_runtime::traits::{AccountIdConversion, ConstU16, ConvertToValue, Replace, TakeFirst};
+use sp_runtime::{
+	traits::{AccountIdConversion, ConstU16, Convert, ConvertToValue, Replace, TakeFirst},
+	RuntimeDebug,
+};
 use xcm::latest::BodyId;
 use xcm_builder::{AliasesIntoAccountId32, LocatableAssetId, PayOverXcm};
 
@@ -71,6 +82,9 @@ impl pallet_fellowship_origins::Config for Runtime {}
 
 pub type FellowshipReferendaInstance = pallet_referenda::Instance1;
 
+/// The lowest rank of the members who may submit proposals to the Fellowship referenda.
+pub const MIN_RANK_TO_SUBMIT: pallet_ranked_collective::Rank = ranks::DAN_3;
+
 impl pallet_referenda::Config<FellowshipReferendaInstance> for Runtime {
 	type WeightInfo = weights::pallet_referenda::WeightInfo<Runtime>;
 	type RuntimeCall = RuntimeCall;
@@ -79,7 +93,11 @@ impl pallet_referenda::Config<FellowshipReferendaInstance> for Runtime {
 	type Currency = Balances;
 	// Fellows can submit proposals.
 	type SubmitOrigin = EitherOf<
-		pallet_ranked_collective::EnsureMember<Runtime, FellowshipCollectiveInstance, 3>,
+		pallet_ranked_collective::EnsureMember<
+			Runtime,
+			FellowshipCollectiveInstance,
+			{ MIN_RANK_TO_SUBMIT },
+		>,
 		MapSuccess<
 			TryWithMorphedArg<
 				RuntimeOrigin,
@@ -208,7 +226,7 @@ parameter_types! {
 	pub Interior: InteriorMultiLocation = PalletInstance(64).into();
 }
 
-const USDT_UNITS: u128 = 1_000_000;
+pub(crate) const USDT_UNITS: u128 = 1_000_000;
 
 /// [`PayOverXcm`] setup to pay the Fellowship salary on the AssetHub in USDT.
 pub type FellowshipSalaryPaymaster = PayOverXcm<
@@ -245,3 +263,130 @@ impl pallet_salary::Config<FellowshipSalaryInstance> for Runtime {
 	// Total monthly salary budget.
 	type Budget = ConstU128<{ 100_000 * USDT_UNITS }>;
 }
+
+parameter_types! {
+	// The interior location on the Asset Hub of the account holding the funds of the Fellowship
+	// treasury. This is the Fellowship Treasury pallet (which sits at index 65). The Polkadot
+	// Treasury tops this sovereign account up.
+	pub FellowshipTreasuryInterior: InteriorMultiLocation =
+		PalletInstance(FELLOWSHIP_TREASURY_PALLET_INDEX).into();
+	pub const UnlimitedTreasurySpend: TreasurySpendLimit = TreasurySpendLimit::UNLIMITED;
+}
+
+/// The assets the Fellowship treasury pays out on the Asset Hub.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TreasuryAsset {
+	/// DOT, the native asset of the Asset Hub.
+	Dot,
+	/// USDT, the trust-backed asset 1984 of the Asset Hub.
+	Usdt,
+}
+
+/// Converts a [`TreasuryAsset`] into its location on the Asset Hub.
+pub struct TreasuryAssetToLocatableAsset;
+impl Convert<TreasuryAsset, LocatableAssetId> for TreasuryAssetToLocatableAsset {
+	fn convert(asset: TreasuryAsset) -> LocatableAssetId {
+		match asset {
+			TreasuryAsset::Dot =>
+				LocatableAssetId { location: AssetHub::get(), asset_id: Parent.into() },
+			TreasuryAsset::Usdt => UsdtAsset::get(),
+		}
+	}
+}
+
+/// The largest amounts of DOT and USDT an origin may spend at once from the Fellowship treasury.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct TreasurySpendLimit {
+	pub dot: Balance,
+	pub usdt: Balance,
+}
+
+impl TreasurySpendLimit {
+	pub const UNLIMITED: Self = Self { dot: Balance::MAX, usdt: Balance::MAX };
+}
+
+impl treasury::SpendLimit<TreasuryAsset, Balance> for TreasurySpendLimit {
+	fn max_amount(&self, asset: &TreasuryAsset) -> Balance {
+		match asset {
+			TreasuryAsset::Dot => self.dot,
+			TreasuryAsset::Usdt => self.usdt,
+		}
+	}
+}
+
+/// [`PayOverXcm`] setup to pay out the spends of the Fellowship treasury on the Asset Hub.
+pub type FellowshipTreasuryPaymaster = PayOverXcm<
+	FellowshipTreasuryInterior,
+	crate::xcm_config::XcmRouter,
+	crate::PolkadotXcm,
+	ConstU32<{ 6 * HOURS }>,
+	AccountId,
+	TreasuryAsset,
+	TreasuryAssetToLocatableAsset,
+	AliasesIntoAccountId32<(), AccountId>,
+>;
+
+impl treasury::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetKind = TreasuryAsset;
+	type Beneficiary = AccountId;
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type Paymaster = FellowshipTreasuryPaymaster;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Paymaster = PayWithEnsure<FellowshipTreasuryPaymaster, OpenHrmpChannel<ConstU32<1000>>>;
+	// Spending is by any of:
+	// - Root;
+	// - the FellowshipAdmin origin (i.e. token holder referendum);
+	// - a vote on one of the Fellowship spend tracks, up to the track's limits.
+	type SpendOrigin = EitherOf<
+		EitherOf<
+			EnsureRootWithSuccess<AccountId, UnlimitedTreasurySpend>,
+			MapSuccess<
+				EnsureXcm<IsVoiceOfBody<GovernanceLocation, FellowshipAdminBodyId>>,
+				Replace<UnlimitedTreasurySpend>,
+			>,
+		>,
+		FellowshipSpender,
+	>;
+	type SpendLimit = TreasurySpendLimit;
+	// Spends not paid out yet are voided by any of:
+	// - Root;
+	// - the FellowshipAdmin origin (i.e. token holder referendum);
+	// - a vote among all Fellows.
+	type VoidOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		EitherOfDiverse<
+			EnsureXcm<IsVoiceOfBody<GovernanceLocation, FellowshipAdminBodyId>>,
+			Fellows,
+		>,
+	>;
+	// 30 days to pay out an approved spend.
+	type PayoutPeriod = ConstU32<{ 30 * DAYS }>;
+	type WeightInfo = weights::pallet_fellowship_treasury::WeightInfo<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = TreasuryArguments;
+}
+
+/// Creates the arguments of Fellowship treasury spends in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TreasuryArguments;
+#[cfg(feature = "runtime-benchmarks")]
+impl treasury::ArgumentsFactory<TreasuryAsset, AccountId> for TreasuryArguments {
+	fn create_asset_kind(_seed: u32) -> TreasuryAsset {
+		TreasuryAsset::Usdt
+	}
+	fn create_beneficiary(seed: [u8; 32]) -> AccountId {
+		AccountId::from(seed)
+	}
+}
+
+parameter_types! {
+	pub const FellowshipKeeperPalletId: PalletId = PalletId(*b"py/felkp");
+}
+
+/// Registers the Fellowship members for their salary, claims their payouts and bumps expired
+/// members with the spare weight of each block.
+impl crate::keeper::Config<FellowshipCollectiveInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = FellowshipKeeperPalletId;
+}
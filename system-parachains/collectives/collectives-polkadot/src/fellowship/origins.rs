@@ -22,6 +22,10 @@ pub use pallet_origins::*;
 #[frame_support::pallet]
 pub mod pallet_origins {
 	use super::ranks;
+	use crate::{
+		fellowship::{TreasurySpendLimit, USDT_UNITS},
+		UNITS,
+	};
 	use frame_support::pallet_prelude::*;
 	use pallet_ranked_collective::Rank;
 
@@ -99,6 +103,17 @@ pub mod pallet_origins {
 		/// Origin aggregated through weighted votes of those with rank 8 or above when voting on
 		/// a month-long track; `Success` is 6.
 		PromoteTo6Dan,
+
+		/// Origin aggregated through weighted votes of those with rank 3 or above when voting on
+		/// a treasury spend track; `Success` is the maximum amount the Fellows may spend at once.
+		FellowsSpender,
+		/// Origin aggregated through weighted votes of those with rank 4 or above when voting on
+		/// a treasury spend track; `Success` is the maximum amount the Architects may spend at
+		/// once.
+		ArchitectsSpender,
+		/// Origin aggregated through weighted votes of those with rank 7 or above when voting on
+		/// a treasury spend track; `Success` is the maximum amount the Masters may spend at once.
+		MastersSpender,
 	}
 
 	impl Origin {
@@ -244,4 +259,16 @@ pub mod pallet_origins {
 			PromoteTo6Dan = ranks::DAN_6,
 		}
 	}
+
+	// Fellowship origin able to spend from the Fellowship treasury; `Success` is the maximum
+	// amount of DOT and of USDT that may be spent at once.
+	decl_ensure! {
+		pub type FellowshipSpender: EnsureOrigin<Success = TreasurySpendLimit> {
+			FellowsSpender = TreasurySpendLimit { dot: 1_000 * UNITS, usdt: 5_000 * USDT_UNITS },
+			ArchitectsSpender =
+				TreasurySpendLimit { dot: 10_000 * UNITS, usdt: 50_000 * USDT_UNITS },
+			MastersSpender =
+				TreasurySpendLimit { dot: 100_000 * UNITS, usdt: 500_000 * USDT_UNITS },
+		}
+	}
 }
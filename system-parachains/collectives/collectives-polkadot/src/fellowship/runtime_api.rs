@@ -0,0 +1,215 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API exposing the rank retention and promotion status of the Fellowship members.
+
+use super::{
+	ranks,
+	tracks::{MinRankOfClass, TrackId},
+	FellowshipCollectiveInstance, FellowshipCoreInstance, MIN_RANK_TO_SUBMIT,
+};
+use crate::{AccountId, Balance, BlockNumber, Runtime};
+use codec::{Codec, Decode, Encode};
+use frame_support::{
+	pallet_prelude::{OptionQuery, ValueQuery},
+	storage_alias,
+	traits::RankedMembers,
+	Twox64Concat,
+};
+use pallet_core_fellowship::{MemberStatus as CoreMemberStatus, ParamsType};
+use pallet_ranked_collective::{IndexToId, MemberCount, MemberIndex, Rank};
+use pallet_referenda::TracksInfo;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Convert, Saturating, Zero},
+	RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+/// The rank retention and promotion status of a member of the Fellowship.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MemberStatus<BlockNumber> {
+	/// The current rank of the member.
+	pub rank: Rank,
+	/// Whether the member is active (i.e. paid the active rather than the passive salary).
+	pub is_active: bool,
+	/// The block of the last promotion of the member.
+	pub last_promotion: BlockNumber,
+	/// The block at which the member last proved their rank.
+	pub last_proof: BlockNumber,
+	/// The block from which on the member may be demoted (or offboarded, for candidates), unless
+	/// they prove their rank again. `None` if the member cannot be demoted at their rank.
+	pub demotable_from: Option<BlockNumber>,
+	/// The block from which on the member may be promoted. `None` at the highest rank.
+	pub promotable_from: Option<BlockNumber>,
+	/// Whether the member has submitted evidence which is awaiting judgement.
+	pub has_evidence: bool,
+}
+
+/// The participation of a rank in a Fellowship referendum track.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct TrackAccess {
+	/// The ID of the track.
+	pub track: TrackId,
+	/// The name of the track.
+	pub name: Vec<u8>,
+	/// Whether members of the rank may vote on the track.
+	pub can_vote: bool,
+	/// Whether members of the rank may submit proposals on the track.
+	pub can_propose: bool,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to query the rank retention and promotion status of the Fellowship members.
+	pub trait FellowshipApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The status of the member `who`, or `None` if they are not a member.
+		fn member_status(who: AccountId) -> Option<MemberStatus<BlockNumber>>;
+
+		/// The members who may be demoted (or offboarded) at block `now`, along with their rank.
+		///
+		/// At most `max` members (and never more than [`MAX_MEMBERS_PER_QUERY`]) are checked,
+		/// starting with the member at index `from` among all members. The index to continue with
+		/// is returned along, or `None` if all members were checked.
+		fn members_at_risk(
+			now: BlockNumber,
+			from: MemberIndex,
+			max: u32,
+		) -> (Vec<(AccountId, Rank)>, Option<MemberIndex>);
+
+		/// The Fellowship referendum tracks members of `rank` may vote or propose on.
+		fn tracks_of_rank(rank: Rank) -> Vec<TrackAccess>;
+	}
+}
+
+/// The largest number of members checked by a single [`FellowshipApi::members_at_risk`] call.
+pub const MAX_MEMBERS_PER_QUERY: u32 = 100;
+
+/// The number of ranks with parameters in `pallet_core_fellowship`.
+const RANK_COUNT: usize = ranks::DAN_9 as usize;
+
+// `pallet_core_fellowship` keeps its storage items `pub(super)` and provides neither getters nor a
+// trait exposing the member status or the parameters, so they are read through aliases. The tests
+// of this API check that the aliases match the storage written by the pallet.
+#[storage_alias]
+type Params = StorageValue<
+	pallet_core_fellowship::Pallet<Runtime, FellowshipCoreInstance>,
+	ParamsType<Balance, BlockNumber, RANK_COUNT>,
+	ValueQuery,
+>;
+#[storage_alias]
+type Member = StorageMap<
+	pallet_core_fellowship::Pallet<Runtime, FellowshipCoreInstance>,
+	Twox64Concat,
+	AccountId,
+	CoreMemberStatus<BlockNumber>,
+	OptionQuery,
+>;
+#[storage_alias]
+type MemberEvidence = StorageMap<
+	pallet_core_fellowship::Pallet<Runtime, FellowshipCoreInstance>,
+	Twox64Concat,
+	AccountId,
+	(
+		pallet_core_fellowship::Wish,
+		pallet_core_fellowship::Evidence<Runtime, FellowshipCoreInstance>,
+	),
+	OptionQuery,
+>;
+
+type Collective = pallet_ranked_collective::Pallet<Runtime, FellowshipCollectiveInstance>;
+
+/// The period after which a member of `rank` may be demoted, mirroring
+/// `pallet_core_fellowship::Pallet::bump`. Zero if the rank cannot be demoted.
+fn demotion_period(
+	params: &ParamsType<Balance, BlockNumber, RANK_COUNT>,
+	rank: Rank,
+) -> BlockNumber {
+	match rank {
+		0 => params.offboard_timeout,
+		r => params.demotion_period.get(r as usize - 1).copied().unwrap_or_default(),
+	}
+}
+
+/// Implementation of [`FellowshipApi::member_status`].
+pub fn member_status(who: AccountId) -> Option<MemberStatus<BlockNumber>> {
+	let rank = Collective::rank_of(&who)?;
+	let status = Member::get(&who)?;
+	let params = Params::get();
+
+	let demotion_period = demotion_period(&params, rank);
+	let demotable_from =
+		(!demotion_period.is_zero()).then(|| status.last_proof.saturating_add(demotion_period));
+	let promotable_from = params
+		.min_promotion_period
+		.get(rank as usize)
+		.map(|period| status.last_promotion.saturating_add(*period));
+
+	Some(MemberStatus {
+		rank,
+		is_active: status.is_active,
+		last_promotion: status.last_promotion,
+		last_proof: status.last_proof,
+		demotable_from,
+		promotable_from,
+		has_evidence: MemberEvidence::contains_key(&who),
+	})
+}
+
+/// Implementation of [`FellowshipApi::members_at_risk`].
+pub fn members_at_risk(
+	now: BlockNumber,
+	from: MemberIndex,
+	max: u32,
+) -> (Vec<(AccountId, Rank)>, Option<MemberIndex>) {
+	let params = Params::get();
+	let count = MemberCount::<Runtime, FellowshipCollectiveInstance>::get(0);
+	let end = from.saturating_add(max.min(MAX_MEMBERS_PER_QUERY)).min(count);
+
+	let at_risk = (from..end)
+		.filter_map(|index| {
+			let who = IndexToId::<Runtime, FellowshipCollectiveInstance>::get(0, index)?;
+			let rank = Collective::rank_of(&who)?;
+			let status = Member::get(&who)?;
+			let demotion_period = demotion_period(&params, rank);
+			let at_risk = !demotion_period.is_zero() &&
+				now >= status.last_proof.saturating_add(demotion_period);
+			at_risk.then_some((who, rank))
+		})
+		.collect();
+	(at_risk, (end < count).then_some(end))
+}
+
+/// Implementation of [`FellowshipApi::tracks_of_rank`].
+pub fn tracks_of_rank(rank: Rank) -> Vec<TrackAccess> {
+	// Proposals may be submitted on any track by the members allowed by `SubmitOrigin`.
+	let can_propose = rank >= MIN_RANK_TO_SUBMIT;
+	<super::tracks::TracksInfo as TracksInfo<Balance, BlockNumber>>::tracks()
+		.iter()
+		.filter_map(|(track, info)| {
+			let can_vote = rank >= MinRankOfClass::convert(*track);
+			(can_vote || can_propose).then(|| TrackAccess {
+				track: *track,
+				name: info.name.as_bytes().to_vec(),
+				can_vote,
+				can_propose,
+			})
+		})
+		.collect()
+}
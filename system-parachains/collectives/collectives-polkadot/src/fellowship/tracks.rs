@@ -16,6 +16,7 @@
 
 //! Track configurations for Fellowship.
 
+use super::ranks;
 use crate::{Balance, BlockNumber, RuntimeOrigin, DAYS, DOLLARS, HOURS, MINUTES};
 use pallet_ranked_collective::Rank;
 use sp_runtime::{traits::Convert, Perbill};
@@ -56,6 +57,12 @@ pub mod constants {
 	pub const PROMOTE_TO_4DAN: TrackId = 24;
 	pub const PROMOTE_TO_5DAN: TrackId = 25;
 	pub const PROMOTE_TO_6DAN: TrackId = 26;
+
+	// Treasury spend tracks (7 days) used to spend from the Fellowship treasury. The required
+	// rank for voting grows with the amount which may be spent.
+	pub const FELLOWS_SPEND: TrackId = 31;
+	pub const ARCHITECTS_SPEND: TrackId = 32;
+	pub const MASTERS_SPEND: TrackId = 33;
 }
 
 /// Convert the track ID (defined above) into the minimum rank (i.e. fellowship Dan grade) required
@@ -71,6 +78,10 @@ impl Convert<TrackId, Rank> for MinRankOfClass {
 			// A promotion vote; the track ID turns out to be 18 more than the minimum required
 			// rank.
 			promotion @ 21..=26 => promotion - 18,
+			// A treasury spend vote; the minimum required rank grows with the amount spent.
+			constants::FELLOWS_SPEND => ranks::DAN_3,
+			constants::ARCHITECTS_SPEND => ranks::DAN_4,
+			constants::MASTERS_SPEND => ranks::DAN_7,
 			_ => Rank::max_value(),
 		}
 	}
@@ -110,13 +121,30 @@ const PROMOTE_MIN_SUPPORT: pallet_referenda::Curve = pallet_referenda::Curve::Li
 	ceil: Perbill::from_percent(100),
 };
 
+const SPEND_MAX_DECIDING: u32 = 10;
+const SPEND_DECISION_DEPOSIT: Balance = 5 * DOLLARS;
+const SPEND_PREPARE_PERIOD: BlockNumber = 1 * HOURS;
+const SPEND_DECISION_PERIOD: BlockNumber = 7 * DAYS;
+const SPEND_CONFIRM_PERIOD: BlockNumber = 1 * DAYS;
+const SPEND_MIN_ENACTMENT_PERIOD: BlockNumber = 1 * HOURS;
+const SPEND_MIN_APPROVAL: pallet_referenda::Curve = pallet_referenda::Curve::LinearDecreasing {
+	length: Perbill::from_percent(100),
+	floor: Perbill::from_percent(60),
+	ceil: Perbill::from_percent(100),
+};
+const SPEND_MIN_SUPPORT: pallet_referenda::Curve = pallet_referenda::Curve::LinearDecreasing {
+	length: Perbill::from_percent(100),
+	floor: Perbill::from_percent(10),
+	ceil: Perbill::from_percent(100),
+};
+
 pub struct TracksInfo;
 impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
 	type Id = TrackId;
 	type RuntimeOrigin = <RuntimeOrigin as frame_support::traits::OriginTrait>::PalletsOrigin;
 	fn tracks() -> &'static [(Self::Id, pallet_referenda::TrackInfo<Balance, BlockNumber>)] {
 		use constants as tracks;
-		static DATA: [(TrackId, pallet_referenda::TrackInfo<Balance, BlockNumber>); 21] = [
+		static DATA: [(TrackId, pallet_referenda::TrackInfo<Balance, BlockNumber>); 24] = [
 			(
 				tracks::MEMBERS,
 				pallet_referenda::TrackInfo {
@@ -483,6 +511,48 @@ impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
 					min_support: PROMOTE_MIN_SUPPORT,
 				},
 			),
+			(
+				tracks::FELLOWS_SPEND,
+				pallet_referenda::TrackInfo {
+					name: "fellows spend",
+					max_deciding: SPEND_MAX_DECIDING,
+					decision_deposit: SPEND_DECISION_DEPOSIT,
+					prepare_period: SPEND_PREPARE_PERIOD,
+					decision_period: SPEND_DECISION_PERIOD,
+					confirm_period: SPEND_CONFIRM_PERIOD,
+					min_enactment_period: SPEND_MIN_ENACTMENT_PERIOD,
+					min_approval: SPEND_MIN_APPROVAL,
+					min_support: SPEND_MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::ARCHITECTS_SPEND,
+				pallet_referenda::TrackInfo {
+					name: "architects spend",
+					max_deciding: SPEND_MAX_DECIDING,
+					decision_deposit: SPEND_DECISION_DEPOSIT,
+					prepare_period: SPEND_PREPARE_PERIOD,
+					decision_period: SPEND_DECISION_PERIOD,
+					confirm_period: SPEND_CONFIRM_PERIOD,
+					min_enactment_period: SPEND_MIN_ENACTMENT_PERIOD,
+					min_approval: SPEND_MIN_APPROVAL,
+					min_support: SPEND_MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::MASTERS_SPEND,
+				pallet_referenda::TrackInfo {
+					name: "masters spend",
+					max_deciding: SPEND_MAX_DECIDING,
+					decision_deposit: SPEND_DECISION_DEPOSIT,
+					prepare_period: SPEND_PREPARE_PERIOD,
+					decision_period: SPEND_DECISION_PERIOD,
+					confirm_period: SPEND_CONFIRM_PERIOD,
+					min_enactment_period: SPEND_MIN_ENACTMENT_PERIOD,
+					min_approval: SPEND_MIN_APPROVAL,
+					min_support: SPEND_MIN_SUPPORT,
+				},
+			),
 		];
 		&DATA[..]
 	}
@@ -525,6 +595,10 @@ impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
 			Ok(Origin::PromoteTo5Dan) => Ok(tracks::PROMOTE_TO_5DAN),
 			Ok(Origin::PromoteTo6Dan) => Ok(tracks::PROMOTE_TO_6DAN),
 
+			Ok(Origin::FellowsSpender) => Ok(tracks::FELLOWS_SPEND),
+			Ok(Origin::ArchitectsSpender) => Ok(tracks::ARCHITECTS_SPEND),
+			Ok(Origin::MastersSpender) => Ok(tracks::MASTERS_SPEND),
+
 			_ => Err(()),
 		}
 	}
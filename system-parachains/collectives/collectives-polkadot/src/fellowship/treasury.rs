@@ -0,0 +1,411 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The Fellowship treasury.
+//!
+//! The funds of the Fellowship treasury are held on the Asset Hub, in the sovereign account of the
+//! location of this pallet. Spends are approved by the Fellowship spend tracks, each up to its own
+//! limit per asset, and paid out from the Asset Hub with a [`Pay`] implementation such as
+//! `PayOverXcm`.
+//!
+//! An approved spend is paid out by anyone calling [`Pallet::payout`] before it expires. Once the
+//! Asset Hub reported the outcome of the payment, [`Pallet::check_status`] removes the spend if it
+//! was paid, or allows the payout to be retried if the payment failed.
+//!
+//! The `pallet_treasury` version this runtime is built with only pays out the native currency of
+//! the chain it runs on, from a pot on that chain, so it cannot serve the Fellowship treasury.
+//!
+//! The pallet is instantiable; the Ambassador treasury is another instance of it, paying out from
+//! the sovereign account of its own pallet location on the Asset Hub.
+
+pub use pallet_fellowship_treasury::*;
+
+/// The largest amount of an asset an origin may spend at once.
+pub trait SpendLimit<AssetKind, Balance> {
+	/// The largest amount of `asset_kind` which may be spent at once.
+	fn max_amount(&self, asset_kind: &AssetKind) -> Balance;
+}
+
+/// Creates the arguments of spends in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait ArgumentsFactory<AssetKind, Beneficiary> {
+	/// An asset kind which can be paid out.
+	fn create_asset_kind(seed: u32) -> AssetKind;
+	/// A beneficiary which can be paid.
+	fn create_beneficiary(seed: [u8; 32]) -> Beneficiary;
+}
+
+/// Weight functions needed for `pallet_fellowship_treasury`.
+pub trait WeightInfo {
+	fn spend() -> frame_support::weights::Weight;
+	fn payout() -> frame_support::weights::Weight;
+	fn check_status() -> frame_support::weights::Weight;
+	fn void_spend() -> frame_support::weights::Weight;
+}
+
+#[frame_support::pallet]
+pub mod pallet_fellowship_treasury {
+	use super::{SpendLimit, WeightInfo};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::tokens::{Pay, PaymentStatus},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Saturating;
+	use sp_std::prelude::*;
+
+	pub type BalanceOf<T, I = ()> = <<T as Config<I>>::Paymaster as Pay>::Balance;
+	pub type PaymentIdOf<T, I = ()> = <<T as Config<I>>::Paymaster as Pay>::Id;
+	pub type SpendOf<T, I = ()> = Spend<
+		<T as Config<I>>::AssetKind,
+		BalanceOf<T, I>,
+		<T as Config<I>>::Beneficiary,
+		BlockNumberFor<T>,
+		PaymentIdOf<T, I>,
+	>;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The kinds of assets which may be spent.
+		type AssetKind: Parameter + MaxEncodedLen;
+
+		/// The receivers of spends.
+		type Beneficiary: Parameter + MaxEncodedLen;
+
+		/// The means of paying out spends from the treasury's funds.
+		type Paymaster: Pay<AssetKind = Self::AssetKind, Beneficiary = Self::Beneficiary>;
+
+		/// Origin allowed to spend, with the limits of what it may spend at once.
+		type SpendOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::SpendLimit>;
+
+		/// The limits of what a [`Config::SpendOrigin`] may spend at once.
+		type SpendLimit: SpendLimit<Self::AssetKind, BalanceOf<Self, I>>;
+
+		/// Origin allowed to void an approved spend which was not yet paid out.
+		type VoidOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The period within which an approved spend must be paid out.
+		#[pallet::constant]
+		type PayoutPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper to create the arguments of spends in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: super::ArgumentsFactory<Self::AssetKind, Self::Beneficiary>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	/// The state of the payment of a spend.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum PaymentState<Id> {
+		/// The spend was not paid out yet.
+		Pending,
+		/// The payment was started and its outcome is not known yet.
+		Attempted { id: Id },
+		/// The payment failed and may be retried.
+		Failed,
+	}
+
+	/// An approved spend.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Spend<AssetKind, Balance, Beneficiary, BlockNumber, PaymentId> {
+		/// The asset to pay out.
+		pub asset_kind: AssetKind,
+		/// The amount of the asset to pay out.
+		pub amount: Balance,
+		/// The account the asset is paid to.
+		pub beneficiary: Beneficiary,
+		/// The block after which the spend can no longer be paid out.
+		pub expire_at: BlockNumber,
+		/// The state of the payment.
+		pub status: PaymentState<PaymentId>,
+	}
+
+	/// The number of spends approved so far, i.e. the index of the next spend.
+	#[pallet::storage]
+	pub type SpendCount<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// The approved spends which were not yet paid out successfully or expired.
+	#[pallet::storage]
+	pub type Spends<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, SpendOf<T, I>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// A spend was approved.
+		SpendApproved {
+			index: u32,
+			asset_kind: T::AssetKind,
+			amount: BalanceOf<T, I>,
+			beneficiary: T::Beneficiary,
+			expire_at: BlockNumberFor<T>,
+		},
+		/// The payment of a spend was started.
+		Paid { index: u32, payment_id: PaymentIdOf<T, I> },
+		/// The payment of a spend failed; its payout may be retried.
+		PaymentFailed { index: u32, payment_id: PaymentIdOf<T, I> },
+		/// A spend was paid out or expired, and removed.
+		SpendProcessed { index: u32 },
+		/// An approved spend was voided.
+		SpendVoided { index: u32 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The amount exceeds what the origin may spend at once.
+		InsufficientPermission,
+		/// There is no spend with the given index.
+		InvalidIndex,
+		/// The spend expired and can no longer be paid out.
+		SpendExpired,
+		/// The spend was already paid out, or its payment is in progress.
+		AlreadyAttempted,
+		/// The payment could not be started.
+		PayoutError,
+		/// The spend was not paid out yet.
+		NotAttempted,
+		/// The outcome of the payment is not known yet.
+		Inconclusive,
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Approve spending `amount` of `asset_kind` to `beneficiary`.
+		///
+		/// The spend must be paid out with [`Pallet::payout`] within `PayoutPeriod`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::spend())]
+		pub fn spend(
+			origin: OriginFor<T>,
+			asset_kind: Box<T::AssetKind>,
+			#[pallet::compact] amount: BalanceOf<T, I>,
+			beneficiary: Box<T::Beneficiary>,
+		) -> DispatchResult {
+			let limit = T::SpendOrigin::ensure_origin(origin)?;
+			ensure!(amount <= limit.max_amount(&asset_kind), Error::<T, I>::InsufficientPermission);
+
+			let index = SpendCount::<T, I>::get();
+			let expire_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::PayoutPeriod::get());
+			Spends::<T, I>::insert(
+				index,
+				Spend {
+					asset_kind: (*asset_kind).clone(),
+					amount,
+					beneficiary: (*beneficiary).clone(),
+					expire_at,
+					status: PaymentState::Pending,
+				},
+			);
+			SpendCount::<T, I>::put(index.saturating_add(1));
+
+			Self::deposit_event(Event::<T, I>::SpendApproved {
+				index,
+				asset_kind: *asset_kind,
+				amount,
+				beneficiary: *beneficiary,
+				expire_at,
+			});
+			Ok(())
+		}
+
+		/// Pay out the approved spend `index`.
+		///
+		/// Anyone may call this. A failed payment may be paid out again, until the spend expires.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::payout())]
+		pub fn payout(origin: OriginFor<T>, index: u32) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut spend = Spends::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < spend.expire_at,
+				Error::<T, I>::SpendExpired
+			);
+			ensure!(
+				!matches!(spend.status, PaymentState::Attempted { .. }),
+				Error::<T, I>::AlreadyAttempted
+			);
+
+			let payment_id =
+				T::Paymaster::pay(&spend.beneficiary, spend.asset_kind.clone(), spend.amount)
+					.map_err(|_| Error::<T, I>::PayoutError)?;
+			spend.status = PaymentState::Attempted { id: payment_id };
+			Spends::<T, I>::insert(index, spend);
+
+			Self::deposit_event(Event::<T, I>::Paid { index, payment_id });
+			Ok(())
+		}
+
+		/// Check the outcome of the payment of spend `index`.
+		///
+		/// A paid spend, or one which expired without a payment in progress, is removed. A spend
+		/// whose payment failed may be paid out again.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::check_status())]
+		pub fn check_status(origin: OriginFor<T>, index: u32) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let mut spend = Spends::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			let now = frame_system::Pallet::<T>::block_number();
+
+			let PaymentState::Attempted { id } = spend.status else {
+				ensure!(now >= spend.expire_at, Error::<T, I>::NotAttempted);
+				Spends::<T, I>::remove(index);
+				Self::deposit_event(Event::<T, I>::SpendProcessed { index });
+				return Ok(Pays::No.into())
+			};
+
+			match T::Paymaster::check_payment(id) {
+				PaymentStatus::Success => {
+					Spends::<T, I>::remove(index);
+					Self::deposit_event(Event::<T, I>::SpendProcessed { index });
+					Ok(Pays::No.into())
+				},
+				PaymentStatus::Failure => {
+					spend.status = PaymentState::Failed;
+					Spends::<T, I>::insert(index, spend);
+					Self::deposit_event(Event::<T, I>::PaymentFailed { index, payment_id: id });
+					Ok(Pays::No.into())
+				},
+				PaymentStatus::InProgress | PaymentStatus::Unknown =>
+					Err(Error::<T, I>::Inconclusive.into()),
+			}
+		}
+
+		/// Void the approved spend `index`, which must not have been paid out yet.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::void_spend())]
+		pub fn void_spend(origin: OriginFor<T>, index: u32) -> DispatchResult {
+			T::VoidOrigin::ensure_origin(origin)?;
+			let spend = Spends::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			ensure!(
+				!matches!(spend.status, PaymentState::Attempted { .. }),
+				Error::<T, I>::AlreadyAttempted
+			);
+
+			Spends::<T, I>::remove(index);
+			Self::deposit_event(Event::<T, I>::SpendVoided { index });
+			Ok(())
+		}
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking {
+	use super::*;
+	use frame_benchmarking::v2::*;
+	use frame_support::traits::{tokens::Pay, EnsureOrigin};
+	use frame_system::RawOrigin;
+	use sp_std::prelude::*;
+
+	/// Approve a spend of the largest amount the successful spend origin may spend.
+	fn approve_spend<T: Config<I>, I: 'static>() -> Result<u32, BenchmarkError> {
+		let origin =
+			T::SpendOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let limit =
+			T::SpendOrigin::try_origin(origin.clone()).map_err(|_| BenchmarkError::Weightless)?;
+		let (asset_kind, amount, beneficiary) = spend_args::<T, I>(&limit);
+		let index = SpendCount::<T, I>::get();
+		Pallet::<T, I>::spend(origin, Box::new(asset_kind), amount, Box::new(beneficiary))?;
+		Ok(index)
+	}
+
+	fn spend_args<T: Config<I>, I: 'static>(
+		limit: &T::SpendLimit,
+	) -> (T::AssetKind, BalanceOf<T, I>, T::Beneficiary) {
+		let asset_kind = T::BenchmarkHelper::create_asset_kind(0);
+		let beneficiary = T::BenchmarkHelper::create_beneficiary([0; 32]);
+		let amount = limit.max_amount(&asset_kind);
+		(asset_kind, amount, beneficiary)
+	}
+
+	#[instance_benchmarks]
+	mod benchmarks {
+		use super::*;
+
+		#[benchmark]
+		fn spend() -> Result<(), BenchmarkError> {
+			let origin =
+				T::SpendOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+			let limit = T::SpendOrigin::try_origin(origin.clone())
+				.map_err(|_| BenchmarkError::Weightless)?;
+			let (asset_kind, amount, beneficiary) = spend_args::<T, I>(&limit);
+
+			#[extrinsic_call]
+			_(origin as T::RuntimeOrigin, Box::new(asset_kind), amount, Box::new(beneficiary));
+
+			assert!(Spends::<T, I>::contains_key(0));
+			Ok(())
+		}
+
+		#[benchmark]
+		fn payout() -> Result<(), BenchmarkError> {
+			let index = approve_spend::<T, I>()?;
+			let spend = Spends::<T, I>::get(index).unwrap();
+			T::Paymaster::ensure_successful(&spend.beneficiary, spend.asset_kind, spend.amount);
+			let caller: T::AccountId = account("caller", 0, 0);
+
+			#[extrinsic_call]
+			_(RawOrigin::Signed(caller), index);
+
+			assert!(matches!(
+				Spends::<T, I>::get(index).unwrap().status,
+				PaymentState::Attempted { .. }
+			));
+			Ok(())
+		}
+
+		#[benchmark]
+		fn check_status() -> Result<(), BenchmarkError> {
+			let index = approve_spend::<T, I>()?;
+			let spend = Spends::<T, I>::get(index).unwrap();
+			T::Paymaster::ensure_successful(&spend.beneficiary, spend.asset_kind, spend.amount);
+			let caller: T::AccountId = account("caller", 0, 0);
+			Pallet::<T, I>::payout(RawOrigin::Signed(caller.clone()).into(), index)?;
+			let PaymentState::Attempted { id } = Spends::<T, I>::get(index).unwrap().status else {
+				return Err(BenchmarkError::Weightless)
+			};
+			T::Paymaster::ensure_concluded(id);
+
+			#[extrinsic_call]
+			_(RawOrigin::Signed(caller), index);
+
+			assert!(!Spends::<T, I>::contains_key(index));
+			Ok(())
+		}
+
+		#[benchmark]
+		fn void_spend() -> Result<(), BenchmarkError> {
+			let index = approve_spend::<T, I>()?;
+			let origin =
+				T::VoidOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+			#[extrinsic_call]
+			_(origin as T::RuntimeOrigin, index);
+
+			assert!(!Spends::<T, I>::contains_key(index));
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,339 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Keeper of the salary and rank bookkeeping of a ranked collective.
+//!
+//! `pallet_salary` requires every member to `induct` themselves, to `register` for each cycle and
+//! to claim their `payout` once the payout period opened, while `pallet_core_fellowship` relies on
+//! someone calling `bump` to demote the members whose rank was not retained in time. The keeper
+//! does all of this on behalf of the members with the spare weight of each block.
+//!
+//! Each salary cycle is worked through in sweeps over all members of the collective:
+//! - a [`Sweep::Register`] sweep at the start of the registration period;
+//! - a [`Sweep::Payout`] sweep once the payout period opened;
+//! - a [`Sweep::Bump`] sweep demoting the members whose rank retention expired.
+//!
+//! A sweep remembers its position within the members in [`Ongoing`], so it continues in the
+//! following blocks until all members were visited. A new salary cycle is started by the keeper
+//! once the previous one is over and its payout sweep is done.
+//!
+//! Most calls made for a member are expected to fail for some members, e.g. registering a member
+//! without a salary or bumping a member whose rank retention did not expire yet. Such failures are
+//! logged and the member is skipped; an induction or a new salary cycle failing is logged as a
+//! warning.
+//!
+//! Each call is made within its own storage layer, so a call failing half-way leaves no partial
+//! changes behind, as if it was dispatched as an extrinsic.
+
+pub use pallet_keeper::*;
+
+#[frame_support::pallet]
+pub mod pallet_keeper {
+	use frame_support::{pallet_prelude::*, storage::with_storage_layer, PalletId};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use pallet_ranked_collective::{IndexToId, MemberCount, MemberIndex};
+	use pallet_salary::CycleIndexOf;
+	use sp_runtime::traits::{AccountIdConversion, Saturating};
+
+	/// The rank under which all members of the collective are enumerated.
+	const ALL_MEMBERS_RANK: pallet_ranked_collective::Rank = 0;
+
+	const LOG_TARGET: &str = "runtime::keeper";
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>:
+		frame_system::Config
+		+ pallet_ranked_collective::Config<I>
+		+ pallet_core_fellowship::Config<I>
+		+ pallet_salary::Config<I>
+	{
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The keeper's pallet ID, used to derive the account which bumps salary cycles and the
+		/// members of the collective.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	/// A kind of sweep over all members of the collective.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum Sweep {
+		/// Induct all members into the salary system and register them for the cycle.
+		Register,
+		/// Claim the payout of all registered members.
+		Payout,
+		/// Demote all members whose rank retention expired.
+		Bump,
+	}
+
+	/// The position of an ongoing sweep.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Cursor<CycleIndex> {
+		/// The kind of the sweep.
+		pub sweep: Sweep,
+		/// The salary cycle the sweep is done for.
+		pub cycle: CycleIndex,
+		/// The index (under rank 0) of the next member to visit.
+		pub next: MemberIndex,
+	}
+
+	pub type CursorOf<T> = Cursor<CycleIndexOf<T>>;
+
+	/// The sweep currently in progress, if any.
+	#[pallet::storage]
+	pub type Ongoing<T: Config<I>, I: 'static = ()> = StorageValue<_, CursorOf<T>, OptionQuery>;
+
+	/// The last salary cycle for which each kind of sweep was completed.
+	#[pallet::storage]
+	pub type LastCompleted<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, Sweep, CycleIndexOf<T>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// A new salary cycle was started by the keeper.
+		CycleStarted { cycle: CycleIndexOf<T> },
+		/// A sweep over all members was started.
+		SweepStarted { sweep: Sweep, cycle: CycleIndexOf<T> },
+		/// A sweep over all members was completed.
+		SweepCompleted { sweep: Sweep, cycle: CycleIndexOf<T> },
+		/// A member was registered for the salary payout of the cycle.
+		Registered { who: T::AccountId, cycle: CycleIndexOf<T> },
+		/// The salary payout of a member was claimed.
+		PaidOut { who: T::AccountId, cycle: CycleIndexOf<T> },
+		/// A member whose rank retention expired was bumped.
+		Bumped { who: T::AccountId },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::keep(now, remaining_weight)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// The account of the keeper.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Progress the bookkeeping as far as `limit` allows. Returns the weight used.
+		pub(crate) fn keep(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+			let mut used = Weight::zero();
+
+			loop {
+				let overhead = Self::overhead_weight();
+				let cursor = match Ongoing::<T, I>::get() {
+					Some(cursor) => cursor,
+					None => {
+						let start_weight = overhead.saturating_add(Self::salary_bump_weight());
+						if !limit.all_gte(used.saturating_add(start_weight)) {
+							break
+						}
+						used.saturating_accrue(start_weight);
+						match Self::next_sweep(now) {
+							Some(cursor) => {
+								Self::deposit_event(Event::<T, I>::SweepStarted {
+									sweep: cursor.sweep,
+									cycle: cursor.cycle,
+								});
+								cursor
+							},
+							None => break,
+						}
+					},
+				};
+
+				let step_weight = overhead.saturating_add(Self::step_weight(cursor.sweep));
+				if !limit.all_gte(used.saturating_add(step_weight)) {
+					// Keep the position for the next block.
+					Ongoing::<T, I>::put(cursor);
+					break
+				}
+				used.saturating_accrue(step_weight);
+
+				match Self::step(cursor) {
+					Some(next) => Ongoing::<T, I>::put(next),
+					None => {
+						Ongoing::<T, I>::kill();
+						LastCompleted::<T, I>::insert(cursor.sweep, cursor.cycle);
+						Self::deposit_event(Event::<T, I>::SweepCompleted {
+							sweep: cursor.sweep,
+							cycle: cursor.cycle,
+						});
+					},
+				}
+			}
+
+			used
+		}
+
+		/// Determine the next sweep due at `now`, starting a new salary cycle if the current one
+		/// is over and all of its sweeps were completed.
+		fn next_sweep(now: BlockNumberFor<T>) -> Option<CursorOf<T>> {
+			let status = pallet_salary::Pallet::<T, I>::status()?;
+			let cycle = status.cycle_index;
+			let registration_ends = status
+				.cycle_start
+				.saturating_add(<T as pallet_salary::Config<I>>::RegistrationPeriod::get());
+			let cycle_ends =
+				status.cycle_start.saturating_add(pallet_salary::Pallet::<T, I>::cycle_period());
+			let done = |sweep| LastCompleted::<T, I>::get(sweep) == Some(cycle);
+
+			let sweep = if now < registration_ends {
+				[Sweep::Register, Sweep::Bump].into_iter().find(|s| !done(*s))
+			} else {
+				[Sweep::Payout, Sweep::Bump].into_iter().find(|s| !done(*s))
+			};
+			if let Some(sweep) = sweep {
+				return Some(Cursor { sweep, cycle, next: 0 })
+			}
+
+			if now >= cycle_ends {
+				match with_storage_layer(|| {
+					pallet_salary::Pallet::<T, I>::bump(
+						RawOrigin::Signed(Self::account_id()).into(),
+					)
+				}) {
+					Ok(_) => Self::deposit_event(Event::<T, I>::CycleStarted {
+						cycle: cycle.saturating_add(1u32.into()),
+					}),
+					Err(e) => log::warn!(
+						target: LOG_TARGET,
+						"Failed to start the salary cycle after {:?}: {:?}",
+						cycle,
+						e,
+					),
+				}
+			}
+			None
+		}
+
+		/// Visit the next member of the sweep at `cursor`. Returns the cursor to continue with, or
+		/// `None` if all members were visited.
+		fn step(cursor: CursorOf<T>) -> Option<CursorOf<T>> {
+			let count = MemberCount::<T, I>::get(ALL_MEMBERS_RANK);
+			if cursor.next >= count {
+				return None
+			}
+			let Some(who) = IndexToId::<T, I>::get(ALL_MEMBERS_RANK, cursor.next) else {
+				return Some(Cursor { next: cursor.next.saturating_add(1), ..cursor })
+			};
+			let signed = || RawOrigin::Signed(who.clone()).into();
+
+			match cursor.sweep {
+				Sweep::Register => {
+					if pallet_salary::Pallet::<T, I>::last_active(&who).is_err() {
+						if let Err(e) =
+							with_storage_layer(|| pallet_salary::Pallet::<T, I>::induct(signed()))
+						{
+							log::warn!(
+								target: LOG_TARGET,
+								"Failed to induct {:?} into the salary system: {:?}",
+								who,
+								e,
+							);
+						}
+					}
+					match with_storage_layer(|| pallet_salary::Pallet::<T, I>::register(signed())) {
+						Ok(_) => Self::deposit_event(Event::<T, I>::Registered {
+							who,
+							cycle: cursor.cycle,
+						}),
+						Err(e) => Self::skipped(cursor, &who, e),
+					}
+				},
+				Sweep::Payout => {
+					let paid =
+						with_storage_layer(|| pallet_salary::Pallet::<T, I>::payout(signed()));
+					match paid {
+						Ok(_) =>
+							Self::deposit_event(Event::<T, I>::PaidOut { who, cycle: cursor.cycle }),
+						Err(e) => Self::skipped(cursor, &who, e),
+					}
+				},
+				Sweep::Bump => {
+					let bumped = match with_storage_layer(|| {
+						pallet_core_fellowship::Pallet::<T, I>::bump(
+							RawOrigin::Signed(Self::account_id()).into(),
+							who.clone(),
+						)
+					}) {
+						Ok(_) => {
+							Self::deposit_event(Event::<T, I>::Bumped { who: who.clone() });
+							true
+						},
+						Err(e) => {
+							Self::skipped(cursor, &who, e);
+							false
+						},
+					};
+					// An offboarded member is swapped with the last member of the collective, so
+					// the same position must be visited again.
+					if bumped && IndexToId::<T, I>::get(ALL_MEMBERS_RANK, cursor.next) != Some(who)
+					{
+						return Some(cursor)
+					}
+				},
+			}
+
+			Some(Cursor { next: cursor.next.saturating_add(1), ..cursor })
+		}
+
+		/// Log that the call made for `who` within the sweep at `cursor` failed with `error`.
+		fn skipped(cursor: CursorOf<T>, who: &T::AccountId, error: impl sp_std::fmt::Debug) {
+			log::debug!(
+				target: LOG_TARGET,
+				"Skipped {:?} in the {:?} sweep of cycle {:?}: {:?}",
+				who,
+				cursor.sweep,
+				cursor.cycle,
+				error,
+			);
+		}
+
+		/// The weight of reading the cursor, the status of the salary and the member count, and of
+		/// writing the cursor.
+		pub(crate) fn overhead_weight() -> Weight {
+			T::DbWeight::get().reads_writes(3, 1)
+		}
+
+		/// The weight of visiting a single member within a sweep of kind `sweep`.
+		pub(crate) fn step_weight(sweep: Sweep) -> Weight {
+			use pallet_core_fellowship::WeightInfo as _;
+			use pallet_salary::WeightInfo as _;
+
+			match sweep {
+				Sweep::Register => <T as pallet_salary::Config<I>>::WeightInfo::induct()
+					.saturating_add(<T as pallet_salary::Config<I>>::WeightInfo::register()),
+				Sweep::Payout => <T as pallet_salary::Config<I>>::WeightInfo::payout(),
+				Sweep::Bump => <T as pallet_core_fellowship::Config<I>>::WeightInfo::bump(),
+			}
+		}
+
+		/// The weight of starting a new salary cycle.
+		pub(crate) fn salary_bump_weight() -> Weight {
+			use pallet_salary::WeightInfo as _;
+			<T as pallet_salary::Config<I>>::WeightInfo::bump()
+		}
+	}
+}
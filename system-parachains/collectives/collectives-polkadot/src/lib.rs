@@ -36,14 +36,23 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+// Alliance membership criteria.
+pub mod alliance;
 pub mod impls;
+pub mod keeper;
 mod weights;
 pub mod xcm_config;
 // Fellowship configurations.
 pub mod fellowship;
+// Ambassador Program configurations.
+pub mod ambassador;
+#[cfg(test)]
+mod tests;
 
+use alliance::pallet_alliance_identity;
+use ambassador::pallet_ambassador_origins;
 use cumulus_pallet_parachain_system::RelayNumberStrictlyIncreases;
-use fellowship::{pallet_fellowship_origins, Fellows};
+use fellowship::{pallet_fellowship_origins, treasury::pallet_fellowship_treasury, Fellows};
 use impls::{AllianceProposalProvider, EqualOrGreatestRootCmp, ToParentTreasury};
 use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
@@ -75,12 +84,12 @@ use frame_system::{
 pub use parachains_common as common;
 use parachains_common::{
 	impls::DealWithFees,
-	polkadot::{account::*, consensus::*, currency::*, fee::WeightToFee},
+	polkadot::{account::*, currency::*, fee::WeightToFee},
 	AccountId, AuraId, Balance, BlockNumber, Hash, Header, Nonce, Signature,
-	AVERAGE_ON_INITIALIZE_RATIO, DAYS, HOURS, MAXIMUM_BLOCK_WEIGHT, MINUTES, NORMAL_DISPATCH_RATIO,
-	SLOT_DURATION,
+	AVERAGE_ON_INITIALIZE_RATIO, DAYS, HOURS, MINUTES, NORMAL_DISPATCH_RATIO, SLOT_DURATION,
 };
 use sp_runtime::RuntimeDebug;
+use system_parachains_constants::{consensus::*, MAXIMUM_BLOCK_WEIGHT};
 use xcm_config::{GovernanceLocation, XcmConfig, XcmOriginToTransactDispatchOrigin};
 
 #[cfg(any(feature = "std", test))]
@@ -150,7 +159,7 @@ parameter_types! {
 
 // Configure FRAME pallets to include in runtime.
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = alliance::AllianceProposalFilter;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type AccountId = AccountId;
@@ -286,6 +295,8 @@ pub enum ProxyType {
 	Alliance,
 	/// Fellowship proxy. Allows calls related to the Fellowship.
 	Fellowship,
+	/// Ambassador proxy. Allows calls related to the Ambassador Program.
+	Ambassador,
 }
 impl Default for ProxyType {
 	fn default() -> Self {
@@ -320,6 +331,17 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 				c,
 				RuntimeCall::FellowshipCollective { .. } |
 					RuntimeCall::FellowshipReferenda { .. } |
+					RuntimeCall::FellowshipTreasury { .. } |
+					RuntimeCall::Utility { .. } |
+					RuntimeCall::Multisig { .. }
+			),
+			ProxyType::Ambassador => matches!(
+				c,
+				RuntimeCall::AmbassadorCollective { .. } |
+					RuntimeCall::AmbassadorReferenda { .. } |
+					RuntimeCall::AmbassadorCore { .. } |
+					RuntimeCall::AmbassadorSalary { .. } |
+					RuntimeCall::AmbassadorTreasury { .. } |
 					RuntimeCall::Utility { .. } |
 					RuntimeCall::Multisig { .. }
 			),
@@ -499,7 +521,7 @@ impl pallet_alliance::Config for Runtime {
 	type InitializeMembers = AllianceMotion;
 	type MembershipChanged = AllianceMotion;
 	type RetirementPeriod = AllianceRetirementPeriod;
-	type IdentityVerifier = (); // Don't block accounts on identity criteria
+	type IdentityVerifier = alliance::CachedIdentityVerifier;
 	type ProposalProvider = AllianceProposalProvider<Runtime, AllianceCollective>;
 	type MaxProposals = ConstU32<ALLIANCE_MAX_MEMBERS>;
 	type MaxFellows = ConstU32<MAX_FELLOWS>;
@@ -512,6 +534,14 @@ impl pallet_alliance::Config for Runtime {
 	type WeightInfo = weights::pallet_alliance::WeightInfo<Runtime>;
 }
 
+impl pallet_alliance_identity::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	// Identities are noted by the Relay Chain governance, which is Root on this chain.
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type MaxWebsiteUrlLength = ConstU32<255>;
+	type WeightInfo = weights::pallet_alliance_identity::WeightInfo<Runtime>;
+}
+
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * RuntimeBlockWeights::get().max_block;
 }
@@ -594,6 +624,7 @@ construct_runtime!(
 		// The Alliance.
 		Alliance: pallet_alliance::{Pallet, Call, Storage, Event<T>, Config<T>} = 50,
 		AllianceMotion: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>} = 51,
+		AllianceIdentity: pallet_alliance_identity::{Pallet, Call, Storage, Event<T>} = 52,
 
 		// The Fellowship.
 		// pub type FellowshipCollectiveInstance = pallet_ranked_collective::Instance1;
@@ -605,6 +636,21 @@ construct_runtime!(
 		FellowshipCore: pallet_core_fellowship::<Instance1>::{Pallet, Call, Storage, Event<T>} = 63,
 		// pub type FellowshipSalaryInstance = pallet_salary::Instance1;
 		FellowshipSalary: pallet_salary::<Instance1>::{Pallet, Call, Storage, Event<T>} = 64,
+		FellowshipTreasury: pallet_fellowship_treasury::{Pallet, Call, Storage, Event<T>} = 65,
+		FellowshipKeeper: keeper::<Instance1>::{Pallet, Storage, Event<T>} = 66,
+
+		// The Ambassador Program.
+		// pub type AmbassadorCollectiveInstance = pallet_ranked_collective::Instance2;
+		AmbassadorCollective: pallet_ranked_collective::<Instance2>::{Pallet, Call, Storage, Event<T>} = 70,
+		// pub type AmbassadorReferendaInstance = pallet_referenda::Instance2;
+		AmbassadorReferenda: pallet_referenda::<Instance2>::{Pallet, Call, Storage, Event<T>} = 71,
+		AmbassadorOrigins: pallet_ambassador_origins::{Origin} = 72,
+		// pub type AmbassadorCoreInstance = pallet_core_fellowship::Instance2;
+		AmbassadorCore: pallet_core_fellowship::<Instance2>::{Pallet, Call, Storage, Event<T>} = 73,
+		// pub type AmbassadorSalaryInstance = pallet_salary::Instance2;
+		AmbassadorSalary: pallet_salary::<Instance2>::{Pallet, Call, Storage, Event<T>} = 74,
+		// pub type AmbassadorTreasuryInstance = pallet_fellowship_treasury::Instance1;
+		AmbassadorTreasury: pallet_fellowship_treasury::<Instance1>::{Pallet, Call, Storage, Event<T>} = 75,
 	}
 );
 
@@ -708,6 +754,7 @@ mod benches {
 		[pallet_collator_selection, CollatorSelection]
 		[cumulus_pallet_xcmp_queue, XcmpQueue]
 		[pallet_alliance, Alliance]
+		[pallet_alliance_identity, AllianceIdentity]
 		[pallet_collective, AllianceMotion]
 		[pallet_xcm, PolkadotXcm]
 		[pallet_preimage, Preimage]
@@ -716,6 +763,12 @@ mod benches {
 		[pallet_ranked_collective, FellowshipCollective]
 		[pallet_core_fellowship, FellowshipCore]
 		[pallet_salary, FellowshipSalary]
+		[pallet_fellowship_treasury, FellowshipTreasury]
+		[pallet_referenda, AmbassadorReferenda]
+		[pallet_ranked_collective, AmbassadorCollective]
+		[pallet_core_fellowship, AmbassadorCore]
+		[pallet_salary, AmbassadorSalary]
+		[pallet_fellowship_treasury, AmbassadorTreasury]
 	);
 }
 
@@ -863,6 +916,27 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl fellowship::runtime_api::FellowshipApi<Block, AccountId, BlockNumber> for Runtime {
+		fn member_status(who: AccountId) -> Option<fellowship::runtime_api::MemberStatus<BlockNumber>> {
+			fellowship::runtime_api::member_status(who)
+		}
+
+		fn members_at_risk(
+			now: BlockNumber,
+			from: pallet_ranked_collective::MemberIndex,
+			max: u32,
+		) -> (
+			Vec<(AccountId, pallet_ranked_collective::Rank)>,
+			Option<pallet_ranked_collective::MemberIndex>,
+		) {
+			fellowship::runtime_api::members_at_risk(now, from, max)
+		}
+
+		fn tracks_of_rank(rank: pallet_ranked_collective::Rank) -> Vec<fellowship::runtime_api::TrackAccess> {
+			fellowship::runtime_api::tracks_of_rank(rank)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
@@ -0,0 +1,828 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Polkadot Collectives Runtime Configuration
+
+use crate::{alliance::*, *};
+use frame_support::{assert_noop, assert_ok, traits::Contains, BoundedVec};
+use pallet_alliance::UnscrupulousItem;
+use sp_runtime::{traits::Dispatchable, BuildStorage};
+
+const FELLOW: AccountId = AccountId::new([1u8; 32]);
+const APPLICANT: AccountId = AccountId::new([2u8; 32]);
+const SUB_ACCOUNT: AccountId = AccountId::new([3u8; 32]);
+
+/// `pallet_identity::IdentityField::Display | pallet_identity::IdentityField::Web`.
+const DISPLAY_AND_WEB: u64 = 1 << 0 | 1 << 2;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(FELLOW, 10_000 * UNITS), (APPLICANT, 10_000 * UNITS)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Alliance::init_members(RuntimeOrigin::root(), vec![FELLOW], vec![]));
+	});
+	ext
+}
+
+fn website(url: &str) -> BoundedVec<u8, ConstU32<255>> {
+	url.as_bytes().to_vec().try_into().unwrap()
+}
+
+fn note_identity(who: AccountId, proof: IdentityProofOf<Runtime>) {
+	assert_ok!(AllianceIdentity::note_identity(RuntimeOrigin::root(), who, proof));
+}
+
+fn good_identity() -> IdentityProofOf<Runtime> {
+	IdentityProof {
+		fields: DISPLAY_AND_WEB,
+		web: Some(website("https://applicant.io")),
+		good_judgement: true,
+		super_account: None,
+		relay_block: 10,
+	}
+}
+
+#[test]
+fn join_requires_cached_identity() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Alliance::join_alliance(RuntimeOrigin::signed(APPLICANT)),
+			pallet_alliance::Error::<Runtime>::WithoutRequiredIdentityFields,
+		);
+
+		note_identity(APPLICANT, good_identity());
+		assert_ok!(Alliance::join_alliance(RuntimeOrigin::signed(APPLICANT)));
+		assert!(pallet_alliance::Members::<Runtime>::get(pallet_alliance::MemberRole::Ally)
+			.contains(&APPLICANT));
+	});
+}
+
+#[test]
+fn join_requires_verified_website() {
+	new_test_ext().execute_with(|| {
+		note_identity(APPLICANT, IdentityProof { web: None, ..good_identity() });
+		assert_noop!(
+			Alliance::join_alliance(RuntimeOrigin::signed(APPLICANT)),
+			pallet_alliance::Error::<Runtime>::WithoutRequiredIdentityFields,
+		);
+	});
+}
+
+#[test]
+fn join_requires_good_judgement() {
+	new_test_ext().execute_with(|| {
+		note_identity(APPLICANT, IdentityProof { good_judgement: false, ..good_identity() });
+		assert_noop!(
+			Alliance::join_alliance(RuntimeOrigin::signed(APPLICANT)),
+			pallet_alliance::Error::<Runtime>::WithoutGoodIdentityJudgement,
+		);
+	});
+}
+
+#[test]
+fn join_accepts_sub_identity_of_good_identity() {
+	new_test_ext().execute_with(|| {
+		note_identity(SUB_ACCOUNT, good_identity());
+		note_identity(
+			APPLICANT,
+			IdentityProof {
+				fields: 0,
+				web: None,
+				good_judgement: false,
+				super_account: Some(SUB_ACCOUNT),
+				relay_block: 10,
+			},
+		);
+		assert_ok!(Alliance::join_alliance(RuntimeOrigin::signed(APPLICANT)));
+	});
+}
+
+#[test]
+fn join_rejects_unscrupulous_website() {
+	new_test_ext().execute_with(|| {
+		note_identity(APPLICANT, good_identity());
+		assert_ok!(Alliance::add_unscrupulous_items(
+			RuntimeOrigin::root(),
+			vec![UnscrupulousItem::Website(website("https://applicant.io"))],
+		));
+		assert_noop!(
+			Alliance::join_alliance(RuntimeOrigin::signed(APPLICANT)),
+			pallet_alliance::Error::<Runtime>::WithoutRequiredIdentityFields,
+		);
+	});
+}
+
+#[test]
+fn join_rejects_unscrupulous_account() {
+	new_test_ext().execute_with(|| {
+		note_identity(APPLICANT, good_identity());
+		assert_ok!(Alliance::add_unscrupulous_items(
+			RuntimeOrigin::root(),
+			vec![UnscrupulousItem::AccountId(APPLICANT)],
+		));
+		assert_noop!(
+			Alliance::join_alliance(RuntimeOrigin::signed(APPLICANT)),
+			pallet_alliance::Error::<Runtime>::AccountNonGrata,
+		);
+	});
+}
+
+#[test]
+fn stale_identity_is_rejected() {
+	new_test_ext().execute_with(|| {
+		note_identity(APPLICANT, good_identity());
+		assert_noop!(
+			AllianceIdentity::note_identity(
+				RuntimeOrigin::root(),
+				APPLICANT,
+				IdentityProof { relay_block: 9, ..good_identity() },
+			),
+			pallet_alliance_identity::Error::<Runtime>::StaleIdentity,
+		);
+		assert_noop!(
+			AllianceIdentity::note_identity(
+				RuntimeOrigin::signed(APPLICANT),
+				APPLICANT,
+				good_identity(),
+			),
+			sp_runtime::DispatchError::BadOrigin,
+		);
+	});
+}
+
+#[test]
+fn proposals_elevating_unscrupulous_accounts_are_filtered() {
+	new_test_ext().execute_with(|| {
+		let elevate = |who: AccountId| {
+			Box::new(RuntimeCall::Alliance(pallet_alliance::Call::elevate_ally {
+				ally: who.into(),
+			}))
+		};
+		let propose = |proposal: Box<RuntimeCall>| {
+			RuntimeCall::Alliance(pallet_alliance::Call::propose {
+				threshold: 2,
+				proposal,
+				length_bound: 1_000,
+			})
+		};
+		let propose_motion = |proposal: Box<RuntimeCall>| {
+			RuntimeCall::AllianceMotion(pallet_collective::Call::propose {
+				threshold: 2,
+				proposal,
+				length_bound: 1_000,
+			})
+		};
+		let batched = |proposal: Box<RuntimeCall>| {
+			Box::new(RuntimeCall::Utility(pallet_utility::Call::batch_all {
+				calls: vec![*proposal],
+			}))
+		};
+
+		assert!(AllianceProposalFilter::contains(&propose(elevate(APPLICANT))));
+		assert!(AllianceProposalFilter::contains(&propose_motion(elevate(APPLICANT))));
+
+		assert_ok!(Alliance::add_unscrupulous_items(
+			RuntimeOrigin::root(),
+			vec![UnscrupulousItem::AccountId(APPLICANT)],
+		));
+
+		assert!(!AllianceProposalFilter::contains(&propose(elevate(APPLICANT))));
+		assert!(!AllianceProposalFilter::contains(&propose_motion(elevate(APPLICANT))));
+		assert!(!AllianceProposalFilter::contains(&propose(batched(elevate(APPLICANT)))));
+		assert!(!AllianceProposalFilter::contains(&propose(Box::new(RuntimeCall::Alliance(
+			pallet_alliance::Call::nominate_ally { who: APPLICANT.into() }
+		)))));
+		assert!(AllianceProposalFilter::contains(&propose(elevate(SUB_ACCOUNT))));
+
+		// Calls wrapped in any other call are checked too.
+		let wrapped = [
+			RuntimeCall::Utility(pallet_utility::Call::as_derivative {
+				index: 0,
+				call: elevate(APPLICANT),
+			}),
+			RuntimeCall::Utility(pallet_utility::Call::with_weight {
+				call: elevate(APPLICANT),
+				weight: Weight::zero(),
+			}),
+			RuntimeCall::Proxy(pallet_proxy::Call::proxy {
+				real: FELLOW.into(),
+				force_proxy_type: None,
+				call: elevate(APPLICANT),
+			}),
+			RuntimeCall::Scheduler(pallet_scheduler::Call::schedule_after {
+				after: 1,
+				maybe_periodic: None,
+				priority: 0,
+				call: elevate(APPLICANT),
+			}),
+			propose(elevate(APPLICANT)),
+		];
+		for call in wrapped {
+			assert!(!AllianceProposalFilter::contains(&propose(batched(Box::new(call)))));
+		}
+
+		// The nomination or elevation is also rejected when it is dispatched.
+		assert!(!AllianceProposalFilter::contains(&elevate(APPLICANT)));
+		assert!(AllianceProposalFilter::contains(&elevate(SUB_ACCOUNT)));
+
+		assert_noop!(
+			propose(elevate(APPLICANT)).dispatch(RuntimeOrigin::signed(FELLOW)),
+			frame_system::Error::<Runtime>::CallFiltered,
+		);
+	});
+}
+
+#[test]
+fn ambassador_voices_vote_on_the_track_of_their_rank() {
+	use ambassador::{pallet_ambassador_origins::Origin, AmbassadorReferendaInstance};
+	use pallet_referenda::TracksInfo;
+	use sp_runtime::traits::Convert;
+
+	type Tracks = <Runtime as pallet_referenda::Config<AmbassadorReferendaInstance>>::Tracks;
+	type MinRankOfClass = <Runtime as pallet_ranked_collective::Config<
+		ambassador::AmbassadorCollectiveInstance,
+	>>::MinRankOfClass;
+
+	let voices = [
+		(Origin::Ambassadors, ambassador::ranks::AMBASSADOR_TIER_1),
+		(Origin::AmbassadorsTier2, ambassador::ranks::AMBASSADOR_TIER_2),
+		(Origin::SeniorAmbassadors, ambassador::ranks::SENIOR_AMBASSADOR_TIER_3),
+		(Origin::SeniorAmbassadorsTier4, ambassador::ranks::SENIOR_AMBASSADOR_TIER_4),
+		(Origin::HeadAmbassadors, ambassador::ranks::HEAD_AMBASSADOR_TIER_5),
+		(Origin::HeadAmbassadorsTier6, ambassador::ranks::HEAD_AMBASSADOR_TIER_6),
+		(Origin::HeadAmbassadorsTier7, ambassador::ranks::HEAD_AMBASSADOR_TIER_7),
+		(Origin::MasterAmbassadors, ambassador::ranks::MASTER_AMBASSADOR_TIER_8),
+		(Origin::MasterAmbassadorsTier9, ambassador::ranks::MASTER_AMBASSADOR_TIER_9),
+	];
+	assert_eq!(Tracks::tracks().len(), voices.len());
+
+	for (origin, rank) in voices {
+		assert_eq!(origin.as_voice(), Some(rank));
+		let track = Tracks::track_for(&OriginCaller::AmbassadorOrigins(origin)).unwrap();
+		assert!(Tracks::info(track).is_some());
+		assert_eq!(MinRankOfClass::convert(track), rank);
+	}
+	assert_eq!(MinRankOfClass::convert(0), pallet_ranked_collective::Rank::MAX);
+	assert!(Tracks::track_for(&OriginCaller::system(frame_system::RawOrigin::Root)).is_err());
+}
+
+#[test]
+fn ambassador_origins_promote_two_ranks_below_and_spend_within_limits() {
+	use ambassador::{
+		pallet_ambassador_origins::Origin, ranks, AmbassadorSpender, EnsureCanPromoteTo,
+		HeadAmbassadors,
+	};
+	use fellowship::TreasurySpendLimit;
+	use frame_support::traits::EnsureOrigin;
+
+	let origin = |origin: Origin| RuntimeOrigin::from(origin);
+
+	assert!(EnsureCanPromoteTo::try_origin(origin(Origin::Ambassadors)).is_err());
+	assert!(EnsureCanPromoteTo::try_origin(origin(Origin::AmbassadorsTier2)).is_err());
+	assert_eq!(
+		EnsureCanPromoteTo::try_origin(origin(Origin::SeniorAmbassadors)).ok(),
+		Some(ranks::AMBASSADOR_TIER_1),
+	);
+	assert_eq!(
+		EnsureCanPromoteTo::try_origin(origin(Origin::MasterAmbassadorsTier9)).ok(),
+		Some(ranks::HEAD_AMBASSADOR_TIER_7),
+	);
+	assert!(EnsureCanPromoteTo::try_origin(RuntimeOrigin::signed(FELLOW)).is_err());
+
+	assert!(AmbassadorSpender::try_origin(origin(Origin::AmbassadorsTier2)).is_err());
+	assert_eq!(
+		AmbassadorSpender::try_origin(origin(Origin::SeniorAmbassadors)).ok(),
+		Some(TreasurySpendLimit { dot: 1_000 * UNITS, usdt: 0 }),
+	);
+	assert_eq!(
+		AmbassadorSpender::try_origin(origin(Origin::HeadAmbassadors)).ok(),
+		Some(TreasurySpendLimit { dot: 10_000 * UNITS, usdt: 0 }),
+	);
+	assert_eq!(
+		AmbassadorSpender::try_origin(origin(Origin::MasterAmbassadors)).ok(),
+		Some(TreasurySpendLimit { dot: 50_000 * UNITS, usdt: 0 }),
+	);
+
+	assert_eq!(
+		HeadAmbassadors::try_origin(origin(Origin::HeadAmbassadors)).ok(),
+		Some(ranks::HEAD_AMBASSADOR_TIER_5),
+	);
+	assert!(HeadAmbassadors::try_origin(origin(Origin::HeadAmbassadorsTier6)).is_err());
+}
+
+#[test]
+fn fellowship_spenders_vote_on_spend_tracks_and_spend_within_limits() {
+	use fellowship::{
+		pallet_fellowship_origins::Origin, tracks::constants as tracks,
+		FellowshipReferendaInstance, FellowshipSpender, TreasurySpendLimit, USDT_UNITS,
+	};
+	use frame_support::traits::EnsureOrigin;
+	use pallet_referenda::TracksInfo;
+
+	type Tracks = <Runtime as pallet_referenda::Config<FellowshipReferendaInstance>>::Tracks;
+
+	let spenders = [
+		(
+			Origin::FellowsSpender,
+			tracks::FELLOWS_SPEND,
+			TreasurySpendLimit { dot: 1_000 * UNITS, usdt: 5_000 * USDT_UNITS },
+		),
+		(
+			Origin::ArchitectsSpender,
+			tracks::ARCHITECTS_SPEND,
+			TreasurySpendLimit { dot: 10_000 * UNITS, usdt: 50_000 * USDT_UNITS },
+		),
+		(
+			Origin::MastersSpender,
+			tracks::MASTERS_SPEND,
+			TreasurySpendLimit { dot: 100_000 * UNITS, usdt: 500_000 * USDT_UNITS },
+		),
+	];
+	for (origin, track, limit) in spenders {
+		assert_eq!(Tracks::track_for(&OriginCaller::FellowshipOrigins(origin)), Ok(track));
+		assert!(Tracks::info(track).is_some());
+		assert_eq!(FellowshipSpender::try_origin(RuntimeOrigin::from(origin)).ok(), Some(limit));
+	}
+	assert!(FellowshipSpender::try_origin(RuntimeOrigin::from(Origin::Fellows)).is_err());
+}
+
+#[test]
+fn fellowship_treasury_spends_are_limited_and_voidable() {
+	use fellowship::{pallet_fellowship_origins::Origin, TreasuryAsset, USDT_UNITS};
+	use pallet_fellowship_treasury::{Error, PaymentState, Spends};
+
+	new_test_ext().execute_with(|| {
+		let fellows_spender = || RuntimeOrigin::from(Origin::FellowsSpender);
+
+		assert_noop!(
+			FellowshipTreasury::spend(
+				fellows_spender(),
+				Box::new(TreasuryAsset::Usdt),
+				5_000 * USDT_UNITS + 1,
+				Box::new(APPLICANT),
+			),
+			Error::<Runtime>::InsufficientPermission
+		);
+		assert_noop!(
+			FellowshipTreasury::spend(
+				RuntimeOrigin::signed(FELLOW),
+				Box::new(TreasuryAsset::Dot),
+				1,
+				Box::new(APPLICANT),
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(FellowshipTreasury::spend(
+			fellows_spender(),
+			Box::new(TreasuryAsset::Usdt),
+			5_000 * USDT_UNITS,
+			Box::new(APPLICANT),
+		));
+		assert_ok!(FellowshipTreasury::spend(
+			RuntimeOrigin::root(),
+			Box::new(TreasuryAsset::Dot),
+			1_000_000 * UNITS,
+			Box::new(APPLICANT),
+		));
+		let spend = Spends::<Runtime>::get(0).unwrap();
+		assert_eq!(spend.amount, 5_000 * USDT_UNITS);
+		assert_eq!(spend.status, PaymentState::Pending);
+
+		// A spend can not be voided by anyone but the Fellows or governance.
+		assert_noop!(
+			FellowshipTreasury::void_spend(RuntimeOrigin::signed(FELLOW), 0),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(FellowshipTreasury::void_spend(RuntimeOrigin::from(Origin::Fellows), 0));
+		assert!(Spends::<Runtime>::get(0).is_none());
+
+		// An expired spend can no longer be paid out, and is removed by checking its status.
+		let expire_at = Spends::<Runtime>::get(1).unwrap().expire_at;
+		assert_noop!(
+			FellowshipTreasury::check_status(RuntimeOrigin::signed(FELLOW), 1),
+			Error::<Runtime>::NotAttempted
+		);
+		System::set_block_number(expire_at);
+		assert_noop!(
+			FellowshipTreasury::payout(RuntimeOrigin::signed(FELLOW), 1),
+			Error::<Runtime>::SpendExpired
+		);
+		assert_ok!(FellowshipTreasury::check_status(RuntimeOrigin::signed(FELLOW), 1));
+		assert!(Spends::<Runtime>::get(1).is_none());
+	});
+}
+
+#[test]
+fn ambassador_treasury_spends_dot_within_limits_apart_from_the_fellowship() {
+	use ambassador::{pallet_ambassador_origins::Origin, AmbassadorTreasuryInstance};
+	use fellowship::TreasuryAsset;
+	use pallet_fellowship_treasury::{Error, Spends};
+
+	new_test_ext().execute_with(|| {
+		let senior_ambassadors = || RuntimeOrigin::from(Origin::SeniorAmbassadors);
+
+		assert_noop!(
+			AmbassadorTreasury::spend(
+				senior_ambassadors(),
+				Box::new(TreasuryAsset::Dot),
+				1_000 * UNITS + 1,
+				Box::new(APPLICANT),
+			),
+			Error::<Runtime, AmbassadorTreasuryInstance>::InsufficientPermission
+		);
+		assert_noop!(
+			AmbassadorTreasury::spend(
+				senior_ambassadors(),
+				Box::new(TreasuryAsset::Usdt),
+				1,
+				Box::new(APPLICANT),
+			),
+			Error::<Runtime, AmbassadorTreasuryInstance>::InsufficientPermission
+		);
+
+		assert_ok!(AmbassadorTreasury::spend(
+			senior_ambassadors(),
+			Box::new(TreasuryAsset::Dot),
+			1_000 * UNITS,
+			Box::new(APPLICANT),
+		));
+		assert_eq!(
+			Spends::<Runtime, AmbassadorTreasuryInstance>::get(0).unwrap().amount,
+			1_000 * UNITS
+		);
+		assert!(Spends::<Runtime>::get(0).is_none());
+
+		assert_noop!(
+			AmbassadorTreasury::void_spend(senior_ambassadors(), 0),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(AmbassadorTreasury::void_spend(RuntimeOrigin::from(Origin::HeadAmbassadors), 0));
+		assert!(Spends::<Runtime, AmbassadorTreasuryInstance>::get(0).is_none());
+	});
+}
+
+#[test]
+fn async_backing_config_matches_relay_chain() {
+	use cumulus_pallet_aura_ext::FixedVelocityConsensusHook;
+	use frame_support::traits::Get;
+	use polkadot_runtime_constants::{
+		async_backing::ASYNC_BACKING_PARAMS, time::MILLISECS_PER_BLOCK,
+	};
+
+	trait HookParams {
+		const PARAMS: (u32, u32, u32);
+	}
+	impl<T, const R: u32, const V: u32, const C: u32> HookParams
+		for FixedVelocityConsensusHook<T, R, V, C>
+	{
+		const PARAMS: (u32, u32, u32) = (R, V, C);
+	}
+	type Hook = <Runtime as cumulus_pallet_parachain_system::Config>::ConsensusHook;
+	let (relay_slot_duration, velocity, capacity) = <Hook as HookParams>::PARAMS;
+
+	assert_eq!(relay_slot_duration as u64, MILLISECS_PER_BLOCK);
+	// Blocks are not authored faster than the consensus hook accepts them.
+	assert!(
+		2 * <Runtime as pallet_timestamp::Config>::MinimumPeriod::get() >=
+			(relay_slot_duration / velocity) as u64
+	);
+	// A block built on the oldest allowed relay parent is still not included while the blocks
+	// built on the following relay parents are authored.
+	assert!(capacity >= velocity * (ASYNC_BACKING_PARAMS.allowed_ancestry_len + 1));
+	// The Relay Chain does not back more than `max_candidate_depth + 1` candidates of a para
+	// building on top of each other.
+	assert!(capacity <= ASYNC_BACKING_PARAMS.max_candidate_depth + 1);
+}
+
+mod keeper_tests {
+	use super::*;
+	use fellowship::{pallet_fellowship_origins::Origin, FellowshipCollectiveInstance, USDT_UNITS};
+	use frame_support::{traits::RankedMembers, weights::Weight};
+	use keeper::{Cursor, Sweep};
+	use pallet_core_fellowship::ParamsType;
+
+	type Keeper = keeper::Pallet<Runtime, FellowshipCollectiveInstance>;
+	type Ongoing = keeper::Ongoing<Runtime, FellowshipCollectiveInstance>;
+	type LastCompleted = keeper::LastCompleted<Runtime, FellowshipCollectiveInstance>;
+	type RegistrationPeriod = <Runtime as pallet_salary::Config<
+		fellowship::FellowshipSalaryInstance,
+	>>::RegistrationPeriod;
+
+	/// Members of rank 1, 1 and 3, with the rank 1 members due to be demoted after 10 blocks.
+	const MEMBERS: [(AccountId, u16); 3] = [
+		(AccountId::new([11u8; 32]), 1),
+		(AccountId::new([12u8; 32]), 1),
+		(AccountId::new([13u8; 32]), 3),
+	];
+
+	fn new_keeper_ext() -> sp_io::TestExternalities {
+		let mut ext = new_test_ext();
+		ext.execute_with(|| {
+			for (who, rank) in MEMBERS {
+				assert_ok!(FellowshipCollective::induct(&who));
+				for _ in 0..rank {
+					assert_ok!(FellowshipCollective::promote(&who));
+				}
+				assert_ok!(FellowshipCore::import(RuntimeOrigin::signed(who)));
+			}
+			let mut demotion_period = [1_000 * DAYS; 9];
+			demotion_period[0] = 10;
+			assert_ok!(FellowshipCore::set_params(
+				RuntimeOrigin::from(Origin::Fellows),
+				Box::new(ParamsType {
+					active_salary: [100 * USDT_UNITS; 9],
+					passive_salary: [0; 9],
+					demotion_period,
+					min_promotion_period: [0; 9],
+					offboard_timeout: 1_000 * DAYS,
+				}),
+			));
+			assert_ok!(FellowshipSalary::init(RuntimeOrigin::signed(FELLOW)));
+		});
+		ext
+	}
+
+	fn keeper_event(event: keeper::Event<Runtime, FellowshipCollectiveInstance>) -> RuntimeEvent {
+		RuntimeEvent::FellowshipKeeper(event)
+	}
+
+	fn keeper_events() -> Vec<keeper::Event<Runtime, FellowshipCollectiveInstance>> {
+		System::events()
+			.into_iter()
+			.filter_map(|r| match r.event {
+				RuntimeEvent::FellowshipKeeper(event) => Some(event),
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// Run the keeper at block `now` with the weight `limit`.
+	fn keep(now: BlockNumber, limit: Weight) -> Weight {
+		System::set_block_number(now);
+		Keeper::keep(now, limit)
+	}
+
+	fn member_step() -> Weight {
+		Keeper::overhead_weight().saturating_add(Keeper::step_weight(Sweep::Register))
+	}
+
+	fn sweep_start() -> Weight {
+		Keeper::overhead_weight().saturating_add(Keeper::salary_bump_weight())
+	}
+
+	#[test]
+	fn keeper_sweeps_through_a_salary_cycle() {
+		new_keeper_ext().execute_with(|| {
+			let status = FellowshipSalary::status().unwrap();
+			let registration_ends = status.cycle_start + RegistrationPeriod::get();
+			let cycle_ends = status.cycle_start + FellowshipSalary::cycle_period();
+
+			// All members are inducted into the salary system. They can only register from the
+			// next cycle on, and none of them is due to be demoted yet.
+			keep(1, Weight::MAX);
+			assert_eq!(Ongoing::get(), None);
+			assert_eq!(LastCompleted::get(Sweep::Register), Some(0));
+			assert_eq!(LastCompleted::get(Sweep::Bump), Some(0));
+			for (who, rank) in MEMBERS {
+				assert!(FellowshipSalary::last_active(&who).is_ok());
+				assert_eq!(FellowshipCollective::rank_of(&who), Some(rank));
+			}
+			assert!(!keeper_events().iter().any(|e| matches!(
+				e,
+				keeper::Event::Registered { .. } | keeper::Event::Bumped { .. }
+			)));
+
+			// Nothing is due until the payout period opens.
+			System::reset_events();
+			keep(registration_ends - 1, Weight::MAX);
+			assert!(keeper_events().is_empty());
+
+			// The payouts are claimed, although there is nothing registered to pay out.
+			keep(registration_ends, Weight::MAX);
+			assert_eq!(LastCompleted::get(Sweep::Payout), Some(0));
+			System::assert_has_event(keeper_event(keeper::Event::SweepCompleted {
+				sweep: Sweep::Payout,
+				cycle: 0,
+			}));
+
+			// The next cycle is started once the current one is over.
+			keep(cycle_ends, Weight::MAX);
+			System::assert_has_event(keeper_event(keeper::Event::CycleStarted { cycle: 1 }));
+			assert_eq!(FellowshipSalary::status().unwrap().cycle_index, 1);
+
+			// All members register for the new cycle, and the members whose rank retention expired
+			// are demoted.
+			System::reset_events();
+			keep(cycle_ends, Weight::MAX);
+			for (who, rank) in MEMBERS {
+				System::assert_has_event(keeper_event(keeper::Event::Registered {
+					who: who.clone(),
+					cycle: 1,
+				}));
+				let demoted = rank == 1;
+				assert_eq!(
+					keeper_events().contains(&keeper::Event::Bumped { who: who.clone() }),
+					demoted
+				);
+				assert_eq!(
+					FellowshipCollective::rank_of(&who),
+					Some(if demoted { 0 } else { rank })
+				);
+			}
+			assert_eq!(LastCompleted::get(Sweep::Register), Some(1));
+			assert_eq!(LastCompleted::get(Sweep::Bump), Some(1));
+		});
+	}
+
+	#[test]
+	fn keeper_stays_within_the_weight_limit_and_resumes_at_the_cursor() {
+		new_keeper_ext().execute_with(|| {
+			// Too little weight to start a sweep.
+			assert_eq!(keep(1, Keeper::overhead_weight()), Weight::zero());
+			assert_eq!(Ongoing::get(), None);
+
+			// A sweep is started and two members are visited.
+			let limit = sweep_start().saturating_add(member_step().saturating_mul(2));
+			assert_eq!(keep(1, limit), limit);
+			assert_eq!(Ongoing::get(), Some(Cursor { sweep: Sweep::Register, cycle: 0, next: 2 }));
+			System::assert_has_event(keeper_event(keeper::Event::SweepStarted {
+				sweep: Sweep::Register,
+				cycle: 0,
+			}));
+
+			// Without weight the cursor stays where it is.
+			assert_eq!(keep(1, Weight::zero()), Weight::zero());
+			assert_eq!(Ongoing::get(), Some(Cursor { sweep: Sweep::Register, cycle: 0, next: 2 }));
+
+			// The sweep resumes at the cursor: the last member is visited, and the sweep is
+			// completed at the next step.
+			assert_eq!(keep(1, member_step()), member_step());
+			assert_eq!(Ongoing::get(), Some(Cursor { sweep: Sweep::Register, cycle: 0, next: 3 }));
+			assert!(FellowshipSalary::last_active(&MEMBERS[2].0).is_ok());
+			assert_eq!(keep(1, member_step()), member_step());
+			assert_eq!(Ongoing::get(), None);
+			assert_eq!(LastCompleted::get(Sweep::Register), Some(0));
+		});
+	}
+}
+
+mod fellowship_api_tests {
+	use super::*;
+	use fellowship::{
+		pallet_fellowship_origins::Origin,
+		runtime_api::{self, MemberStatus, TrackAccess},
+		tracks::constants as tracks,
+	};
+	use pallet_core_fellowship::{ParamsType, Wish};
+
+	const MEMBER_A: AccountId = AccountId::new([21u8; 32]);
+	const MEMBER_B: AccountId = AccountId::new([22u8; 32]);
+	const MEMBER_C: AccountId = AccountId::new([23u8; 32]);
+
+	/// Induct `who` into the Fellowship at block `now` and promote them to `rank`.
+	fn induct_fellow(who: AccountId, rank: u16, now: BlockNumber) {
+		System::set_block_number(now);
+		assert_ok!(FellowshipCore::induct(RuntimeOrigin::from(Origin::Members), who.clone()));
+		let promote_to = [Origin::PromoteTo1Dan, Origin::PromoteTo2Dan, Origin::PromoteTo3Dan];
+		for to_rank in 1..=rank {
+			assert_ok!(FellowshipCore::promote(
+				RuntimeOrigin::from(promote_to[to_rank as usize - 1]),
+				who.clone(),
+				to_rank,
+			));
+		}
+	}
+
+	/// Rank 1 may be demoted after 10 blocks and promoted to rank 2 after 20 blocks.
+	fn set_params() {
+		let mut demotion_period = [1_000 * DAYS; 9];
+		demotion_period[0] = 10;
+		let mut min_promotion_period = [0; 9];
+		min_promotion_period[1] = 20;
+		assert_ok!(FellowshipCore::set_params(
+			RuntimeOrigin::from(Origin::Fellows),
+			Box::new(ParamsType {
+				active_salary: [0; 9],
+				passive_salary: [0; 9],
+				demotion_period,
+				min_promotion_period,
+				offboard_timeout: 1_000 * DAYS,
+			}),
+		));
+	}
+
+	#[test]
+	fn member_status_reflects_core_fellowship() {
+		new_test_ext().execute_with(|| {
+			induct_fellow(MEMBER_A, 1, 5);
+			set_params();
+
+			let status = MemberStatus {
+				rank: 1,
+				is_active: true,
+				last_promotion: 5,
+				last_proof: 5,
+				demotable_from: Some(15),
+				promotable_from: Some(25),
+				has_evidence: false,
+			};
+			assert_eq!(runtime_api::member_status(MEMBER_A), Some(status.clone()));
+			assert_eq!(runtime_api::member_status(MEMBER_B), None);
+
+			assert_ok!(FellowshipCore::submit_evidence(
+				RuntimeOrigin::signed(MEMBER_A),
+				Wish::Retention,
+				b"evidence".to_vec().try_into().unwrap(),
+			));
+			assert_ok!(FellowshipCore::set_active(RuntimeOrigin::signed(MEMBER_A), false));
+			assert_eq!(
+				runtime_api::member_status(MEMBER_A),
+				Some(MemberStatus { is_active: false, has_evidence: true, ..status })
+			);
+		});
+	}
+
+	#[test]
+	fn members_at_risk_are_queried_in_pages() {
+		new_test_ext().execute_with(|| {
+			induct_fellow(MEMBER_A, 1, 1);
+			induct_fellow(MEMBER_B, 3, 1);
+			induct_fellow(MEMBER_C, 1, 10);
+			set_params();
+
+			assert_eq!(runtime_api::members_at_risk(10, 0, 10), (vec![], None));
+			assert_eq!(runtime_api::members_at_risk(11, 0, 10), (vec![(MEMBER_A, 1)], None));
+			assert_eq!(runtime_api::members_at_risk(11, 0, 2), (vec![(MEMBER_A, 1)], Some(2)));
+			assert_eq!(runtime_api::members_at_risk(20, 2, 2), (vec![(MEMBER_C, 1)], None));
+			assert_eq!(
+				runtime_api::members_at_risk(20, 0, u32::MAX),
+				(vec![(MEMBER_A, 1), (MEMBER_C, 1)], None)
+			);
+			assert_eq!(runtime_api::members_at_risk(20, 3, 10), (vec![], None));
+		});
+	}
+
+	#[test]
+	fn members_at_risk_checks_a_bounded_number_of_members() {
+		new_test_ext().execute_with(|| {
+			let count = runtime_api::MAX_MEMBERS_PER_QUERY + 1;
+			for i in 0..count {
+				let mut id = [30u8; 32];
+				id[..4].copy_from_slice(&i.to_le_bytes());
+				assert_ok!(FellowshipCore::induct(
+					RuntimeOrigin::from(Origin::Members),
+					AccountId::new(id),
+				));
+			}
+
+			let (_, next) = runtime_api::members_at_risk(1, 0, u32::MAX);
+			assert_eq!(next, Some(runtime_api::MAX_MEMBERS_PER_QUERY));
+			let (_, next) = runtime_api::members_at_risk(1, runtime_api::MAX_MEMBERS_PER_QUERY, 10);
+			assert_eq!(next, None);
+		});
+	}
+
+	#[test]
+	fn tracks_of_rank_follow_the_track_and_submission_ranks() {
+		assert!(runtime_api::tracks_of_rank(0).is_empty());
+		assert_eq!(
+			runtime_api::tracks_of_rank(1),
+			vec![TrackAccess {
+				track: tracks::MEMBERS,
+				name: b"members".to_vec(),
+				can_vote: true,
+				can_propose: false,
+			}]
+		);
+
+		// From the rank allowed to submit proposals on, members may propose on all tracks.
+		let fellows = runtime_api::tracks_of_rank(fellowship::MIN_RANK_TO_SUBMIT);
+		assert!(fellows.iter().all(|access| access.can_propose));
+		let voting = |track| fellows.iter().find(|access| access.track == track).unwrap().can_vote;
+		assert!(voting(tracks::FELLOWS));
+		assert!(voting(tracks::FELLOWS_SPEND));
+		assert!(voting(tracks::RETAIN_AT_1DAN));
+		assert!(!voting(tracks::ARCHITECTS));
+		assert!(!voting(tracks::ARCHITECTS_SPEND));
+	}
+}
@@ -19,10 +19,12 @@ pub mod cumulus_pallet_xcmp_queue;
 pub mod extrinsic_weights;
 pub mod frame_system;
 pub mod pallet_alliance;
+pub mod pallet_alliance_identity;
 pub mod pallet_balances;
 pub mod pallet_collator_selection;
 pub mod pallet_collective;
 pub mod pallet_core_fellowship;
+pub mod pallet_fellowship_treasury;
 pub mod pallet_multisig;
 pub mod pallet_preimage;
 pub mod pallet_proxy;
@@ -0,0 +1,57 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_alliance_identity`.
+//!
+//! These weights were not produced by the benchmark CLI yet. They take the storage accesses of
+//! the benchmarks in `alliance` and the measured execution time of `pallet_alliance`'s
+//! `add_unscrupulous_items` with one item, which also writes a single bounded value. Regenerate
+//! them with:
+//!
+//! ```text
+//! polkadot-parachain benchmark pallet --chain=collectives-polkadot-dev \
+//!     --pallet=pallet_alliance_identity --extrinsic=* \
+//!     --output=system-parachains/collectives/collectives-polkadot/src/weights
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_alliance_identity`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> crate::alliance::WeightInfo for WeightInfo<T> {
+	/// Storage: `AllianceIdentity::Identities` (r:1 w:1)
+	/// Proof: `AllianceIdentity::Identities` (`max_values`: None, `max_size`: Some(345), added: 2820, mode: `MaxEncodedLen`)
+	fn note_identity() -> Weight {
+		Weight::from_parts(14_163_000, 0)
+			.saturating_add(Weight::from_parts(0, 3810))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `AllianceIdentity::Identities` (r:1 w:1)
+	/// Proof: `AllianceIdentity::Identities` (`max_values`: None, `max_size`: Some(345), added: 2820, mode: `MaxEncodedLen`)
+	fn clear_identity() -> Weight {
+		Weight::from_parts(12_489_000, 0)
+			.saturating_add(Weight::from_parts(0, 3810))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
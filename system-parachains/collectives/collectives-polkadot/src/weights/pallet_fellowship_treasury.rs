@@ -0,0 +1,80 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_fellowship_treasury`.
+//!
+//! These weights were not produced by the benchmark CLI yet. They take the storage accesses of
+//! the benchmarks in `fellowship::treasury` and the measured execution time of the closest
+//! `pallet_salary` calls, which pay over XCM the same way: `payout` for `payout`, `check_payment`
+//! for `check_status` and `init` for `spend` and `void_spend`. Regenerate them with:
+//!
+//! ```text
+//! polkadot-parachain benchmark pallet --chain=collectives-polkadot-dev \
+//!     --pallet=pallet_fellowship_treasury --extrinsic=* \
+//!     --output=system-parachains/collectives/collectives-polkadot/src/weights
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_fellowship_treasury`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> crate::fellowship::treasury::WeightInfo for WeightInfo<T> {
+	/// Storage: `FellowshipTreasury::SpendCount` (r:1 w:1)
+	/// Storage: `FellowshipTreasury::Spends` (r:0 w:1)
+	fn spend() -> Weight {
+		Weight::from_parts(10_384_000, 0)
+			.saturating_add(Weight::from_parts(0, 1541))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `FellowshipTreasury::Spends` (r:1 w:1)
+	/// Storage: `ParachainInfo::ParachainId` (r:1 w:0)
+	/// Storage: `PolkadotXcm::QueryCounter` (r:1 w:1)
+	/// Storage: `PolkadotXcm::SupportedVersion` (r:1 w:0)
+	/// Storage: `PolkadotXcm::VersionDiscoveryQueue` (r:1 w:1)
+	/// Storage: `PolkadotXcm::SafeXcmVersion` (r:1 w:0)
+	/// Storage: `ParachainSystem::RelevantMessagingState` (r:1 w:0)
+	/// Storage: `XcmpQueue::OutboundXcmpStatus` (r:1 w:1)
+	/// Storage: `XcmpQueue::OutboundXcmpMessages` (r:0 w:1)
+	/// Storage: `PolkadotXcm::Queries` (r:0 w:1)
+	fn payout() -> Weight {
+		Weight::from_parts(56_599_000, 0)
+			.saturating_add(Weight::from_parts(0, 4168))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	/// Storage: `FellowshipTreasury::Spends` (r:1 w:1)
+	/// Storage: `PolkadotXcm::Queries` (r:1 w:1)
+	fn check_status() -> Weight {
+		Weight::from_parts(23_061_000, 0)
+			.saturating_add(Weight::from_parts(0, 3943))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `FellowshipTreasury::Spends` (r:1 w:1)
+	fn void_spend() -> Weight {
+		Weight::from_parts(10_384_000, 0)
+			.saturating_add(Weight::from_parts(0, 3943))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
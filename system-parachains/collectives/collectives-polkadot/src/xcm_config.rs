@@ -14,8 +14,9 @@
 // limitations under the License.
 
 use super::{
-	AccountId, AllPalletsWithSystem, Balances, Fellows, ParachainInfo, ParachainSystem,
-	PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
+	pallet_alliance_identity, AccountId, AllPalletsWithSystem, Balances, Fellows, ParachainInfo,
+	ParachainSystem, PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin,
+	TransactionByteFee, WeightToFee, XcmpQueue,
 };
 use frame_support::{
 	match_types, parameter_types,
@@ -26,6 +27,7 @@ use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
 use parachains_common::{impls::ToStakingPot, xcm_config::ConcreteNativeAssetFrom};
 use polkadot_parachain_primitives::primitives::Sibling;
+pub use system_parachains_constants::xcm_config::{polkadot::BaseDeliveryFee, WaivedLocations};
 use xcm::latest::prelude::*;
 use xcm_builder::{
 	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
@@ -34,7 +36,7 @@ use xcm_builder::{
 	OriginToPluralityVoice, ParentAsSuperuser, ParentIsPreset, RelayChainAsNative,
 	SiblingParachainAsNative, SiblingParachainConvertsVia, SignedAccountId32AsNative,
 	SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId,
-	UsingComponents, WithComputedOrigin, WithUniqueTopic,
+	UsingComponents, WithComputedOrigin, WithUniqueTopic, XcmFeeManagerFromComponents,
 };
 use xcm_executor::{traits::WithOriginFilter, XcmExecutor};
 
@@ -181,6 +183,9 @@ impl Contains<RuntimeCall> for SafeCallFilter {
 				pallet_collective::Call::vote { .. } |
 					pallet_collective::Call::disapprove_proposal { .. } |
 					pallet_collective::Call::close { .. },
+			) | RuntimeCall::AllianceIdentity(
+				pallet_alliance_identity::Call::note_identity { .. } |
+					pallet_alliance_identity::Call::clear_identity { .. },
 			) | RuntimeCall::FellowshipCollective(
 				pallet_ranked_collective::Call::add_member { .. } |
 					pallet_ranked_collective::Call::promote_member { .. } |
@@ -196,6 +201,21 @@ impl Contains<RuntimeCall> for SafeCallFilter {
 					pallet_core_fellowship::Call::offboard { .. } |
 					pallet_core_fellowship::Call::submit_evidence { .. } |
 					pallet_core_fellowship::Call::import { .. },
+			) | RuntimeCall::AmbassadorCollective(
+				pallet_ranked_collective::Call::add_member { .. } |
+					pallet_ranked_collective::Call::promote_member { .. } |
+					pallet_ranked_collective::Call::demote_member { .. } |
+					pallet_ranked_collective::Call::remove_member { .. },
+			) | RuntimeCall::AmbassadorCore(
+				pallet_core_fellowship::Call::bump { .. } |
+					pallet_core_fellowship::Call::set_params { .. } |
+					pallet_core_fellowship::Call::set_active { .. } |
+					pallet_core_fellowship::Call::approve { .. } |
+					pallet_core_fellowship::Call::induct { .. } |
+					pallet_core_fellowship::Call::promote { .. } |
+					pallet_core_fellowship::Call::offboard { .. } |
+					pallet_core_fellowship::Call::submit_evidence { .. } |
+					pallet_core_fellowship::Call::import { .. },
 			)
 		)
 	}
@@ -251,7 +271,8 @@ impl xcm_executor::Config for XcmConfig {
 	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
 	type AssetLocker = ();
 	type AssetExchanger = ();
-	type FeeManager = ();
+	// Delivery fees are burnt, except for governance and the system parachains.
+	type FeeManager = XcmFeeManagerFromComponents<WaivedLocations, ()>;
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
 	type CallDispatcher = WithOriginFilter<SafeCallFilter>;
@@ -263,11 +284,24 @@ impl xcm_executor::Config for XcmConfig {
 /// Forms the basis for local origins sending/executing XCMs.
 pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
 
+parameter_types! {
+	/// The asset ID for the asset that we use to pay for message delivery fees.
+	pub FeeAssetId: AssetId = Concrete(DotLocation::get());
+}
+
+/// The price of delivering a message to the Relay Chain.
+pub type UmpDeliveryPrice = system_parachains_constants::xcm_config::UmpDeliveryPrice<
+	Runtime,
+	FeeAssetId,
+	BaseDeliveryFee,
+	TransactionByteFee,
+>;
+
 /// The means for routing XCM messages which are not for local execution into the right message
 /// queues.
 pub type XcmRouter = WithUniqueTopic<(
 	// Two routers - use UMP to communicate with the relay chain:
-	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, PolkadotXcm, ()>,
+	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, PolkadotXcm, UmpDeliveryPrice>,
 	// ..and XCMP to communicate with the sibling chains.
 	XcmpQueue,
 )>;
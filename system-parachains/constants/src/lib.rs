@@ -0,0 +1,191 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Constants and delivery fees shared by the system parachains, which are authored with
+//! asynchronous backing.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight};
+
+/// We allow for 2 seconds of compute with a 12 second average block time, as the execution of a
+/// block may span more than one relay chain block with asynchronous backing.
+pub const MAXIMUM_BLOCK_WEIGHT: Weight = Weight::from_parts(
+	WEIGHT_REF_TIME_PER_SECOND.saturating_mul(2),
+	primitives::MAX_POV_SIZE as u64,
+);
+
+/// Consensus-related.
+pub mod consensus {
+	/// The slot duration of the Relay Chain in milliseconds.
+	pub const RELAY_CHAIN_SLOT_DURATION_MILLIS: u32 = 6000;
+
+	/// The maximum number of blocks a system parachain builds per Relay Chain block.
+	pub const BLOCK_PROCESSING_VELOCITY: u32 = 1;
+
+	/// The maximum number of blocks of a system parachain which are built but not yet included
+	/// in the Relay Chain. Must cover the `allowed_ancestry_len` and stay within the
+	/// `max_candidate_depth` of the asynchronous backing parameters of the Relay Chains.
+	pub const UNINCLUDED_SEGMENT_CAPACITY: u32 = 3;
+}
+
+/// The delivery fees of the messages sent by the system parachains.
+pub mod xcm_config {
+	use codec::Encode;
+	use core::marker::PhantomData;
+	use cumulus_pallet_parachain_system::relay_state_snapshot::RelayDispatchQueueRemainingCapacity;
+	use cumulus_primitives_utility::PriceForParentDelivery;
+	use frame_support::{
+		match_types,
+		traits::{Contains, Get},
+	};
+	use sp_runtime::{FixedPointNumber, FixedU128};
+	use xcm::latest::prelude::*;
+
+	/// The base fee of delivering a message from a Polkadot system parachain.
+	pub mod polkadot {
+		use parachains_common::{polkadot::currency::CENTS, Balance};
+
+		frame_support::parameter_types! {
+			pub const BaseDeliveryFee: Balance = CENTS.saturating_mul(3);
+		}
+	}
+
+	/// The base fee of delivering a message from a Kusama system parachain.
+	pub mod kusama {
+		use parachains_common::{kusama::currency::CENTS, Balance};
+
+		frame_support::parameter_types! {
+			pub const BaseDeliveryFee: Balance = CENTS.saturating_mul(3);
+		}
+	}
+
+	match_types! {
+		/// Locations which don't pay delivery fees: governance, i.e. the Relay Chain and its
+		/// pluralities as well as the Root origin of this chain, and the system parachains.
+		pub type WaivedLocations: impl Contains<MultiLocation> = {
+			MultiLocation { parents: 0, interior: Here } |
+			MultiLocation { parents: 1, interior: Here } |
+			MultiLocation { parents: 1, interior: X1(Plurality { .. }) } |
+			MultiLocation { parents: 1, interior: X1(Parachain(1000..=1999)) }
+		};
+	}
+
+	/// The price of delivering a message to the Relay Chain.
+	///
+	/// `BaseFee` plus `ByteFee` per byte of the message, in `FeeAssetId`, multiplied by
+	/// [`upward_queue_fee_factor`], such that the price tracks the pressure on the upward message
+	/// queue of this parachain on the Relay Chain.
+	pub struct UmpDeliveryPrice<Runtime, FeeAssetId, BaseFee, ByteFee>(
+		PhantomData<(Runtime, FeeAssetId, BaseFee, ByteFee)>,
+	);
+	impl<
+			Runtime: cumulus_pallet_parachain_system::Config,
+			FeeAssetId: Get<AssetId>,
+			BaseFee: Get<u128>,
+			ByteFee: Get<u128>,
+		> PriceForParentDelivery for UmpDeliveryPrice<Runtime, FeeAssetId, BaseFee, ByteFee>
+	{
+		fn price_for_parent_delivery(message: &Xcm<()>) -> MultiAssets {
+			let message_fee = ByteFee::get().saturating_mul(message.encoded_size() as u128);
+			let amount = upward_queue_fee_factor::<Runtime>()
+				.saturating_mul_int(BaseFee::get().saturating_add(message_fee));
+			(FeeAssetId::get(), amount).into()
+		}
+	}
+
+	/// The [`fee_factor`] of the upward message queue of this parachain on the Relay Chain, as
+	/// reported by the Relay Chain with the validation data of this block. 1 without a report.
+	pub fn upward_queue_fee_factor<Runtime: cumulus_pallet_parachain_system::Config>() -> FixedU128
+	{
+		type ParachainSystem<T> = cumulus_pallet_parachain_system::Pallet<T>;
+		match (
+			ParachainSystem::<Runtime>::relevant_messaging_state(),
+			ParachainSystem::<Runtime>::host_configuration(),
+		) {
+			(Some(state), Some(config)) => fee_factor(
+				state.relay_dispatch_queue_remaining_capacity,
+				config.max_upward_queue_count,
+				config.max_upward_queue_size,
+			),
+			_ => FixedU128::from_u32(1),
+		}
+	}
+
+	/// `1 + 9 * fill^2`, where `fill` is how full a queue with the `remaining` capacity out of
+	/// `max_count` messages and `max_size` bytes is, by message count or size, whichever is
+	/// fuller. The factor is 1 for an empty queue and 10 for a full one.
+	pub fn fee_factor(
+		remaining: RelayDispatchQueueRemainingCapacity,
+		max_count: u32,
+		max_size: u32,
+	) -> FixedU128 {
+		let fill = |remaining: u32, max: u32| {
+			FixedU128::saturating_from_rational(max.saturating_sub(remaining), max.max(1))
+		};
+		let fill = fill(remaining.remaining_count, max_count)
+			.max(fill(remaining.remaining_size, max_size));
+		FixedU128::from_u32(1)
+			.saturating_add(FixedU128::from_u32(9).saturating_mul(fill.saturating_mul(fill)))
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+
+		fn factor(remaining_count: u32, remaining_size: u32) -> FixedU128 {
+			fee_factor(
+				RelayDispatchQueueRemainingCapacity { remaining_count, remaining_size },
+				100,
+				1_000,
+			)
+		}
+
+		#[test]
+		fn fee_factor_grows_with_the_square_of_the_fill() {
+			assert_eq!(factor(100, 1_000), FixedU128::from_u32(1));
+			assert_eq!(factor(50, 1_000), FixedU128::from_rational(13, 4));
+			assert_eq!(factor(100, 500), FixedU128::from_rational(13, 4));
+			assert_eq!(factor(0, 1_000), FixedU128::from_u32(10));
+		}
+
+		#[test]
+		fn fee_factor_follows_the_fuller_dimension() {
+			assert_eq!(factor(50, 0), FixedU128::from_u32(10));
+			assert_eq!(factor(0, 500), FixedU128::from_u32(10));
+			assert_eq!(factor(90, 500), factor(100, 500));
+		}
+
+		#[test]
+		fn fee_factor_handles_degenerate_configurations() {
+			assert_eq!(
+				fee_factor(
+					RelayDispatchQueueRemainingCapacity { remaining_count: 0, remaining_size: 0 },
+					0,
+					0
+				),
+				FixedU128::from_u32(1),
+			);
+			assert_eq!(
+				fee_factor(
+					RelayDispatchQueueRemainingCapacity { remaining_count: 200, remaining_size: 0 },
+					100,
+					0
+				),
+				FixedU128::from_u32(1),
+			);
+		}
+	}
+}